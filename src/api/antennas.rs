@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// A saved filter collecting matching notes as they arrive.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Antenna {
+    pub id: String,
+    pub name: String,
+    /// Whether it got notes we haven't read yet.
+    #[serde(default)]
+    pub has_unread_note: bool,
+}
+
+impl Client {
+    /// Fetches our antennas.
+    pub fn antennas(&self) -> Result<Vec<Antenna>, Error> {
+        self.request("antennas/list", json!({}))
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// A topic-based space that notes can be posted in.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Channel {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub users_count: u64,
+    #[serde(default)]
+    pub notes_count: u64,
+    /// Whether we follow it, only included when signed in.
+    #[serde(default)]
+    pub is_following: bool,
+}
+
+/// Which channels to list.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChannelListing {
+    Featured,
+    Followed,
+    Owned,
+}
+
+impl Client {
+    /// Fetches a listing of channels.
+    pub fn channels(&self, listing: ChannelListing) -> Result<Vec<Channel>, Error> {
+        let endpoint = match listing {
+            ChannelListing::Featured => "channels/featured",
+            ChannelListing::Followed => "channels/followed",
+            ChannelListing::Owned => "channels/owned",
+        };
+        self.request(endpoint, json!({ "limit": 50 }))
+    }
+
+    /// Finds channels by name or description.
+    pub fn search_channels(&self, query: &str) -> Result<Vec<Channel>, Error> {
+        self.request(
+            "channels/search",
+            json!({ "query": query, "type": "nameAndDescription", "limit": 50 }),
+        )
+    }
+
+    /// Follows or unfollows a channel.
+    pub fn set_channel_following(&self, channel_id: &str, follow: bool) -> Result<(), Error> {
+        let endpoint = if follow {
+            "channels/follow"
+        } else {
+            "channels/unfollow"
+        };
+        self.request_empty(endpoint, json!({ "channelId": channel_id }))
+    }
+}
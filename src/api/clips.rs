@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// A named collection of notes.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Clip {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+impl Client {
+    /// Fetches our clips.
+    pub fn clips(&self) -> Result<Vec<Clip>, Error> {
+        self.request("clips/list", json!({ "limit": 100 }))
+    }
+
+    /// Fetches our clips that contain a note.
+    pub fn note_clips(&self, note_id: &str) -> Result<Vec<Clip>, Error> {
+        self.request("notes/clips", json!({ "noteId": note_id }))
+    }
+
+    pub fn create_clip(&self, name: &str) -> Result<Clip, Error> {
+        self.request("clips/create", json!({ "name": name }))
+    }
+
+    pub fn rename_clip(&self, clip_id: &str, name: &str) -> Result<Clip, Error> {
+        self.request("clips/update", json!({ "clipId": clip_id, "name": name }))
+    }
+
+    pub fn delete_clip(&self, clip_id: &str) -> Result<(), Error> {
+        self.request_empty("clips/delete", json!({ "clipId": clip_id }))
+    }
+
+    /// Adds a note to a clip, or removes it.
+    pub fn set_clipped(&self, clip_id: &str, note_id: &str, clipped: bool) -> Result<(), Error> {
+        let endpoint = if clipped {
+            "clips/add-note"
+        } else {
+            "clips/remove-note"
+        };
+        self.request_empty(endpoint, json!({ "clipId": clip_id, "noteId": note_id }))
+    }
+}
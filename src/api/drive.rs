@@ -0,0 +1,210 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{check_response, Client, Error};
+
+/// A file stored in our Drive.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveFile {
+    pub id: String,
+    pub name: String,
+    /// The MIME type, e.g. `image/png`.
+    #[serde(rename = "type")]
+    pub file_type: String,
+    /// The size in bytes.
+    pub size: u64,
+    #[serde(default)]
+    pub is_sensitive: bool,
+    /// The alt text, if any.
+    pub comment: Option<String>,
+    pub folder_id: Option<String>,
+}
+
+/// A folder in our Drive.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveFolder {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+}
+
+/// How much of our Drive is used, in bytes.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveUsage {
+    pub capacity: u64,
+    pub usage: u64,
+}
+
+impl Client {
+    /// Fetches how much of our Drive is used.
+    pub fn drive_usage(&self) -> Result<DriveUsage, Error> {
+        self.request("drive", json!({}))
+    }
+
+    /// Fetches the folders in a folder, or at the root if `folder_id` is `None`.
+    pub fn drive_folders(&self, folder_id: Option<&str>) -> Result<Vec<DriveFolder>, Error> {
+        self.request(
+            "drive/folders",
+            json!({ "folderId": folder_id, "limit": 100 }),
+        )
+    }
+
+    /// Fetches the files in a folder, or at the root if `folder_id` is `None`.
+    pub fn drive_files(&self, folder_id: Option<&str>) -> Result<Vec<DriveFile>, Error> {
+        self.request(
+            "drive/files",
+            json!({ "folderId": folder_id, "limit": 100 }),
+        )
+    }
+
+    pub fn create_folder(&self, name: &str, parent_id: Option<&str>) -> Result<DriveFolder, Error> {
+        self.request(
+            "drive/folders/create",
+            json!({ "name": name, "parentId": parent_id }),
+        )
+    }
+
+    pub fn rename_folder(&self, folder_id: &str, name: &str) -> Result<DriveFolder, Error> {
+        self.update_folder(folder_id, json!({ "name": name }))
+    }
+
+    /// Moves a folder into another, or to the root if `parent_id` is `None`.
+    pub fn move_folder(
+        &self,
+        folder_id: &str,
+        parent_id: Option<&str>,
+    ) -> Result<DriveFolder, Error> {
+        self.update_folder(folder_id, json!({ "parentId": parent_id }))
+    }
+
+    /// Deletes a folder, which must be empty.
+    pub fn delete_folder(&self, folder_id: &str) -> Result<(), Error> {
+        self.request_empty("drive/folders/delete", json!({ "folderId": folder_id }))
+    }
+
+    pub fn rename_file(&self, file_id: &str, name: &str) -> Result<DriveFile, Error> {
+        self.update_file(file_id, json!({ "name": name }))
+    }
+
+    /// Moves a file into a folder, or to the root if `folder_id` is `None`.
+    pub fn move_file(&self, file_id: &str, folder_id: Option<&str>) -> Result<DriveFile, Error> {
+        self.update_file(file_id, json!({ "folderId": folder_id }))
+    }
+
+    /// Sets a file's alt text, removing it if `None`, and whether it's sensitive.
+    pub fn describe_file(
+        &self,
+        file_id: &str,
+        alt: Option<&str>,
+        sensitive: bool,
+    ) -> Result<DriveFile, Error> {
+        self.update_file(file_id, json!({ "comment": alt, "isSensitive": sensitive }))
+    }
+
+    pub fn delete_file(&self, file_id: &str) -> Result<(), Error> {
+        self.request_empty("drive/files/delete", json!({ "fileId": file_id }))
+    }
+
+    /// Uploads a local file into a folder, or at the root if `folder_id` is `None`.
+    ///
+    /// `on_progress` is called with how many bytes of the file were sent so far.
+    pub fn upload_file(
+        &self,
+        path: &Path,
+        folder_id: Option<&str>,
+        on_progress: impl FnMut(u64),
+    ) -> Result<DriveFile, Error> {
+        let file = File::open(path).map_err(|err| Error::Transport(err.to_string()))?;
+        let size = file
+            .metadata()
+            .map_err(|err| Error::Transport(err.to_string()))?
+            .len();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().replace('"', "_"))
+            .unwrap_or_else(|| "file".into());
+
+        let boundary = format!(
+            "keytan-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos())
+        );
+        let mut head = String::new();
+        let fields = [
+            ("i", self.token.clone()),
+            ("folderId", folder_id.map(str::to_owned)),
+            ("name", Some(name.clone())),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                head.push_str(&format!(
+                    "--{boundary}\r\nContent-Disposition: form-data; name=\"{field}\"\r\n\r\n{value}\r\n"
+                ));
+            }
+        }
+        head.push_str(&format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{name}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        ));
+        let tail = format!("\r\n--{boundary}--\r\n");
+        let length = head.len() as u64 + size + tail.len() as u64;
+
+        let body = io::Cursor::new(head)
+            .chain(Progress {
+                inner: file,
+                sent: 0,
+                on_progress,
+            })
+            .chain(io::Cursor::new(tail));
+        let url = format!("https://{}/api/drive/files/create", self.instance);
+        let request = self
+            .agent
+            .post(&url)
+            .set(
+                "Content-Type",
+                &format!("multipart/form-data; boundary={boundary}"),
+            )
+            .set("Content-Length", &length.to_string());
+
+        check_response(request.send(body))?
+            .into_json()
+            .map_err(|err| Error::Decode(err.to_string()))
+    }
+
+    fn update_folder(&self, folder_id: &str, mut changes: Value) -> Result<DriveFolder, Error> {
+        changes["folderId"] = folder_id.into();
+        self.request("drive/folders/update", changes)
+    }
+
+    fn update_file(&self, file_id: &str, mut changes: Value) -> Result<DriveFile, Error> {
+        changes["fileId"] = file_id.into();
+        self.request("drive/files/update", changes)
+    }
+}
+
+/// Reports how much of a reader was read.
+struct Progress<R, F> {
+    inner: R,
+    sent: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64)> Read for Progress<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.sent += read as u64;
+        (self.on_progress)(self.sent);
+        Ok(read)
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// A custom emoji of the instance.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Emoji {
+    /// The name used between colons, e.g. `blobcat`.
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Emojis {
+    emojis: Vec<Emoji>,
+}
+
+impl Client {
+    /// Returns the instance's custom emoji, fetching them only the first time.
+    pub fn custom_emojis(&self) -> Result<Vec<Emoji>, Error> {
+        if let Some(emojis) = self.emojis.read().as_ref() {
+            return Ok(emojis.clone());
+        }
+
+        let emojis = self.request::<Emojis>("emojis", json!({}))?.emojis;
+        *self.emojis.write() = Some(emojis.clone());
+        Ok(emojis)
+    }
+}
@@ -0,0 +1,13 @@
+use serde_json::json;
+
+use super::{Client, Error};
+
+impl Client {
+    /// Finds hashtags starting with `query`, without the leading `#`.
+    pub fn search_hashtags(&self, query: &str) -> Result<Vec<String>, Error> {
+        self.request(
+            "hashtags/search",
+            json!({ "query": query.trim_start_matches('#'), "limit": 30 }),
+        )
+    }
+}
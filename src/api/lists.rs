@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// A list of users whose notes can be read as a timeline.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserList {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub user_ids: Vec<String>,
+}
+
+impl Client {
+    /// Fetches our user lists.
+    pub fn user_lists(&self) -> Result<Vec<UserList>, Error> {
+        self.request("users/lists/list", json!({}))
+    }
+}
@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Client, Error};
+
+/// Information about the instance.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    #[serde(default)]
+    pub policies: Policies,
+    /// The largest file that can be uploaded, in bytes, on older instances.
+    pub max_file_size: Option<u64>,
+}
+
+impl Meta {
+    /// The largest file that can be uploaded, in bytes, if known.
+    pub fn max_upload_size(&self) -> Option<u64> {
+        self.policies
+            .max_file_size_mb
+            .map(|mb| mb * 1024 * 1024)
+            .or(self.max_file_size)
+    }
+}
+
+/// What users are allowed to do by default.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policies {
+    pub can_search_notes: bool,
+    /// The largest file that can be uploaded, in megabytes.
+    pub max_file_size_mb: Option<u64>,
+}
+
+impl Default for Policies {
+    fn default() -> Self {
+        Self {
+            can_search_notes: true,
+            max_file_size_mb: None,
+        }
+    }
+}
+
+impl Client {
+    /// Fetches the instance's information.
+    pub fn meta(&self) -> Result<Meta, Error> {
+        self.request("meta", json!({ "detail": true }))
+    }
+}
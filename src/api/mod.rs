@@ -0,0 +1,124 @@
+pub mod antennas;
+pub mod channels;
+pub mod clips;
+pub mod drive;
+pub mod emojis;
+pub mod hashtags;
+pub mod lists;
+pub mod meta;
+pub mod models;
+pub mod notes;
+pub mod notifications;
+pub mod store;
+pub mod streaming;
+pub mod timelines;
+pub mod users;
+
+use std::{fmt, sync::Arc};
+
+use emojis::Emoji;
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+use store::Store;
+
+/// A blocking client for a Misskey instance's API.
+#[derive(Clone)]
+pub struct Client {
+    agent: ureq::Agent,
+    /// The instance's host, e.g. `misskey.io`.
+    pub instance: String,
+    /// The access token to authenticate with, if any.
+    token: Option<String>,
+    /// Every note fetched through this client (or its clones).
+    pub store: Arc<RwLock<Store>>,
+    /// The instance's custom emoji, once fetched.
+    emojis: Arc<RwLock<Option<Vec<Emoji>>>>,
+}
+
+impl Client {
+    pub fn new(instance: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            agent: ureq::Agent::new(),
+            instance: instance.into(),
+            token,
+            store: Arc::default(),
+            emojis: Arc::default(),
+        }
+    }
+
+    /// Calls an API `endpoint` with a JSON object `body` and deserializes the response.
+    pub fn request<T: DeserializeOwned>(&self, endpoint: &str, body: Value) -> Result<T, Error> {
+        let response = self.send(endpoint, body)?;
+
+        response
+            .into_json()
+            .map_err(|err| Error::Decode(err.to_string()))
+    }
+
+    /// Calls an API `endpoint` that doesn't return anything (`204 No Content`).
+    pub fn request_empty(&self, endpoint: &str, body: Value) -> Result<(), Error> {
+        self.send(endpoint, body).map(|_| ())
+    }
+
+    fn send(&self, endpoint: &str, body: Value) -> Result<ureq::Response, Error> {
+        let mut body = match body {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        if let Some(token) = &self.token {
+            body.insert("i".into(), Value::String(token.clone()));
+        }
+
+        let url = format!("https://{}/api/{}", self.instance, endpoint);
+        check_response(self.agent.post(&url).send_json(Value::Object(body)))
+    }
+}
+
+/// Turns error statuses into the instance's error, if it sent one.
+fn check_response(result: Result<ureq::Response, ureq::Error>) -> Result<ureq::Response, Error> {
+    match result {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(status, response)) => {
+            Err(match response.into_json::<ErrorResponse>() {
+                Ok(ErrorResponse { error }) => Error::Api {
+                    code: error.code,
+                    message: error.message,
+                },
+                Err(_) => Error::Transport(format!("HTTP {status}")),
+            })
+        }
+        Err(err) => Err(Error::Transport(err.to_string())),
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The instance rejected the request.
+    Api { code: String, message: String },
+    /// The request never got a proper response.
+    Transport(String),
+    /// The response didn't have the expected shape.
+    Decode(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api { code, message } => write!(f, "{message} ({code})"),
+            Self::Transport(err) => write!(f, "request failed: {err}"),
+            Self::Decode(err) => write!(f, "unexpected response: {err}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+}
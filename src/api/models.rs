@@ -0,0 +1,183 @@
+//! The notes and users the API returns, shared by every screen through the `Store`.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub use super::{channels::Channel, drive::DriveFile};
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: String,
+    /// The display name, if the user has set one.
+    pub name: Option<String>,
+    pub username: String,
+    /// The user's instance, or `None` for local users.
+    pub host: Option<String>,
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub is_bot: bool,
+    /// The profile's bio, only included in detailed responses.
+    pub description: Option<String>,
+}
+
+impl User {
+    /// Returns the display name, falling back to the username.
+    pub fn display_name(&self) -> &str {
+        match &self.name {
+            Some(name) if !name.is_empty() => name,
+            _ => &self.username,
+        }
+    }
+    /// Returns the full handle without the leading `@`, e.g. `alice@misskey.io`.
+    pub fn acct(&self) -> String {
+        match &self.host {
+            Some(host) => format!("{}@{}", self.username, host),
+            None => self.username.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// When the note was last edited, if it was.
+    pub updated_at: Option<DateTime<Utc>>,
+    pub user: User,
+    pub text: Option<String>,
+    /// The content warning, if any.
+    pub cw: Option<String>,
+    pub visibility: Visibility,
+    /// Who can see a `specified` note, besides its author and mentioned users.
+    #[serde(default)]
+    pub visible_user_ids: Vec<String>,
+    /// The ids of mentioned users.
+    #[serde(default)]
+    pub mentions: Vec<String>,
+    pub reply_id: Option<String>,
+    /// The note replied to, if included. Stored notes share the store's copy.
+    pub reply: Option<Arc<Note>>,
+    pub renote_id: Option<String>,
+    pub renote: Option<Arc<Note>>,
+    /// Attached files, in order.
+    #[serde(default)]
+    pub files: Vec<DriveFile>,
+    pub poll: Option<Poll>,
+    /// Reactions, the most used first.
+    #[serde(default, with = "reaction_counts")]
+    pub reactions: Vec<Reaction>,
+    /// Our reaction, if we reacted.
+    pub my_reaction: Option<String>,
+    #[serde(default)]
+    pub renote_count: u64,
+    #[serde(default)]
+    pub replies_count: u64,
+    /// The channel the note was posted in, if any.
+    pub channel_id: Option<String>,
+    pub channel: Option<NoteChannel>,
+    /// Hashtags in the text, without `#`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A poll attached to a note.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Poll {
+    pub choices: Vec<PollChoice>,
+    /// Whether several choices can be voted for.
+    #[serde(default)]
+    pub multiple: bool,
+    /// When voting ends, if it does.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PollChoice {
+    pub text: String,
+    pub votes: u64,
+    /// Whether we voted for it.
+    #[serde(default)]
+    pub is_voted: bool,
+}
+
+/// How many times a note was reacted to with an emoji, e.g. `👍` or `:blobcat@.:`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Reaction {
+    pub emoji: String,
+    pub count: u64,
+}
+
+/// Reads reactions from the API's map of emoji to counts, and writes them back.
+mod reaction_counts {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Reaction;
+
+    pub fn serialize<S: Serializer>(
+        reactions: &[Reaction],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let counts: BTreeMap<&str, u64> = reactions
+            .iter()
+            .map(|reaction| (reaction.emoji.as_str(), reaction.count))
+            .collect();
+        counts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Reaction>, D::Error> {
+        let counts = BTreeMap::<String, u64>::deserialize(deserializer)?;
+        let mut reactions: Vec<Reaction> = counts
+            .into_iter()
+            .map(|(emoji, count)| Reaction { emoji, count })
+            .collect();
+        reactions.sort_by(|a, b| b.count.cmp(&a.count));
+        Ok(reactions)
+    }
+}
+
+/// The channel a note was posted in, as included with the note.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NoteChannel {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Home,
+    Followers,
+    /// Only visible to the users in `visibleUserIds` (a direct note).
+    Specified,
+}
+
+impl Visibility {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Public => Self::Home,
+            Self::Home => Self::Followers,
+            Self::Followers => Self::Specified,
+            Self::Specified => Self::Public,
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Home => "home",
+            Self::Followers => "followers",
+            Self::Specified => "specified",
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{
+    models::{Note, Visibility},
+    Client, Error,
+};
+
+/// A note to post through `notes/create`.
+#[derive(Serialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewNote {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cw: Option<String>,
+    pub visibility: Visibility,
+    /// Who can see a `specified` note.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub visible_user_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_id: Option<String>,
+    /// The note to quote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renote_id: Option<String>,
+    /// The channel to post in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    /// Drive files to attach.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll: Option<NewPoll>,
+}
+
+// instances don't report their poll limits, so these are Misskey's.
+/// The fewest choices a poll can have.
+pub const MIN_POLL_CHOICES: usize = 2;
+/// The most choices a poll can have.
+pub const MAX_POLL_CHOICES: usize = 10;
+/// The longest a poll choice can be, in characters.
+pub const MAX_POLL_CHOICE_LENGTH: usize = 50;
+
+/// A poll to attach to a new note.
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPoll {
+    pub choices: Vec<String>,
+    /// Whether voters can pick several choices.
+    pub multiple: bool,
+    /// When voting ends, in milliseconds since the UNIX epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    /// How long voting lasts after posting, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expired_after: Option<i64>,
+}
+
+/// Filters for `notes/search`.
+#[derive(Default, Clone)]
+pub struct NoteSearch {
+    pub query: String,
+    /// Only notes by this user.
+    pub user_id: Option<String>,
+    /// Only notes from this instance, with `.` for ours.
+    pub host: Option<String>,
+    /// Only notes in this channel.
+    pub channel_id: Option<String>,
+}
+
+/// A note we added to our favorites.
+#[derive(Clone, Debug)]
+pub struct Favorite {
+    /// The favorite's own id, to page through favorites with.
+    pub id: String,
+    pub note: Arc<Note>,
+}
+
+/// A favorite as the API returns it, before its note is stored.
+#[derive(Deserialize)]
+struct RawFavorite {
+    id: String,
+    note: Note,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NoteState {
+    is_favorited: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedNote {
+    created_note: Note,
+}
+
+impl Client {
+    /// Fetches notes that mention us, only direct ones if `visibility` is `Specified`.
+    pub fn mentions(
+        &self,
+        visibility: Option<Visibility>,
+        until_id: Option<&str>,
+    ) -> Result<Vec<Arc<Note>>, Error> {
+        let mut body = json!({ "limit": 30 });
+        if let Some(visibility) = visibility {
+            body["visibility"] = visibility.as_str().into();
+        }
+        if let Some(until_id) = until_id {
+            body["untilId"] = until_id.into();
+        }
+
+        self.fetch_notes("notes/mentions", body)
+    }
+
+    /// Searches notes on the server.
+    pub fn search_notes(
+        &self,
+        query: &NoteSearch,
+        until_id: Option<&str>,
+    ) -> Result<Vec<Arc<Note>>, Error> {
+        let mut body = json!({ "query": query.query, "limit": 30 });
+        if let Some(user_id) = &query.user_id {
+            body["userId"] = user_id.as_str().into();
+        }
+        if let Some(host) = &query.host {
+            body["host"] = host.as_str().into();
+        }
+        if let Some(channel_id) = &query.channel_id {
+            body["channelId"] = channel_id.as_str().into();
+        }
+        if let Some(until_id) = until_id {
+            body["untilId"] = until_id.into();
+        }
+
+        self.fetch_notes("notes/search", body)
+    }
+
+    /// Calls an endpoint returning notes, and stores them.
+    pub fn fetch_notes(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<Vec<Arc<Note>>, Error> {
+        let notes: Vec<Note> = self.request(endpoint, body)?;
+        Ok(self.store.write().insert(notes))
+    }
+
+    /// Posts a note and returns it.
+    pub fn create_note(&self, note: &NewNote) -> Result<Arc<Note>, Error> {
+        let body = serde_json::to_value(note).map_err(|err| Error::Decode(err.to_string()))?;
+        let note = self
+            .request::<CreatedNote>("notes/create", body)?
+            .created_note;
+        Ok(self.store.write().insert_note(note))
+    }
+
+    /// Fetches our favorites, newest first, or those older than `until_id`.
+    pub fn favorites(&self, until_id: Option<&str>) -> Result<Vec<Favorite>, Error> {
+        let mut body = json!({ "limit": 30 });
+        if let Some(until_id) = until_id {
+            body["untilId"] = until_id.into();
+        }
+
+        let favorites: Vec<RawFavorite> = self.request("i/favorites", body)?;
+        let mut store = self.store.write();
+        Ok(favorites
+            .into_iter()
+            .map(|favorite| Favorite {
+                id: favorite.id,
+                note: store.insert_note(favorite.note),
+            })
+            .collect())
+    }
+
+    /// Favorites a note, or unfavorites it if it already is.
+    /// Returns whether it's now a favorite.
+    pub fn toggle_favorite(&self, note_id: &str) -> Result<bool, Error> {
+        let state: NoteState = self.request("notes/state", json!({ "noteId": note_id }))?;
+        let endpoint = if state.is_favorited {
+            "notes/favorites/delete"
+        } else {
+            "notes/favorites/create"
+        };
+        self.request_empty(endpoint, json!({ "noteId": note_id }))?;
+        Ok(!state.is_favorited)
+    }
+}
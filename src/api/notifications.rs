@@ -0,0 +1,152 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{
+    models::{Note, User},
+    Client, Error,
+};
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationType {
+    Note,
+    Follow,
+    Mention,
+    Reply,
+    Renote,
+    Quote,
+    Reaction,
+    PollEnded,
+    ReceiveFollowRequest,
+    FollowRequestAccepted,
+    RoleAssigned,
+    AchievementEarned,
+    App,
+    Test,
+    /// Several reactions to the same note, only returned by the grouped endpoint.
+    #[serde(rename = "reaction:grouped")]
+    ReactionGrouped,
+    /// Several renotes of the same note, only returned by the grouped endpoint.
+    #[serde(rename = "renote:grouped")]
+    RenoteGrouped,
+    #[serde(other)]
+    Unknown,
+}
+
+impl NotificationType {
+    /// The types that can be passed to `includeTypes` and `excludeTypes`.
+    pub const FILTERABLE: [Self; 14] = [
+        Self::Note,
+        Self::Follow,
+        Self::Mention,
+        Self::Reply,
+        Self::Renote,
+        Self::Quote,
+        Self::Reaction,
+        Self::PollEnded,
+        Self::ReceiveFollowRequest,
+        Self::FollowRequestAccepted,
+        Self::RoleAssigned,
+        Self::AchievementEarned,
+        Self::App,
+        Self::Test,
+    ];
+
+    /// The name the API uses for this type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Follow => "follow",
+            Self::Mention => "mention",
+            Self::Reply => "reply",
+            Self::Renote => "renote",
+            Self::Quote => "quote",
+            Self::Reaction => "reaction",
+            Self::PollEnded => "pollEnded",
+            Self::ReceiveFollowRequest => "receiveFollowRequest",
+            Self::FollowRequestAccepted => "followRequestAccepted",
+            Self::RoleAssigned => "roleAssigned",
+            Self::AchievementEarned => "achievementEarned",
+            Self::App => "app",
+            Self::Test => "test",
+            Self::ReactionGrouped => "reaction:grouped",
+            Self::RenoteGrouped => "renote:grouped",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: String,
+    pub created_at: String,
+    #[serde(rename = "type")]
+    pub kind: NotificationType,
+    pub user: Option<User>,
+    pub note: Option<Note>,
+    /// The reaction of a `reaction` notification.
+    pub reaction: Option<String>,
+    /// The reactions of a `reaction:grouped` notification.
+    #[serde(default)]
+    pub reactions: Vec<GroupedReaction>,
+    /// The users of a `renote:grouped` notification.
+    #[serde(default)]
+    pub users: Vec<User>,
+    /// The body of an `app` notification.
+    pub body: Option<String>,
+    /// The achievement of an `achievementEarned` notification.
+    pub achievement: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GroupedReaction {
+    pub user: User,
+    pub reaction: String,
+}
+
+/// Which notifications to fetch.
+#[derive(Default, Clone)]
+pub struct NotificationQuery {
+    /// Whether to merge reactions and renotes on the same note.
+    pub grouped: bool,
+    pub include_types: Vec<NotificationType>,
+    pub exclude_types: Vec<NotificationType>,
+    /// Only fetch notifications older than this id.
+    pub until_id: Option<String>,
+}
+
+impl Client {
+    /// Fetches notifications through `i/notifications` or `i/notifications-grouped`.
+    pub fn notifications(&self, query: &NotificationQuery) -> Result<Vec<Notification>, Error> {
+        let types = |types: &[NotificationType]| {
+            types
+                .iter()
+                .map(|kind| Value::from(kind.as_str()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut body = json!({ "limit": 50 });
+        if !query.include_types.is_empty() {
+            body["includeTypes"] = types(&query.include_types).into();
+        }
+        if !query.exclude_types.is_empty() {
+            body["excludeTypes"] = types(&query.exclude_types).into();
+        }
+        if let Some(until_id) = &query.until_id {
+            body["untilId"] = until_id.as_str().into();
+        }
+
+        let endpoint = if query.grouped {
+            "i/notifications-grouped"
+        } else {
+            "i/notifications"
+        };
+        self.request(endpoint, body)
+    }
+
+    /// Marks every notification as read.
+    pub fn mark_all_notifications_read(&self) -> Result<(), Error> {
+        self.request_empty("notifications/mark-all-as-read", json!({}))
+    }
+}
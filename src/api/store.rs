@@ -0,0 +1,145 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::models::{Note, User};
+
+/// Every note and user seen so far, shared by the screens showing them.
+///
+/// Notes are handed out as `Arc`s, so feeds showing the same note share one copy,
+/// and replies and renotes point at the stored copies of the notes they include.
+/// A note fetched again replaces its copy, and bumps the revision for feeds to pick it up.
+/// Notes are also searchable here when the server can't search for us.
+#[derive(Default)]
+pub struct Store {
+    notes: HashMap<String, Arc<Note>>,
+    users: HashMap<String, Arc<User>>,
+    /// The ids of the notes replying to or renoting each note.
+    referrers: HashMap<String, Vec<String>>,
+    /// How many times stored notes were replaced.
+    revision: u64,
+}
+
+/// Which cached notes to look for.
+#[derive(Default, Clone)]
+pub struct LocalQuery {
+    /// Words that must all appear in the text or CW, case-insensitively.
+    pub words: Vec<String>,
+    /// The author, as `username` or `username@host`.
+    pub user: Option<String>,
+    /// The author's instance, with `.` for local users.
+    pub host: Option<String>,
+    pub channel_id: Option<String>,
+}
+
+impl Store {
+    /// Adds or updates notes, along with the notes they reply to or renote and their
+    /// authors. Returns the stored notes.
+    pub fn insert(&mut self, notes: Vec<Note>) -> Vec<Arc<Note>> {
+        notes
+            .into_iter()
+            .map(|note| self.insert_note(note))
+            .collect()
+    }
+
+    /// Adds or updates a note, like `insert`.
+    ///
+    /// The note points at the stored copies of the notes it replies to or renotes,
+    /// including stored ones the server left out.
+    pub fn insert_note(&mut self, mut note: Note) -> Arc<Note> {
+        for (id, related) in [
+            (&note.reply_id, &mut note.reply),
+            (&note.renote_id, &mut note.renote),
+        ] {
+            *related = match related.take() {
+                Some(included) => Some(self.insert_note(Arc::unwrap_or_clone(included))),
+                None => id.as_ref().and_then(|id| self.note(id)),
+            };
+            if let Some(related) = related {
+                let referrers = self.referrers.entry(related.id.clone()).or_default();
+                if !referrers.contains(&note.id) {
+                    referrers.push(note.id.clone());
+                }
+            }
+        }
+        self.users
+            .insert(note.user.id.clone(), Arc::new(note.user.clone()));
+
+        self.replace(Arc::new(note))
+    }
+
+    /// Stores a note, pointing the notes replying to or renoting it at the new copy.
+    fn replace(&mut self, note: Arc<Note>) -> Arc<Note> {
+        if self.notes.insert(note.id.clone(), note.clone()).is_none() {
+            return note;
+        }
+        self.revision += 1;
+
+        let referrers = self.referrers.get(&note.id).cloned().unwrap_or_default();
+        for id in referrers {
+            let Some(referrer) = self.notes.get(&id) else {
+                continue;
+            };
+            let mut referrer = (**referrer).clone();
+            for related in [&mut referrer.reply, &mut referrer.renote]
+                .into_iter()
+                .flatten()
+            {
+                if related.id == note.id {
+                    *related = note.clone();
+                }
+            }
+            self.replace(Arc::new(referrer));
+        }
+        note
+    }
+
+    pub fn note(&self, id: &str) -> Option<Arc<Note>> {
+        self.notes.get(id).cloned()
+    }
+
+    pub fn user(&self, id: &str) -> Option<Arc<User>> {
+        self.users.get(id).cloned()
+    }
+
+    /// Returns how many times stored notes were replaced, to tell when copies got stale.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns the matching notes, newest first.
+    pub fn search(&self, query: &LocalQuery) -> Vec<Arc<Note>> {
+        let words: Vec<String> = query.words.iter().map(|w| w.to_lowercase()).collect();
+
+        let mut found: Vec<Arc<Note>> = self
+            .notes
+            .values()
+            .filter(|note| {
+                let content = format!(
+                    "{}\n{}",
+                    note.cw.as_deref().unwrap_or(""),
+                    note.text.as_deref().unwrap_or("")
+                )
+                .to_lowercase();
+                let host = note.user.host.as_deref().unwrap_or(".");
+
+                words.iter().all(|word| content.contains(word.as_str()))
+                    && query.user.as_ref().map_or(true, |user| {
+                        user.eq_ignore_ascii_case(&note.user.username)
+                            || user.eq_ignore_ascii_case(&note.user.acct())
+                    })
+                    && query
+                        .host
+                        .as_ref()
+                        .map_or(true, |wanted| wanted.eq_ignore_ascii_case(host))
+                    && query
+                        .channel_id
+                        .as_ref()
+                        .map_or(true, |id| note.channel_id.as_ref() == Some(id))
+            })
+            .cloned()
+            .collect();
+
+        // ids sort by creation time.
+        found.sort_by(|a, b| b.id.cmp(&a.id));
+        found
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    io::ErrorKind,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tungstenite::{stream::MaybeTlsStream, Message};
+
+use super::{models::Note, Client, Error};
+
+/// A message from the streaming API.
+#[derive(Deserialize)]
+struct Event {
+    #[serde(rename = "type")]
+    kind: String,
+    body: Option<ChannelEvent>,
+}
+
+/// An event from a channel we connected to.
+#[derive(Deserialize)]
+struct ChannelEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    body: Value,
+}
+
+/// How long a read waits for a message before checking whether to stop.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How many times in a row to try reconnecting before giving up.
+const RECONNECT_ATTEMPTS: u32 = 5;
+
+impl Client {
+    /// Connects to a streaming `channel` and passes each note posted to it to `on_note`.
+    /// Returns once `stop` is set or `on_note` returns `false`.
+    ///
+    /// Reconnects when the connection is lost, waiting longer after each failed attempt,
+    /// and returns the error once `RECONNECT_ATTEMPTS` attempts in a row failed.
+    pub fn stream_notes(
+        &self,
+        channel: &str,
+        params: Value,
+        stop: &AtomicBool,
+        mut on_note: impl FnMut(Arc<Note>) -> bool,
+    ) -> Result<(), Error> {
+        let mut failures = 0;
+        loop {
+            let Err(err) = self.stream_once(channel, &params, stop, &mut failures, &mut on_note)
+            else {
+                return Ok(());
+            };
+            failures += 1;
+            if failures >= RECONNECT_ATTEMPTS {
+                return Err(err);
+            }
+            // waits 1s, 2s, 4s... checking every second whether to stop.
+            for _ in 0..1 << (failures - 1) {
+                if stop.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                thread::sleep(READ_TIMEOUT);
+            }
+        }
+    }
+
+    /// Streams notes over a single connection, like `stream_notes`.
+    /// Returns an error when the connection is lost, and resets `failures` once connected.
+    fn stream_once(
+        &self,
+        channel: &str,
+        params: &Value,
+        stop: &AtomicBool,
+        failures: &mut u32,
+        on_note: &mut impl FnMut(Arc<Note>) -> bool,
+    ) -> Result<(), Error> {
+        let mut url = format!("wss://{}/streaming", self.instance);
+        if let Some(token) = &self.token {
+            url.push_str(&format!("?i={token}"));
+        }
+        let transport = |err: tungstenite::Error| Error::Transport(err.to_string());
+
+        let (mut socket, _) = tungstenite::connect(url).map_err(transport)?;
+        let tcp = match socket.get_ref() {
+            MaybeTlsStream::Plain(stream) => Some(stream),
+            MaybeTlsStream::Rustls(stream) => Some(stream.get_ref()),
+            _ => None,
+        };
+        if let Some(tcp) = tcp {
+            tcp.set_read_timeout(Some(READ_TIMEOUT))
+                .map_err(|err| Error::Transport(err.to_string()))?;
+        }
+        let connect = json!({
+            "type": "connect",
+            "body": { "channel": channel, "id": channel, "params": params },
+        });
+        socket
+            .send(Message::text(connect.to_string()))
+            .map_err(transport)?;
+        *failures = 0;
+
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                socket.close(None).ok();
+                return Ok(());
+            }
+            let text = match socket.read() {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => {
+                    return Err(Error::Transport("the server closed the connection".into()))
+                }
+                Ok(_) => continue,
+                Err(tungstenite::Error::Io(err))
+                    if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    continue
+                }
+                Err(err) => return Err(transport(err)),
+            };
+            let Ok(Event {
+                kind,
+                body: Some(event),
+            }) = serde_json::from_str(&text)
+            else {
+                continue;
+            };
+            if kind != "channel" || event.kind != "note" {
+                continue;
+            }
+
+            // a note we can't read is skipped rather than ending the stream.
+            let Ok(note) = serde_json::from_value::<Note>(event.body) else {
+                continue;
+            };
+            let note = self.store.write().insert_note(note);
+            if !on_note(note) {
+                socket.close(None).ok();
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::{fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{models::Note, Client, Error};
+
+/// The most notes fetched from a timeline at once.
+pub const TIMELINE_LIMIT: usize = 20;
+
+/// A source of notes that can be read as a feed and followed live.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Timeline {
+    /// Notes from followed users.
+    Home,
+    /// Notes from users of our instance.
+    Local,
+    /// Home and local together.
+    Social,
+    /// Every known note.
+    Global,
+    /// Notes with hashtags matching a query.
+    Hashtag { query: TagQuery },
+    /// Notes from the members of one of our user lists.
+    UserList { id: String, name: String },
+    /// Notes matched by one of our antennas.
+    Antenna { id: String, name: String },
+    /// Notes posted in a channel.
+    Channel { id: String, name: String },
+    /// Notes in one of our clips.
+    Clip { id: String, name: String },
+    /// Notes mentioning us.
+    Mentions,
+    /// Notes found by a search for words.
+    Search { query: String },
+}
+
+impl Timeline {
+    /// The instance's own timelines, always shown first on the home screen.
+    pub const MAIN: [Timeline; 4] = [Self::Home, Self::Local, Self::Social, Self::Global];
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Home => "Home".into(),
+            Self::Local => "Local".into(),
+            Self::Social => "Social".into(),
+            Self::Global => "Global".into(),
+            Self::Hashtag { query } => query.to_string(),
+            Self::UserList { name, .. } => format!("List: {name}"),
+            Self::Antenna { name, .. } => format!("Antenna: {name}"),
+            Self::Channel { name, .. } => format!("Channel: {name}"),
+            Self::Clip { name, .. } => format!("Clip: {name}"),
+            Self::Mentions => "Mentions".into(),
+            Self::Search { query } => format!("Search: {query}"),
+        }
+    }
+
+    /// The endpoint listing this timeline's notes, with its parameters.
+    fn endpoint(&self) -> (&'static str, Value) {
+        match self {
+            Self::Home => ("notes/timeline", json!({})),
+            Self::Local => ("notes/local-timeline", json!({})),
+            Self::Social => ("notes/hybrid-timeline", json!({})),
+            Self::Global => ("notes/global-timeline", json!({})),
+            Self::Hashtag { query } => ("notes/search-by-tag", json!({ "query": query })),
+            Self::UserList { id, .. } => ("notes/user-list-timeline", json!({ "listId": id })),
+            Self::Antenna { id, .. } => ("antennas/notes", json!({ "antennaId": id })),
+            Self::Channel { id, .. } => ("channels/timeline", json!({ "channelId": id })),
+            Self::Clip { id, .. } => ("clips/notes", json!({ "clipId": id })),
+            Self::Mentions => ("notes/mentions", json!({})),
+            Self::Search { query } => ("notes/search", json!({ "query": query })),
+        }
+    }
+
+    /// The streaming channel posting this timeline's new notes, with its parameters.
+    /// Returns `None` if it can't be followed live.
+    pub fn channel(&self) -> Option<(&'static str, Value)> {
+        Some(match self {
+            Self::Home => ("homeTimeline", json!({})),
+            Self::Local => ("localTimeline", json!({})),
+            Self::Social => ("hybridTimeline", json!({})),
+            Self::Global => ("globalTimeline", json!({})),
+            Self::Hashtag { query } => ("hashtag", json!({ "q": query })),
+            Self::UserList { id, .. } => ("userList", json!({ "listId": id })),
+            Self::Antenna { id, .. } => ("antenna", json!({ "antennaId": id })),
+            Self::Channel { id, .. } => ("channel", json!({ "channelId": id })),
+            Self::Clip { .. } | Self::Mentions | Self::Search { .. } => return None,
+        })
+    }
+}
+
+/// Hashtags to look for: any of the groups, where a group matches notes with all its tags.
+///
+/// `[["foo", "bar"], ["baz"]]` matches notes with both `#foo` and `#bar`, or with `#baz`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(transparent)]
+pub struct TagQuery(pub Vec<Vec<String>>);
+
+impl TagQuery {
+    /// Parses either the JSON form, or a shorthand like `#foo #bar | #baz`.
+    /// Returns `None` if there's no tag in it.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let groups: Vec<Vec<String>> = if input.starts_with('[') {
+            serde_json::from_str(input).ok()?
+        } else {
+            input
+                .split('|')
+                .map(|group| group.split_whitespace().map(str::to_owned).collect())
+                .collect()
+        };
+
+        let groups: Vec<Vec<String>> = groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|tag| tag.trim_start_matches('#').to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect();
+
+        (!groups.is_empty()).then_some(Self(groups))
+    }
+
+    /// A query for a single tag.
+    pub fn tag(tag: &str) -> Self {
+        Self(vec![vec![tag.trim_start_matches('#').to_owned()]])
+    }
+}
+
+impl fmt::Display for TagQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<String> = self
+            .0
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|tag| format!("#{tag}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        write!(f, "{}", groups.join(" | "))
+    }
+}
+
+impl Client {
+    /// Fetches a timeline's notes, newest first: those older than `until_id` and newer than
+    /// `since_id`, or the newest ones. At most `TIMELINE_LIMIT` are fetched at once.
+    pub fn timeline(
+        &self,
+        timeline: &Timeline,
+        until_id: Option<&str>,
+        since_id: Option<&str>,
+    ) -> Result<Vec<Arc<Note>>, Error> {
+        let (endpoint, mut body) = timeline.endpoint();
+        body["limit"] = TIMELINE_LIMIT.into();
+        if let Some(until_id) = until_id {
+            body["untilId"] = until_id.into();
+        }
+        if let Some(since_id) = since_id {
+            body["sinceId"] = since_id.into();
+        }
+
+        let mut notes = self.fetch_notes(endpoint, body)?;
+        // some endpoints list the oldest first when given only `sinceId`.
+        notes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(notes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(groups: &[&[&str]]) -> Option<TagQuery> {
+        Some(TagQuery(
+            groups
+                .iter()
+                .map(|group| group.iter().map(|tag| (*tag).to_owned()).collect())
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_shorthand() {
+        assert_eq!(TagQuery::parse("#foo"), query(&[&["foo"]]));
+        assert_eq!(TagQuery::parse("foo bar"), query(&[&["foo", "bar"]]));
+        assert_eq!(
+            TagQuery::parse(" #foo #bar | #baz "),
+            query(&[&["foo", "bar"], &["baz"]])
+        );
+        // empty groups and lone `#`s are dropped.
+        assert_eq!(
+            TagQuery::parse("#foo || # | #baz"),
+            query(&[&["foo"], &["baz"]])
+        );
+    }
+
+    #[test]
+    fn parses_json() {
+        assert_eq!(
+            TagQuery::parse(r##"[["foo", "#bar"], ["baz"]]"##),
+            query(&[&["foo", "bar"], &["baz"]])
+        );
+        assert_eq!(TagQuery::parse(r#"[["foo"], []]"#), query(&[&["foo"]]));
+        assert_eq!(TagQuery::parse(r#"[["foo"]"#), None);
+    }
+
+    #[test]
+    fn rejects_queries_without_tags() {
+        assert_eq!(TagQuery::parse(""), None);
+        assert_eq!(TagQuery::parse("  #  |  "), None);
+        assert_eq!(TagQuery::parse("[]"), None);
+        assert_eq!(TagQuery::parse("[[]]"), None);
+    }
+
+    #[test]
+    fn writes_shorthand_back() {
+        let input = "#foo #bar | #baz";
+        assert_eq!(TagQuery::parse(input).unwrap().to_string(), input);
+        assert_eq!(TagQuery::tag("#foo").to_string(), "#foo");
+    }
+}
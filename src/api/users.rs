@@ -0,0 +1,49 @@
+use serde_json::json;
+
+use super::{models::User, Client, Error};
+
+impl Client {
+    /// Fetches the account this client is signed in as.
+    pub fn me(&self) -> Result<User, Error> {
+        self.request("i", json!({}))
+    }
+
+    /// Fetches several users at once.
+    pub fn users_by_ids(&self, ids: &[String]) -> Result<Vec<User>, Error> {
+        self.request("users/show", json!({ "userIds": ids }))
+    }
+
+    /// Finds users by name or username.
+    pub fn search_users(&self, query: &str) -> Result<Vec<User>, Error> {
+        self.request(
+            "users/search",
+            json!({ "query": query, "limit": 30, "origin": "combined", "detail": true }),
+        )
+    }
+
+    /// Finds users whose handle starts with `acct`, e.g. `ali` or `alice@mis`, with or without `@`.
+    pub fn search_users_by_acct(&self, acct: &str) -> Result<Vec<User>, Error> {
+        let acct = acct.trim_start_matches('@');
+        let mut body = json!({ "limit": 10, "detail": false });
+        match acct.split_once('@') {
+            Some((username, host)) => {
+                body["username"] = username.into();
+                body["host"] = host.into();
+            }
+            None => body["username"] = acct.into(),
+        }
+
+        self.request("users/search-by-username-and-host", body)
+    }
+
+    /// Looks up a user by handle, e.g. `alice` or `alice@misskey.io`, with or without `@`.
+    pub fn user_by_acct(&self, acct: &str) -> Result<User, Error> {
+        let acct = acct.trim_start_matches('@');
+        let body = match acct.split_once('@') {
+            Some((username, host)) => json!({ "username": username, "host": host }),
+            None => json!({ "username": acct }),
+        };
+
+        self.request("users/show", body)
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::timelines::Timeline;
+
+/// The narrowest and widest a column can be, relative to the others.
+pub const MIN_WIDTH: u16 = 1;
+pub const MAX_WIDTH: u16 = 5;
+
+/// A column of the deck screen.
+///
+/// ```toml
+/// [[deck]]
+/// column = "timeline"
+/// timeline = { type = "local" }
+/// width = 2
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct DeckColumn {
+    #[serde(flatten)]
+    pub source: ColumnSource,
+    /// The column's share of the screen's width.
+    #[serde(default = "default_width")]
+    pub width: u16,
+}
+
+/// What a deck column shows.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "column", rename_all = "camelCase")]
+pub enum ColumnSource {
+    Notifications,
+    Timeline { timeline: Timeline },
+}
+
+impl DeckColumn {
+    pub fn new(source: ColumnSource) -> Self {
+        Self {
+            source,
+            width: default_width(),
+        }
+    }
+
+    /// The columns shown until the deck is changed.
+    pub fn defaults() -> Vec<Self> {
+        [
+            ColumnSource::Timeline {
+                timeline: Timeline::Home,
+            },
+            ColumnSource::Notifications,
+            ColumnSource::Timeline {
+                timeline: Timeline::Mentions,
+            },
+            ColumnSource::Timeline {
+                timeline: Timeline::Local,
+            },
+        ]
+        .into_iter()
+        .map(Self::new)
+        .collect()
+    }
+}
+
+impl ColumnSource {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Notifications => "Notifications".into(),
+            Self::Timeline { timeline } => timeline.title(),
+        }
+    }
+}
+
+fn default_width() -> u16 {
+    MIN_WIDTH
+}
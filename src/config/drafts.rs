@@ -0,0 +1,277 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use super::write_private;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    drive::DriveFile,
+    models::{Note, NoteChannel, Visibility},
+    notes::NewPoll,
+};
+
+/// How long drafts have to stay unchanged before changes made while typing are saved.
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Notes that were being written but not posted yet, stored as JSON in the platform's data directory.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Drafts {
+    drafts: Vec<Draft>,
+    /// Why the drafts file couldn't be loaded, to tell once.
+    #[serde(skip)]
+    pub problem: Option<String>,
+    /// When the drafts last changed, if that isn't saved yet.
+    #[serde(skip)]
+    changed_at: Option<Instant>,
+}
+
+/// What a draft is written for. An account has at most one draft per target.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DraftTarget {
+    /// The instance the draft is posted to, e.g. `misskey.io`.
+    #[serde(alias = "account")]
+    pub host: String,
+    /// The id of the user posting it, if it was known when the draft was saved.
+    pub user_id: Option<String>,
+    /// The id of the note being replied to, if any.
+    pub reply_id: Option<String>,
+    /// The id of the note being quoted, if any.
+    pub quote_id: Option<String>,
+    /// The id of the channel to post in, if any.
+    pub channel_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Draft {
+    pub target: DraftTarget,
+    /// The note being replied to, to show it again when resuming.
+    pub reply: Option<Note>,
+    /// The note being quoted, to show it again when resuming.
+    pub quote: Option<Note>,
+    pub channel: Option<NoteChannel>,
+    pub cw: String,
+    pub text: String,
+    pub visibility: Visibility,
+    /// Who can see the note if it's `specified`, as ids and as handles to show.
+    pub visible_users: Vec<(String, String)>,
+    pub files: Vec<DraftFile>,
+    pub poll: Option<NewPoll>,
+    /// When the draft was last saved, as a UNIX timestamp.
+    pub saved_at: i64,
+}
+
+/// A Drive file attached to a draft.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DraftFile {
+    pub file: DriveFile,
+    pub alt: String,
+    pub sensitive: bool,
+}
+
+impl Draft {
+    /// Formats when the draft was last saved, e.g. `2024-10-04 18:30`.
+    pub fn saved_at_text(&self) -> String {
+        Local
+            .timestamp_opt(self.saved_at, 0)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Drafts {
+    /// Returns the path of the drafts file, e.g. `~/.local/share/keytan/drafts.json`.
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("keytan").join("drafts.json"))
+    }
+    /// Loads the drafts file, or starts without drafts if it's missing.
+    ///
+    /// A file that can't be read is moved aside to be recovered by hand, rather than
+    /// overwritten by the next save, and `problem` says so.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let err = match fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str(&text) {
+                Ok(drafts) => return drafts,
+                Err(err) => err.to_string(),
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => err.to_string(),
+        };
+
+        let backup = path.with_extension(format!("json.{}.bak", Local::now().timestamp()));
+        let problem = match fs::rename(&path, &backup) {
+            Ok(()) => format!(
+                "Couldn't read the drafts ({err}), moved them to {}.",
+                backup.display()
+            ),
+            Err(_) => format!("Couldn't read the drafts ({err}), they'll be overwritten."),
+        };
+        Self {
+            problem: Some(problem),
+            ..Self::default()
+        }
+    }
+    /// Writes the drafts file, creating its directory if needed.
+    pub fn save(&mut self) -> io::Result<()> {
+        self.changed_at = None;
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_private(&path, &text)
+    }
+    /// Notes that the drafts changed without saving them, for `save_settled` to do later.
+    pub fn mark_changed(&mut self) {
+        self.changed_at = Some(Instant::now());
+    }
+    /// Saves the changes noted by `mark_changed` once the drafts stayed unchanged for a
+    /// moment, so typing doesn't rewrite the file on every key.
+    pub fn save_settled(&mut self) -> io::Result<()> {
+        match self.changed_at {
+            Some(time) if time.elapsed() >= SAVE_DELAY => self.save(),
+            _ => Ok(()),
+        }
+    }
+    /// Saves the changes noted by `mark_changed` right away, if any.
+    pub fn save_changed(&mut self) -> io::Result<()> {
+        match self.changed_at {
+            Some(_) => self.save(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the drafts of the user `user_id` on `host`, the most recently saved first.
+    /// Drafts saved before the user was known are included.
+    pub fn of_account(&self, host: &str, user_id: Option<&str>) -> Vec<Draft> {
+        let mut drafts: Vec<Draft> = self
+            .drafts
+            .iter()
+            .filter(|draft| {
+                draft.target.host == host
+                    && (draft.target.user_id.is_none()
+                        || draft.target.user_id.as_deref() == user_id)
+            })
+            .cloned()
+            .collect();
+        drafts.sort_by_key(|draft| -draft.saved_at);
+        drafts
+    }
+    /// Finds the draft written for `target`.
+    pub fn get(&self, target: &DraftTarget) -> Option<&Draft> {
+        self.drafts.iter().find(|draft| draft.target == *target)
+    }
+    /// Adds a draft, replacing the one with the same target if any.
+    pub fn put(&mut self, draft: Draft) {
+        self.remove(&draft.target);
+        self.drafts.push(draft);
+    }
+    /// Discards the draft written for `target`, if any.
+    pub fn remove(&mut self, target: &DraftTarget) {
+        self.drafts.retain(|draft| draft.target != *target);
+    }
+    /// Counts the drafts saved at or after `time`, as a UNIX timestamp.
+    pub fn count_since(&self, time: i64) -> usize {
+        self.drafts
+            .iter()
+            .filter(|draft| draft.saved_at >= time)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A drafts file with one draft per target, written before drafts were kept per user.
+    const OLD_DRAFTS: &str = r#"{"drafts": [
+        {"target": {"account": "misskey.io", "reply_id": null, "channel_id": null},
+         "reply": null, "channel": null, "cw": "", "text": "old", "visibility": "public",
+         "visible_users": [], "files": [], "poll": null, "saved_at": 1}
+    ]}"#;
+
+    fn draft(user_id: Option<&str>, text: &str, saved_at: i64) -> Draft {
+        Draft {
+            target: DraftTarget {
+                host: "misskey.io".into(),
+                user_id: user_id.map(Into::into),
+                reply_id: None,
+                quote_id: None,
+                channel_id: None,
+            },
+            reply: None,
+            quote: None,
+            channel: None,
+            cw: String::new(),
+            text: text.into(),
+            visibility: Visibility::Public,
+            visible_users: Vec::new(),
+            files: Vec::new(),
+            poll: None,
+            saved_at,
+        }
+    }
+
+    fn texts(drafts: &[Draft]) -> Vec<&str> {
+        drafts.iter().map(|draft| draft.text.as_str()).collect()
+    }
+
+    #[test]
+    fn reads_drafts_saved_per_instance() {
+        let drafts: Drafts = serde_json::from_str(OLD_DRAFTS).unwrap();
+        let old = &drafts.of_account("misskey.io", Some("alice"))[0];
+        assert_eq!(old.target.host, "misskey.io");
+        assert_eq!(old.target.user_id, None);
+        assert!(old.quote.is_none());
+    }
+
+    #[test]
+    fn keeps_the_drafts_of_each_user_apart() {
+        let mut drafts: Drafts = serde_json::from_str(OLD_DRAFTS).unwrap();
+        drafts.put(draft(Some("alice"), "alice's", 2));
+        drafts.put(draft(Some("bob"), "bob's", 3));
+
+        assert_eq!(
+            texts(&drafts.of_account("misskey.io", Some("alice"))),
+            ["alice's", "old"]
+        );
+        assert_eq!(
+            texts(&drafts.of_account("misskey.io", Some("bob"))),
+            ["bob's", "old"]
+        );
+        assert_eq!(texts(&drafts.of_account("misskey.io", None)), ["old"]);
+        assert!(drafts.of_account("example.com", Some("alice")).is_empty());
+    }
+
+    #[test]
+    fn tells_quotes_apart_from_new_notes() {
+        let mut drafts = Drafts::default();
+        let mut quote = draft(Some("alice"), "quoting", 1);
+        quote.target.quote_id = Some("9xyz".into());
+        drafts.put(draft(Some("alice"), "new", 2));
+        drafts.put(quote.clone());
+
+        assert_eq!(drafts.get(&quote.target).unwrap().text, "quoting");
+        assert_eq!(drafts.of_account("misskey.io", Some("alice")).len(), 2);
+    }
+
+    #[test]
+    fn waits_for_changes_to_settle_before_saving() {
+        let mut drafts = Drafts::default();
+        drafts.mark_changed();
+        // the change is too recent for the file to be written.
+        drafts.save_settled().unwrap();
+        assert!(drafts.changed_at.is_some());
+    }
+}
@@ -0,0 +1,904 @@
+use std::{collections::HashMap, fmt};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+/// Key bindings set in the config, on top of each screen's defaults.
+///
+/// Keys are written like `j`, `ctrl-j`, `alt-enter`, `shift-tab` or `f2`,
+/// and sequences separated by spaces, like `g g`.
+///
+/// An action bound in the config loses its default keys, and a key bound in the config
+/// no longer does what it did by default: binding `next-note` to `n` on the home screen
+/// unbinds `j`, and `n` stops opening the notifications.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keys {
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub home: HashMap<String, HomeAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub login: HashMap<String, LoginAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub timeline: HashMap<String, TimelineAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub deck: HashMap<String, DeckAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub search: HashMap<String, SearchAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub compose: HashMap<String, ComposeAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub notifications: HashMap<String, NotificationsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub drafts: HashMap<String, DraftsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub drive: HashMap<String, DriveAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub mentions: HashMap<String, MentionsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub channels: HashMap<String, ChannelsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub clips: HashMap<String, ClipsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub favorites: HashMap<String, FavoritesAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub feeds: HashMap<String, FeedsAction>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub hashtag: HashMap<String, HashtagAction>,
+}
+
+impl Keys {
+    pub fn is_empty(&self) -> bool {
+        self.home.is_empty()
+            && self.login.is_empty()
+            && self.timeline.is_empty()
+            && self.deck.is_empty()
+            && self.search.is_empty()
+            && self.compose.is_empty()
+            && self.notifications.is_empty()
+            && self.drafts.is_empty()
+            && self.drive.is_empty()
+            && self.mentions.is_empty()
+            && self.channels.is_empty()
+            && self.clips.is_empty()
+            && self.favorites.is_empty()
+            && self.feeds.is_empty()
+            && self.hashtag.is_empty()
+    }
+}
+
+/// Something a screen does when a key is pressed.
+pub trait Action: Copy + PartialEq + 'static {
+    /// The keys bound when the config doesn't change them.
+    const DEFAULTS: &'static [(&'static str, Self)];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum HomeAction {
+    NextNote,
+    PrevNote,
+    NextPage,
+    PrevPage,
+    NextTab,
+    PrevTab,
+    GoToTab,
+    JumpToStart,
+    JumpToEnd,
+    Refresh,
+    Unpin,
+    Reply,
+    Quote,
+    OpenHashtags,
+    OpenNotifications,
+    OpenMentions,
+    OpenDeck,
+    Compose,
+    OpenSearch,
+    OpenFeeds,
+    OpenChannels,
+    ToggleFavorite,
+    ClipNote,
+    OpenFavorites,
+    OpenClips,
+    OpenDrive,
+    OpenDrafts,
+    CommandLine,
+    Quit,
+}
+
+impl Action for HomeAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextNote),
+        ("k", Self::PrevNote),
+        ("J", Self::NextPage),
+        ("K", Self::PrevPage),
+        ("tab", Self::NextTab),
+        ("shift-tab", Self::PrevTab),
+        ("g t", Self::GoToTab),
+        ("G", Self::JumpToStart),
+        ("g g", Self::JumpToEnd),
+        ("r", Self::Refresh),
+        ("p", Self::Unpin),
+        ("enter", Self::Reply),
+        ("Q", Self::Quote),
+        ("#", Self::OpenHashtags),
+        ("n", Self::OpenNotifications),
+        ("m", Self::OpenMentions),
+        ("d", Self::OpenDeck),
+        ("c", Self::Compose),
+        ("/", Self::OpenSearch),
+        ("l", Self::OpenFeeds),
+        ("C", Self::OpenChannels),
+        ("f", Self::ToggleFavorite),
+        ("a", Self::ClipNote),
+        ("F", Self::OpenFavorites),
+        ("A", Self::OpenClips),
+        ("D", Self::OpenDrive),
+        ("E", Self::OpenDrafts),
+        (":", Self::CommandLine),
+        ("q", Self::Quit),
+        ("esc", Self::Quit),
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoginAction {
+    NextField,
+    PrevField,
+    Quit,
+}
+
+impl Action for LoginAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("tab", Self::NextField),
+        ("shift-tab", Self::PrevField),
+        ("esc", Self::Quit),
+    ];
+}
+
+/// Actions of a single timeline, like a hashtag's or a list's.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimelineAction {
+    NextNote,
+    PrevNote,
+    NextPage,
+    PrevPage,
+    JumpToStart,
+    JumpToEnd,
+    Refresh,
+    TogglePin,
+    Reply,
+    Quote,
+    OpenHashtags,
+    Compose,
+    ToggleFavorite,
+    ClipNote,
+    Close,
+}
+
+impl Action for TimelineAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextNote),
+        ("k", Self::PrevNote),
+        ("J", Self::NextPage),
+        ("K", Self::PrevPage),
+        ("G", Self::JumpToStart),
+        ("g g", Self::JumpToEnd),
+        ("r", Self::Refresh),
+        ("p", Self::TogglePin),
+        ("enter", Self::Reply),
+        ("Q", Self::Quote),
+        ("#", Self::OpenHashtags),
+        ("c", Self::Compose),
+        ("f", Self::ToggleFavorite),
+        ("a", Self::ClipNote),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the deck. The source picker keeps its own keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeckAction {
+    NextColumn,
+    PrevColumn,
+    NextNote,
+    PrevNote,
+    NextPage,
+    PrevPage,
+    Refresh,
+    Reply,
+    AddColumn,
+    RemoveColumn,
+    MoveColumnRight,
+    MoveColumnLeft,
+    Widen,
+    Narrow,
+    Close,
+}
+
+impl Action for DeckAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("l", Self::NextColumn),
+        ("right", Self::NextColumn),
+        ("h", Self::PrevColumn),
+        ("left", Self::PrevColumn),
+        ("j", Self::NextNote),
+        ("down", Self::NextNote),
+        ("k", Self::PrevNote),
+        ("up", Self::PrevNote),
+        ("J", Self::NextPage),
+        ("K", Self::PrevPage),
+        ("r", Self::Refresh),
+        ("enter", Self::Reply),
+        ("a", Self::AddColumn),
+        ("x", Self::RemoveColumn),
+        ("L", Self::MoveColumnRight),
+        ("H", Self::MoveColumnLeft),
+        ("+", Self::Widen),
+        ("=", Self::Widen),
+        ("-", Self::Narrow),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the search results. The query field keeps its editing keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchAction {
+    SwitchTab,
+    NextResult,
+    PrevResult,
+    Open,
+    FocusQuery,
+    TogglePin,
+    Close,
+}
+
+impl Action for SearchAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("tab", Self::SwitchTab),
+        ("j", Self::NextResult),
+        ("k", Self::PrevResult),
+        ("enter", Self::Open),
+        ("/", Self::FocusQuery),
+        ("p", Self::TogglePin),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the compose form. In the text fields, only the ones moving between fields
+/// and opening the editor are taken, and other keys edit the text.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComposeAction {
+    NextField,
+    PrevField,
+    OpenEditor,
+    /// Does what the selected field is for: picks a file, adds a poll choice, posts...
+    Confirm,
+    CycleVisibility,
+    Next,
+    Prev,
+    Remove,
+    AttachPath,
+    EditAlt,
+    ToggleSensitive,
+    EditChoice,
+    SetExpiry,
+    ToggleMultiple,
+    TogglePoll,
+}
+
+impl Action for ComposeAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("tab", Self::NextField),
+        ("shift-tab", Self::PrevField),
+        ("f2", Self::OpenEditor),
+        ("enter", Self::Confirm),
+        ("space", Self::CycleVisibility),
+        ("j", Self::Next),
+        ("k", Self::Prev),
+        ("x", Self::Remove),
+        ("backspace", Self::Remove),
+        ("u", Self::AttachPath),
+        ("a", Self::EditAlt),
+        ("s", Self::ToggleSensitive),
+        ("e", Self::EditChoice),
+        ("t", Self::SetExpiry),
+        ("m", Self::ToggleMultiple),
+        ("p", Self::TogglePoll),
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationsAction {
+    NextNotification,
+    PrevNotification,
+    NextFilter,
+    PrevFilter,
+    CycleFilter,
+    ToggleGrouped,
+    Refresh,
+    MarkAllRead,
+    Close,
+}
+
+impl Action for NotificationsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextNotification),
+        ("k", Self::PrevNotification),
+        ("l", Self::NextFilter),
+        ("h", Self::PrevFilter),
+        ("space", Self::CycleFilter),
+        ("v", Self::ToggleGrouped),
+        ("r", Self::Refresh),
+        ("m", Self::MarkAllRead),
+        ("q", Self::Close),
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum DraftsAction {
+    NextDraft,
+    PrevDraft,
+    Resume,
+    Discard,
+    Close,
+}
+
+impl Action for DraftsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextDraft),
+        ("k", Self::PrevDraft),
+        ("enter", Self::Resume),
+        ("d", Self::Discard),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the drive. Prompts keep their editing keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum DriveAction {
+    NextEntry,
+    PrevEntry,
+    Open,
+    Up,
+    NewFolder,
+    Rename,
+    Upload,
+    Cut,
+    Paste,
+    Delete,
+    Refresh,
+    Close,
+}
+
+impl Action for DriveAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextEntry),
+        ("k", Self::PrevEntry),
+        ("enter", Self::Open),
+        ("l", Self::Open),
+        ("h", Self::Up),
+        ("backspace", Self::Up),
+        ("n", Self::NewFolder),
+        ("e", Self::Rename),
+        ("u", Self::Upload),
+        ("x", Self::Cut),
+        ("p", Self::Paste),
+        ("d", Self::Delete),
+        ("r", Self::Refresh),
+        ("q", Self::Close),
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum MentionsAction {
+    SwitchTab,
+    NextNote,
+    PrevNote,
+    /// Replies to the selected note, or opens the selected conversation.
+    Confirm,
+    Refresh,
+    Close,
+}
+
+impl Action for MentionsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("tab", Self::SwitchTab),
+        ("j", Self::NextNote),
+        ("k", Self::PrevNote),
+        ("enter", Self::Confirm),
+        ("r", Self::Refresh),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the channels. The search field keeps its editing keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChannelsAction {
+    SwitchTab,
+    NextChannel,
+    PrevChannel,
+    Open,
+    ToggleFollow,
+    Refresh,
+    FocusQuery,
+    Close,
+}
+
+impl Action for ChannelsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("tab", Self::SwitchTab),
+        ("j", Self::NextChannel),
+        ("k", Self::PrevChannel),
+        ("enter", Self::Open),
+        ("f", Self::ToggleFollow),
+        ("r", Self::Refresh),
+        ("/", Self::FocusQuery),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the clips. The name prompt keeps its editing keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipsAction {
+    NextClip,
+    PrevClip,
+    /// Opens the selected clip, or adds the note to it or removes it.
+    Confirm,
+    NewClip,
+    Rename,
+    Delete,
+    Refresh,
+    Close,
+}
+
+impl Action for ClipsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextClip),
+        ("k", Self::PrevClip),
+        ("enter", Self::Confirm),
+        ("n", Self::NewClip),
+        ("e", Self::Rename),
+        ("d", Self::Delete),
+        ("r", Self::Refresh),
+        ("q", Self::Close),
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum FavoritesAction {
+    NextNote,
+    PrevNote,
+    Reply,
+    Unfavorite,
+    ClipNote,
+    Refresh,
+    Close,
+}
+
+impl Action for FavoritesAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextNote),
+        ("k", Self::PrevNote),
+        ("enter", Self::Reply),
+        ("f", Self::Unfavorite),
+        ("a", Self::ClipNote),
+        ("r", Self::Refresh),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the lists and antennas.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeedsAction {
+    NextFeed,
+    PrevFeed,
+    Open,
+    TogglePin,
+    Refresh,
+    Close,
+}
+
+impl Action for FeedsAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("j", Self::NextFeed),
+        ("k", Self::PrevFeed),
+        ("enter", Self::Open),
+        ("p", Self::TogglePin),
+        ("r", Self::Refresh),
+        ("q", Self::Close),
+    ];
+}
+
+/// Actions of the hashtag prompt. Other keys edit the hashtag.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum HashtagAction {
+    NextSuggestion,
+    PrevSuggestion,
+    Open,
+}
+
+impl Action for HashtagAction {
+    const DEFAULTS: &'static [(&'static str, Self)] = &[
+        ("down", Self::NextSuggestion),
+        ("up", Self::PrevSuggestion),
+        ("enter", Self::Open),
+    ];
+}
+
+/// A key with its modifiers, as written in the config.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Reads a key pressed in the terminal.
+    pub fn from_event(event: KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let code = match event.code {
+            // Shift-Tab is its own key code for crossterm.
+            KeyCode::BackTab => KeyCode::BackTab,
+            // uppercase letters already tell Shift apart.
+            KeyCode::Char(c) => KeyCode::Char(c),
+            code => {
+                modifiers |= event.modifiers & KeyModifiers::SHIFT;
+                code
+            }
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parses a key like `j`, `ctrl-j`, `alt-enter`, `shift-tab` or `f2`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // a lone `-` is a key, not a separator.
+        while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "m" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next()?, chars.next()) {
+            (c, None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => KeyCode::F(name.strip_prefix('f')?.parse().ok()?),
+            },
+        };
+        // like in the terminal, Shift on a letter makes it uppercase.
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Some(Self { code, modifiers })
+    }
+}
+
+/// Writes the key like the config does, with names capitalized for help texts, e.g.
+/// `j`, `Ctrl-j`, `Enter` or `Shift-Tab`.
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::BackTab => f.write_str("Shift-Tab"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Maps keys and key sequences to a screen's actions.
+pub struct Keymap<A> {
+    bindings: HashMap<Vec<Key>, A>,
+    /// The start of a sequence typed so far.
+    pending: Vec<Key>,
+}
+
+impl<A: Action> Default for Keymap<A> {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+impl<A: Action> Keymap<A> {
+    /// Binds the default keys, then the config's `overrides`.
+    ///
+    /// An overridden action loses its default keys, and an overriding key replaces the
+    /// default bound to it. Keys that can't be read are skipped.
+    pub fn new(overrides: &HashMap<String, A>) -> Self {
+        let bindings = A::DEFAULTS
+            .iter()
+            .filter(|(_, action)| !overrides.values().any(|overridden| overridden == action))
+            .map(|(keys, action)| (*keys, *action))
+            .chain(
+                overrides
+                    .iter()
+                    .map(|(keys, action)| (keys.as_str(), *action)),
+            )
+            .filter_map(|(keys, action)| {
+                let sequence: Option<Vec<Key>> = keys.split_whitespace().map(Key::parse).collect();
+                Some((sequence.filter(|keys| !keys.is_empty())?, action))
+            })
+            .collect();
+
+        Self {
+            bindings,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the action bound to the sequence ending with `event`, if any.
+    ///
+    /// Returns `None` while a sequence is being typed, and forgets it when the key doesn't continue it.
+    pub fn action(&mut self, event: KeyEvent) -> Option<A> {
+        let key = Key::from_event(event);
+        self.pending.push(key);
+        if let Some(action) = self.bindings.get(&self.pending) {
+            self.pending.clear();
+            return Some(*action);
+        }
+        if self.is_pending() {
+            return None;
+        }
+
+        // the key may start a sequence of its own.
+        let restart = self.pending.len() > 1;
+        self.pending.clear();
+        if restart {
+            self.action(event)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the keys bound to `action` as help texts show them, e.g. `g g`, or `None`
+    /// if it has none. Of several bindings, the shortest is taken.
+    pub fn keys_for(&self, action: A) -> Option<String> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(keys, _)| {
+                let text: Vec<String> = keys.iter().map(Key::to_string).collect();
+                (keys.len(), text.join(" "))
+            })
+            .min_by(|(a_len, a), (b_len, b)| (a_len, a.len(), a).cmp(&(b_len, b.len(), b)))
+            .map(|(_, text)| text)
+    }
+
+    /// Writes a help text like `Move (j/k) / Reply (Enter)` from labels and the actions
+    /// whose keys go after them, leaving out labels whose actions have no keys.
+    pub fn help(&self, hints: &[(&str, &[A])]) -> String {
+        hints
+            .iter()
+            .filter_map(|(label, actions)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .filter_map(|action| self.keys_for(*action))
+                    .collect();
+                (!keys.is_empty()).then(|| format!("{label} ({})", keys.join("/")))
+            })
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Whether a sequence was started and not finished yet.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+            && self
+                .bindings
+                .keys()
+                .any(|keys| keys.len() > self.pending.len() && keys.starts_with(&self.pending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key { code, modifiers }
+    }
+
+    fn press(keymap: &mut Keymap<HomeAction>, code: KeyCode) -> Option<HomeAction> {
+        keymap.action(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            Key::parse("j"),
+            Some(key(KeyCode::Char('j'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("ctrl-j"),
+            Some(key(KeyCode::Char('j'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            Key::parse("alt-enter"),
+            Some(key(KeyCode::Enter, KeyModifiers::ALT))
+        );
+        assert_eq!(
+            Key::parse("f2"),
+            Some(key(KeyCode::F(2), KeyModifiers::NONE))
+        );
+        assert_eq!(Key::parse("nope"), None);
+        assert_eq!(Key::parse("hyper-j"), None);
+    }
+
+    #[test]
+    fn parses_shift() {
+        assert_eq!(
+            Key::parse("shift-tab"),
+            Some(key(KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("shift-j"),
+            Some(key(KeyCode::Char('J'), KeyModifiers::NONE))
+        );
+        // crossterm reports Shift-Tab as BackTab with Shift held.
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(Some(Key::from_event(event)), Key::parse("shift-tab"));
+        let event = KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT);
+        assert_eq!(Some(Key::from_event(event)), Key::parse("J"));
+    }
+
+    #[test]
+    fn parses_dashes() {
+        assert_eq!(
+            Key::parse("-"),
+            Some(key(KeyCode::Char('-'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("ctrl--"),
+            Some(key(KeyCode::Char('-'), KeyModifiers::CONTROL))
+        );
+    }
+
+    #[test]
+    fn tells_sequences_apart() {
+        let mut keymap = Keymap::<HomeAction>::default();
+        assert_eq!(press(&mut keymap, KeyCode::Char('g')), None);
+        assert!(keymap.is_pending());
+        assert_eq!(
+            press(&mut keymap, KeyCode::Char('g')),
+            Some(HomeAction::JumpToEnd)
+        );
+        assert_eq!(press(&mut keymap, KeyCode::Char('g')), None);
+        assert_eq!(
+            press(&mut keymap, KeyCode::Char('t')),
+            Some(HomeAction::GoToTab)
+        );
+        assert!(!keymap.is_pending());
+    }
+
+    #[test]
+    fn restarts_broken_sequences() {
+        let mut keymap = Keymap::<HomeAction>::default();
+        assert_eq!(press(&mut keymap, KeyCode::Char('g')), None);
+        // `j` doesn't continue `g`, so it's taken on its own.
+        assert_eq!(
+            press(&mut keymap, KeyCode::Char('j')),
+            Some(HomeAction::NextNote)
+        );
+        assert_eq!(press(&mut keymap, KeyCode::Char('g')), None);
+        assert_eq!(press(&mut keymap, KeyCode::Char('x')), None);
+        assert!(!keymap.is_pending());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let overrides = HashMap::from([("n".to_owned(), HomeAction::NextNote)]);
+        let mut keymap = Keymap::new(&overrides);
+        assert_eq!(
+            press(&mut keymap, KeyCode::Char('n')),
+            Some(HomeAction::NextNote)
+        );
+        assert_eq!(press(&mut keymap, KeyCode::Char('j')), None);
+        // other actions keep their defaults.
+        assert_eq!(
+            press(&mut keymap, KeyCode::Char('k')),
+            Some(HomeAction::PrevNote)
+        );
+    }
+
+    #[test]
+    fn writes_help_from_bindings() {
+        let keymap = Keymap::<DeckAction>::default();
+        assert_eq!(keymap.keys_for(DeckAction::NextNote).as_deref(), Some("j"));
+        assert_eq!(
+            keymap.help(&[
+                ("Move", &[DeckAction::NextNote, DeckAction::PrevNote]),
+                ("Reply", &[DeckAction::Reply]),
+                ("Resize", &[DeckAction::Narrow, DeckAction::Widen]),
+            ]),
+            "Move (j/k) / Reply (Enter) / Resize (-/+)"
+        );
+
+        let overrides = HashMap::from([
+            ("ctrl-n".to_owned(), HomeAction::NextNote),
+            ("space".to_owned(), HomeAction::Reply),
+            ("j".to_owned(), HomeAction::JumpToStart),
+        ]);
+        let keymap = Keymap::new(&overrides);
+        assert_eq!(
+            keymap.help(&[
+                ("Move", &[HomeAction::NextNote, HomeAction::PrevNote]),
+                ("Reply", &[HomeAction::Reply]),
+                ("Top", &[HomeAction::JumpToStart]),
+                ("Bottom", &[HomeAction::JumpToEnd]),
+                ("Back", &[HomeAction::PrevTab]),
+            ]),
+            "Move (Ctrl-n/k) / Reply (Space) / Top (j) / Bottom (g g) / Back (Shift-Tab)"
+        );
+    }
+
+    #[test]
+    fn reads_config() {
+        let keys: Keys = toml::from_str(
+            r#"
+            [home]
+            ctrl-n = "next-note"
+            [drive]
+            o = "open"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(keys.home["ctrl-n"], HomeAction::NextNote);
+        assert_eq!(keys.drive["o"], DriveAction::Open);
+        assert!(!keys.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+pub mod deck;
+pub mod drafts;
+pub mod keymap;
+pub mod theme;
+pub mod time;
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{timelines::Timeline, Client};
+
+use deck::DeckColumn;
+use keymap::Keys;
+use theme::ThemeConfig;
+use time::TimeConfig;
+
+/// User settings, stored as TOML in the platform's config directory.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// The account to use on startup.
+    pub account: Option<Account>,
+    /// Timelines shown as tabs after the main ones on the home screen.
+    pub pinned_timelines: Vec<Timeline>,
+    /// Key bindings replacing the default ones, per screen.
+    #[serde(skip_serializing_if = "Keys::is_empty")]
+    pub keys: Keys,
+    #[serde(skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
+    /// How the times of notes are shown.
+    #[serde(skip_serializing_if = "TimeConfig::is_default")]
+    pub time: TimeConfig,
+    /// The deck screen's columns, left to right. Empty for the default ones.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deck: Vec<DeckColumn>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Account {
+    /// The instance's host, e.g. `misskey.io`.
+    pub instance: String,
+    /// An access token, as generated in the instance's API settings.
+    pub token: String,
+    /// The id of the account's user, looked up on the first launch to tell its drafts apart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+impl Account {
+    /// Creates an API client authenticated as this account.
+    pub fn client(&self) -> Client {
+        Client::new(self.instance.clone(), Some(self.token.clone()))
+    }
+}
+
+impl Config {
+    /// Pins `timeline` to the home screen's tabs, or unpins it if it already is.
+    pub fn toggle_pinned(&mut self, timeline: Timeline) {
+        match self.pinned_timelines.iter().position(|t| *t == timeline) {
+            Some(idx) => {
+                self.pinned_timelines.remove(idx);
+            }
+            None => self.pinned_timelines.push(timeline),
+        }
+    }
+    /// Returns the path of the config file, e.g. `~/.config/keytan/config.toml`.
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("keytan").join("config.toml"))
+    }
+    /// Loads the config file.
+    /// Falls back to the defaults if it's missing or invalid.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+    /// Writes the config file, creating its directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = toml::to_string_pretty(self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_private(&path, &text)
+    }
+}
+
+/// Writes a file that only we can read, through a new file renamed over it, so a crash
+/// never leaves it half written.
+pub fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    // left over from a crash, maybe.
+    fs::remove_file(&temp).ok();
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&temp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp, path)
+}
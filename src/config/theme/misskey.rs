@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{Palette, ThemeColor};
+
+/// How deep `@` references can go, to stop on themes referencing themselves.
+const MAX_DEPTH: usize = 16;
+
+/// A Misskey theme, as exported from its settings.
+///
+/// Only its colors are read, from `props` like `{ accent: '#86b300', bg: '#000', fg: '@accent' }`.
+/// Values can reference other props (`@fg`), constants (`$primary`) and apply functions
+/// (`:darken<5<@bg`), as in Misskey.
+#[derive(Deserialize)]
+pub struct Theme {
+    /// The built-in theme it extends, `dark` or `light`.
+    pub base: Option<String>,
+    #[serde(default)]
+    props: HashMap<String, Value>,
+}
+
+/// Expressions Misskey's base themes give props that themes usually leave out.
+const FALLBACKS: [(&str, &str); 3] = [
+    ("accentedBg", ":alpha<0.15<@accent"),
+    ("divider", ":alpha<0.1<@fg"),
+    ("cwFg", ":alpha<0.7<@fg"),
+];
+
+/// A color with its opacity, from 0 to 1.
+#[derive(Clone, Copy)]
+struct Rgba {
+    r: f64,
+    g: f64,
+    b: f64,
+    a: f64,
+}
+
+impl Theme {
+    /// Reads a theme written in JSON5.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let json = json5_to_json(text)?;
+        serde_json::from_str(&json).map_err(|err| err.to_string())
+    }
+
+    /// Picks the colors keytan uses from the theme's props.
+    pub fn palette(&self) -> Palette {
+        let bg = self.color("bg").map(|bg| Rgba { a: 1.0, ..bg });
+        let color = |prop: &str| {
+            let color = self.color(prop)?;
+            // terminals have no transparency, so it's blended into the background.
+            let color = match bg {
+                Some(bg) => color.over(bg),
+                None => color,
+            };
+            Some(ThemeColor(color.into()))
+        };
+
+        Palette {
+            fg: color("fg"),
+            bg: color("bg"),
+            accent: color("accent"),
+            selection_fg: color("accent"),
+            selection_bg: color("accentedBg"),
+            mention: color("mention"),
+            hashtag: color("hashtag"),
+            link: color("link"),
+            cw: color("cwFg"),
+            renote: color("renote"),
+            border: color("divider"),
+        }
+    }
+
+    fn color(&self, prop: &str) -> Option<Rgba> {
+        self.resolve(&self.prop(prop)?, 0)
+    }
+
+    fn prop(&self, name: &str) -> Option<String> {
+        match self.props.get(name) {
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(_) => None,
+            None => FALLBACKS
+                .iter()
+                .find(|(prop, _)| *prop == name)
+                .map(|(_, value)| (*value).to_owned()),
+        }
+    }
+
+    /// Evaluates a prop's value into a color.
+    fn resolve(&self, value: &str, depth: usize) -> Option<Rgba> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let value = value.trim();
+
+        if let Some(name) = value.strip_prefix('@') {
+            return self.resolve(&self.prop(name)?, depth + 1);
+        }
+        if value.starts_with('$') {
+            return self.resolve(&self.prop(value)?, depth + 1);
+        }
+        if let Some(call) = value.strip_prefix(':') {
+            let mut parts = call.splitn(3, '<');
+            let (function, arg, value) = (parts.next()?, parts.next()?, parts.next()?);
+            let arg: f64 = arg.trim().parse().ok()?;
+            return Some(self.resolve(value, depth + 1)?.apply(function, arg));
+        }
+        Rgba::parse(value)
+    }
+}
+
+impl Rgba {
+    /// Reads CSS colors like `#fff`, `#86b300`, `#86b30080`, `rgb(0, 0, 0)` and `rgba(0, 0, 0, 0.5)`.
+    fn parse(text: &str) -> Option<Self> {
+        if let Some(hex) = text.strip_prefix('#') {
+            let digits: Vec<u8> = hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|digit| digit as u8))
+                .collect::<Option<_>>()?;
+            let channels: Vec<f64> = match digits.len() {
+                3 | 4 => digits.iter().map(|d| f64::from(d * 17)).collect(),
+                6 | 8 => digits
+                    .chunks(2)
+                    .map(|pair| f64::from(pair[0] * 16 + pair[1]))
+                    .collect(),
+                _ => return None,
+            };
+            return Some(Self {
+                r: channels[0],
+                g: channels[1],
+                b: channels[2],
+                a: channels.get(3).map_or(1.0, |a| a / 255.0),
+            });
+        }
+        if text == "transparent" {
+            return Some(Self {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+                a: 0.0,
+            });
+        }
+
+        let args = text
+            .strip_prefix("rgba(")
+            .or_else(|| text.strip_prefix("rgb("))?
+            .strip_suffix(')')?;
+        let channels: Vec<f64> = args
+            .split(',')
+            .map(|channel| channel.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        match channels[..] {
+            [r, g, b] => Some(Self { r, g, b, a: 1.0 }),
+            [r, g, b, a] => Some(Self { r, g, b, a }),
+            _ => None,
+        }
+    }
+
+    /// Applies one of Misskey's color functions, like `darken` for `:darken<5<@bg`.
+    fn apply(self, function: &str, arg: f64) -> Self {
+        let (h, s, l) = self.hsl();
+        match function {
+            "darken" => Self::from_hsl(h, s, (l - arg / 100.0).clamp(0.0, 1.0), self.a),
+            "lighten" => Self::from_hsl(h, s, (l + arg / 100.0).clamp(0.0, 1.0), self.a),
+            "saturate" => Self::from_hsl(h, (s + arg / 100.0).clamp(0.0, 1.0), l, self.a),
+            "hue" => Self::from_hsl((h + arg).rem_euclid(360.0), s, l, self.a),
+            "alpha" => Self {
+                a: arg.clamp(0.0, 1.0),
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    /// Blends this color over an opaque `bg`.
+    fn over(self, bg: Self) -> Self {
+        let mix = |fg: f64, bg: f64| fg * self.a + bg * (1.0 - self.a);
+        Self {
+            r: mix(self.r, bg.r),
+            g: mix(self.g, bg.g),
+            b: mix(self.b, bg.b),
+            a: 1.0,
+        }
+    }
+
+    /// Returns the hue in degrees, and the saturation and lightness from 0 to 1.
+    fn hsl(self) -> (f64, f64, f64) {
+        let (r, g, b) = (self.r / 255.0, self.g / 255.0, self.b / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        if max == min {
+            return (0.0, 0.0, l);
+        }
+
+        let d = max - min;
+        let s = if l > 0.5 {
+            d / (2.0 - max - min)
+        } else {
+            d / (max + min)
+        };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    fn from_hsl(h: f64, s: f64, l: f64, a: f64) -> Self {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = l - c / 2.0;
+        let (r, g, b) = match h {
+            h if h < 60.0 => (c, x, 0.0),
+            h if h < 120.0 => (x, c, 0.0),
+            h if h < 180.0 => (0.0, c, x),
+            h if h < 240.0 => (0.0, x, c),
+            h if h < 300.0 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        Self {
+            r: (r + m) * 255.0,
+            g: (g + m) * 255.0,
+            b: (b + m) * 255.0,
+            a,
+        }
+    }
+}
+
+impl From<Rgba> for Color {
+    fn from(color: Rgba) -> Self {
+        let channel = |value: f64| value.round().clamp(0.0, 255.0) as u8;
+        Color::Rgb(channel(color.r), channel(color.g), channel(color.b))
+    }
+}
+
+/// Rewrites JSON5 as JSON: drops comments and trailing commas,
+/// and quotes keys and single-quoted strings with double quotes.
+fn json5_to_json(text: &str) -> Result<String, String> {
+    let mut json = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                json.push('"');
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            // a single quote needs no escaping in JSON.
+                            Some('\'') => json.push('\''),
+                            // a line continuation.
+                            Some('\n') => (),
+                            Some(escaped) => {
+                                json.push('\\');
+                                json.push(escaped);
+                            }
+                            None => return Err("unterminated string".into()),
+                        },
+                        Some(end) if end == c => break,
+                        Some('"') => json.push_str("\\\""),
+                        Some(c) => json.push(c),
+                        None => return Err("unterminated string".into()),
+                    }
+                }
+                json.push('"');
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        json.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err("unterminated comment".into()),
+                    }
+                }
+            }
+            '}' | ']' => {
+                let kept = json.trim_end().len();
+                json.truncate(kept);
+                if json.ends_with(',') {
+                    json.pop();
+                }
+                json.push(c);
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let mut word = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                // words before a colon are keys, the others `true`, `false` and `null`.
+                if chars.peek() == Some(&':') {
+                    json.push('"');
+                    json.push_str(&word);
+                    json.push('"');
+                } else {
+                    json.push_str(&word);
+                    json.push(' ');
+                }
+            }
+            c => json.push(c),
+        }
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(r: u8, g: u8, b: u8) -> Option<ThemeColor> {
+        Some(ThemeColor(Color::Rgb(r, g, b)))
+    }
+
+    #[test]
+    fn reads_json5() {
+        let theme = Theme::parse(
+            r##"{
+                // exported from Misskey
+                id: 'abc',
+                name: 'It\'s "dark"',
+                base: 'dark',
+                /* only colors matter */
+                props: {
+                    accent: '#86b300',
+                    bg: "#000",
+                    fg: '@accent',
+                },
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.base.as_deref(), Some("dark"));
+        let palette = theme.palette();
+        assert_eq!(palette.accent, rgb(0x86, 0xb3, 0x00));
+        assert_eq!(palette.bg, rgb(0, 0, 0));
+        assert_eq!(palette.fg, palette.accent);
+    }
+
+    #[test]
+    fn rejects_broken_json5() {
+        assert!(Theme::parse("{ name: 'unterminated }").is_err());
+        assert!(Theme::parse("{ props: {} /* open").is_err());
+        assert!(Theme::parse("{ props: ").is_err());
+    }
+
+    #[test]
+    fn resolves_references_and_functions() {
+        let theme = Theme::parse(
+            r##"{
+                props: {
+                    $primary: 'rgb(255, 0, 0)',
+                    accent: '$primary',
+                    bg: '#ffffff',
+                    fg: ':darken<50<@bg',
+                    link: ':hue<120<@accent',
+                    hashtag: ':lighten<10<#000',
+                },
+            }"##,
+        )
+        .unwrap();
+        let palette = theme.palette();
+        assert_eq!(palette.accent, rgb(255, 0, 0));
+        assert_eq!(palette.fg, rgb(128, 128, 128));
+        assert_eq!(palette.link, rgb(0, 255, 0));
+        assert_eq!(palette.hashtag, rgb(26, 26, 26));
+    }
+
+    #[test]
+    fn blends_alpha_into_the_background() {
+        let theme = Theme::parse(
+            "{ props: { bg: '#000000', fg: '#ffffff', accent: '#ff000080', mention: 'transparent' } }",
+        )
+        .unwrap();
+        let palette = theme.palette();
+        assert_eq!(palette.accent, rgb(128, 0, 0));
+        assert_eq!(palette.mention, rgb(0, 0, 0));
+        // left out, so Misskey's fallbacks apply: `cwFg` is `fg` at 70%.
+        assert_eq!(palette.cw, rgb(179, 179, 179));
+        assert_eq!(palette.selection_bg, rgb(38, 0, 0));
+    }
+
+    #[test]
+    fn stops_on_cycles() {
+        let theme =
+            Theme::parse("{ props: { fg: '@bg', bg: '@fg', accent: 'nonsense' } }").unwrap();
+        let palette = theme.palette();
+        assert_eq!(palette.fg, None);
+        assert_eq!(palette.accent, None);
+        assert_eq!(palette.renote, None);
+    }
+}
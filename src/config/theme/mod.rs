@@ -0,0 +1,221 @@
+mod misskey;
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use ratatui::style::Color;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Config;
+
+/// The theme set in the config: a built-in or Misskey theme, with some colors changed.
+///
+/// ```toml
+/// [theme]
+/// name = "dark"
+/// accent = "#86b300"
+/// ```
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// A built-in theme: `default`, `dark`, `light` or `high-contrast`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// A Misskey theme file (JSON5) to use instead of a built-in one,
+    /// relative to the config file's directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub misskey: Option<PathBuf>,
+    /// Colors replacing the theme's.
+    #[serde(flatten)]
+    pub colors: Palette,
+}
+
+/// The colors of a theme. Those left out use the terminal's.
+///
+/// Colors are written as names like `red`, indexes like `208`, or hex like `#86b300`.
+#[derive(Default, Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Palette {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<ThemeColor>,
+    /// Highlights, e.g. the selected item's borders and names.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<ThemeColor>,
+    /// The selected tab or item's text. The selection is shown reversed if neither is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashtag: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<ThemeColor>,
+    /// Content warnings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cw: Option<ThemeColor>,
+    /// Renotes' text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renote: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<ThemeColor>,
+}
+
+/// A color written in the config, read with `ratatui`'s color parser.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ThemeColor(pub Color);
+
+impl ThemeConfig {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.misskey.is_none() && self.colors.is_empty()
+    }
+
+    /// Resolves the theme's colors.
+    /// Falls back to the default theme if the name is unknown or the Misskey theme can't be read.
+    pub fn palette(&self) -> Palette {
+        let base = match &self.misskey {
+            Some(path) => {
+                let path = Config::path()
+                    .and_then(|config| Some(config.parent()?.join(path)))
+                    .unwrap_or_else(|| path.clone());
+                misskey_palette(&path).ok()
+            }
+            None => self.name.as_deref().and_then(Palette::built_in),
+        };
+        base.unwrap_or_default().with(&self.colors)
+    }
+}
+
+/// Reads a Misskey theme file, filling the colors it doesn't set from its base theme.
+fn misskey_palette(path: &Path) -> io::Result<Palette> {
+    let text = fs::read_to_string(path)?;
+    let theme = misskey::Theme::parse(&text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let base = match theme.base.as_deref() {
+        Some("light") => Palette::LIGHT,
+        _ => Palette::DARK,
+    };
+    Ok(base.with(&theme.palette()))
+}
+
+impl Palette {
+    pub const DARK: Self = Self {
+        fg: Some(ThemeColor(Color::Rgb(0xda, 0xda, 0xda))),
+        bg: Some(ThemeColor(Color::Rgb(0x17, 0x17, 0x17))),
+        accent: Some(ThemeColor(Color::Rgb(0x86, 0xb3, 0x00))),
+        selection_fg: Some(ThemeColor(Color::Rgb(0xff, 0xff, 0xff))),
+        selection_bg: Some(ThemeColor(Color::Rgb(0x35, 0x47, 0x00))),
+        mention: Some(ThemeColor(Color::Rgb(0xda, 0x6d, 0x35))),
+        hashtag: Some(ThemeColor(Color::Rgb(0xff, 0x91, 0x56))),
+        link: Some(ThemeColor(Color::Rgb(0x1b, 0xc4, 0xff))),
+        cw: Some(ThemeColor(Color::Rgb(0xb1, 0xb1, 0xb1))),
+        renote: Some(ThemeColor(Color::Rgb(0x22, 0x9e, 0x82))),
+        border: Some(ThemeColor(Color::Rgb(0x40, 0x40, 0x40))),
+    };
+
+    pub const LIGHT: Self = Self {
+        fg: Some(ThemeColor(Color::Rgb(0x5f, 0x5f, 0x5f))),
+        bg: Some(ThemeColor(Color::Rgb(0xff, 0xff, 0xff))),
+        accent: Some(ThemeColor(Color::Rgb(0x86, 0xb3, 0x00))),
+        selection_fg: Some(ThemeColor(Color::Rgb(0x2c, 0x2c, 0x2c))),
+        selection_bg: Some(ThemeColor(Color::Rgb(0xe3, 0xee, 0xc2))),
+        mention: Some(ThemeColor(Color::Rgb(0xb8, 0x4f, 0x19))),
+        hashtag: Some(ThemeColor(Color::Rgb(0xd8, 0x6a, 0x2e))),
+        link: Some(ThemeColor(Color::Rgb(0x44, 0xa4, 0xc1))),
+        cw: Some(ThemeColor(Color::Rgb(0x8a, 0x8a, 0x8a))),
+        renote: Some(ThemeColor(Color::Rgb(0x22, 0x9e, 0x82))),
+        border: Some(ThemeColor(Color::Rgb(0xd4, 0xd4, 0xd4))),
+    };
+
+    /// Only the 16 basic colors, for terminals and eyes that need them bold.
+    pub const HIGH_CONTRAST: Self = Self {
+        fg: Some(ThemeColor(Color::White)),
+        bg: Some(ThemeColor(Color::Black)),
+        accent: Some(ThemeColor(Color::LightYellow)),
+        selection_fg: Some(ThemeColor(Color::Black)),
+        selection_bg: Some(ThemeColor(Color::LightYellow)),
+        mention: Some(ThemeColor(Color::LightCyan)),
+        hashtag: Some(ThemeColor(Color::LightMagenta)),
+        link: Some(ThemeColor(Color::LightBlue)),
+        cw: Some(ThemeColor(Color::LightRed)),
+        renote: Some(ThemeColor(Color::LightGreen)),
+        border: Some(ThemeColor(Color::White)),
+    };
+
+    /// Finds a built-in theme by name.
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "high-contrast" => Some(Self::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fg.is_none()
+            && self.bg.is_none()
+            && self.accent.is_none()
+            && self.selection_fg.is_none()
+            && self.selection_bg.is_none()
+            && self.mention.is_none()
+            && self.hashtag.is_none()
+            && self.link.is_none()
+            && self.cw.is_none()
+            && self.renote.is_none()
+            && self.border.is_none()
+    }
+
+    /// Returns these colors, replaced by those set in `other`.
+    pub fn with(self, other: &Palette) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            accent: other.accent.or(self.accent),
+            selection_fg: other.selection_fg.or(self.selection_fg),
+            selection_bg: other.selection_bg.or(self.selection_bg),
+            mention: other.mention.or(self.mention),
+            hashtag: other.hashtag.or(self.hashtag),
+            link: other.link.or(self.link),
+            cw: other.cw.or(self.cw),
+            renote: other.renote.or(self.renote),
+            border: other.border.or(self.border),
+        }
+    }
+}
+
+impl Serialize for ThemeColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
+
+impl FromStr for ThemeColor {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Color::from_str(text)
+            .map(Self)
+            .map_err(|_| format!("invalid color: {text}"))
+    }
+}
+
+impl fmt::Display for ThemeColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
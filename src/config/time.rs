@@ -0,0 +1,84 @@
+use std::{fmt, str::FromStr};
+
+use chrono::FixedOffset;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// How the times of notes are shown.
+///
+/// ```toml
+/// [time]
+/// format = "absolute"
+/// timezone = "+09:00"
+/// ```
+#[derive(Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct TimeConfig {
+    pub format: TimeFormat,
+    pub timezone: TimeZone,
+}
+
+/// How a note's time is written in feeds.
+/// Note details always show the full date and time.
+#[derive(Default, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeFormat {
+    /// How long ago, like `3m` or `2h`, or the date for older notes, like `Oct 4`.
+    #[default]
+    Relative,
+    /// The date and time, like `2024-10-04 13:37`.
+    Absolute,
+}
+
+/// The timezone times are shown in: `local`, `utc`, or an offset from UTC like `+09:00`.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum TimeZone {
+    /// The system's timezone.
+    #[default]
+    Local,
+    Utc,
+    Offset(FixedOffset),
+}
+
+impl TimeConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl FromStr for TimeZone {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "utc" | "z" => Ok(Self::Utc),
+            _ => text
+                .parse()
+                .map(Self::Offset)
+                .map_err(|_| format!("invalid timezone: {text}, expected local, utc or +hh:mm")),
+        }
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Utc => write!(f, "utc"),
+            Self::Offset(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+impl Serialize for TimeZone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
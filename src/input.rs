@@ -1,11 +1,11 @@
 use std::{sync::Arc, thread};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyEventKind};
 use parking_lot::RwLock;
 
 use crate::state::KeytanState;
 
-/// Handles keyboard input and controls.
+/// Handles keyboard and mouse input, and controls.
 pub struct InputControl {
     state: Arc<RwLock<KeytanState>>,
 }
@@ -14,22 +14,31 @@ impl InputControl {
     pub fn new(state: Arc<RwLock<KeytanState>>) -> Self {
         Self { state }
     }
-    /// Listens to and handles keyboard inputs in a separate thread.
+    /// Listens to and handles keyboard and mouse inputs in a separate thread.
     pub fn listen(&mut self) {
         let state = self.state.clone();
 
         thread::spawn(move || loop {
-            if let Ok(Event::Key(key)) = event::read() {
-                if key.kind == KeyEventKind::Press {
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
                     let mut state = state.write();
 
-                    if key.code == KeyCode::Esc {
-                        state.exit();
+                    state.handle_input(key);
+                    if state.done {
                         return;
                     }
+                }
+                Ok(Event::Paste(text)) => state.write().ui.handle_paste(&text),
+                Ok(Event::Resize(_, _)) => state.write().ui.handle_resize(),
+                Ok(Event::Mouse(event)) => {
+                    let mut state = state.write();
 
-                    state.ui.handle_key(key.code);
+                    state.handle_mouse(event);
+                    if state.done {
+                        return;
+                    }
                 }
+                _ => (),
             }
         });
     }
@@ -1,15 +1,18 @@
 use std::{sync::Arc, thread, time::Duration};
 
+use config::Config;
 use input::InputControl;
 use parking_lot::RwLock;
 use state::KeytanState;
 
+pub mod api;
+pub mod config;
 pub mod input;
 pub mod state;
 pub mod ui;
 
 fn main() {
-    let app = Arc::new(RwLock::new(KeytanState::default()));
+    let app = Arc::new(RwLock::new(KeytanState::new(Config::load())));
     let mut input_control = InputControl::new(app.clone());
 
     input_control.listen();
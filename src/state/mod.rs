@@ -1,20 +1,90 @@
-use crossterm::event::KeyCode;
+pub mod session;
 
-use crate::ui::Ui;
+use std::{mem, sync::Arc};
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use parking_lot::RwLock;
+use session::Session;
+
+use crate::{
+    config::{drafts::Drafts, Config},
+    ui::{self, screens::home::Home, theme::Theme, time, Ui},
+};
 
-#[derive(Default)]
 pub struct KeytanState {
     pub ui: Ui,
+    pub config: Arc<RwLock<Config>>,
+    pub drafts: Arc<RwLock<Drafts>>,
+    /// When the app started, as a UNIX timestamp.
+    started_at: i64,
     pub done: bool,
 }
 
 impl KeytanState {
+    pub fn new(config: Config) -> Self {
+        Theme::set(Theme::new(&config.theme.palette()));
+        time::set(config.time);
+        let account = config.account.clone();
+        let config = Arc::new(RwLock::new(config));
+        let drafts = Arc::new(RwLock::new(Drafts::load()));
+        let home = match account {
+            Some(account) => Home::new(Session {
+                client: account.client(),
+                config: config.clone(),
+                drafts: drafts.clone(),
+            }),
+            None => Home::make_dummy(&config.read()),
+        };
+
+        Self {
+            ui: Ui::new(Box::new(home)),
+            // ui: Ui::new(Box::new(Login::default())),
+            config,
+            drafts,
+            started_at: Utc::now().timestamp(),
+            done: false,
+        }
+    }
     // TODO: handle key up and other events for flexibility.
-    pub fn handle_input(&mut self, key: KeyCode) {
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        // any other key than a yes cancels quitting.
+        if self.ui.quit_prompt.write().take().is_some() {
+            if key.code == KeyCode::Char('y') {
+                self.exit();
+            }
+            return;
+        }
         self.ui.handle_key(key);
+        if mem::take(&mut self.ui.quit_requested) {
+            self.request_exit();
+        }
+    }
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        // the screen is covered while asking to quit.
+        if self.ui.quit_prompt.read().is_some() {
+            return;
+        }
+        self.ui.handle_mouse(event);
+        if mem::take(&mut self.ui.quit_requested) {
+            self.request_exit();
+        }
+    }
+    /// Quits, asking first if notes written since launch weren't posted.
+    pub fn request_exit(&mut self) {
+        let unposted = self.drafts.read().count_since(self.started_at);
+        if unposted == 0 {
+            self.exit();
+            return;
+        }
+        *self.ui.quit_prompt.write() = Some(format!(
+            "{unposted} draft(s) weren't posted. They're saved, quit anyway? (y/n)"
+        ));
     }
     pub fn exit(&mut self) {
-        ratatui::restore();
+        // changes made while typing may not be saved yet.
+        self.drafts.write().save_changed().ok();
+        ui::restore();
         self.done = true;
     }
 }
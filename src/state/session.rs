@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::{
+    api::Client,
+    config::{drafts::Drafts, Config},
+};
+
+/// What screens need to talk to the instance and read or change settings.
+#[derive(Clone)]
+pub struct Session {
+    pub client: Client,
+    /// The config shared by every screen, saved on change.
+    pub config: Arc<RwLock<Config>>,
+    /// The unposted notes shared by every screen, saved on change or, while typing,
+    /// once changes settle.
+    pub drafts: Arc<RwLock<Drafts>>,
+}
+
+impl Session {
+    /// Applies `change` to the config and saves it.
+    pub fn update_config(&self, change: impl FnOnce(&mut Config)) {
+        let mut config = self.config.write();
+        change(&mut config);
+        // not being able to save shouldn't stop the current session.
+        config.save().ok();
+    }
+    /// Applies `change` to the drafts and saves them.
+    pub fn update_drafts(&self, change: impl FnOnce(&mut Drafts)) {
+        let mut drafts = self.drafts.write();
+        change(&mut drafts);
+        drafts.save().ok();
+    }
+    /// Applies `change` to the drafts, leaving them to be saved once they stop changing.
+    /// Meant for changes made while typing, with `save_settled_drafts` called regularly.
+    pub fn edit_drafts(&self, change: impl FnOnce(&mut Drafts)) {
+        let mut drafts = self.drafts.write();
+        change(&mut drafts);
+        drafts.mark_changed();
+    }
+    /// Saves changes made by `edit_drafts` if the drafts stayed unchanged for a moment.
+    pub fn save_settled_drafts(&self) {
+        self.drafts.write().save_settled().ok();
+    }
+    /// Saves changes made by `edit_drafts` right away.
+    pub fn save_changed_drafts(&self) {
+        self.drafts.write().save_changed().ok();
+    }
+    /// Returns the id of the signed in user, once looked up.
+    pub fn user_id(&self) -> Option<String> {
+        let config = self.config.read();
+        config.account.as_ref()?.user_id.clone()
+    }
+}
@@ -0,0 +1,34 @@
+use std::{
+    env,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Opens a URL in `$BROWSER`, or the system's default browser.
+///
+/// Doesn't wait for the browser, which runs beside the TUI with its output dropped.
+pub fn open(url: &str) -> Result<(), String> {
+    let browser = env::var("BROWSER").ok();
+    let mut words: Vec<&str> = match &browser {
+        Some(browser) => browser.split_whitespace().collect(),
+        None if cfg!(target_os = "macos") => vec!["open"],
+        None if cfg!(windows) => vec!["cmd", "/C", "start", ""],
+        None => vec!["xdg-open"],
+    };
+    if words.is_empty() {
+        return Err("$BROWSER is empty.".into());
+    }
+    let program = words.remove(0);
+
+    let mut child = Command::new(program)
+        .args(words)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("Can't run {program}: {err}"))?;
+    // reaps it once it exits.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
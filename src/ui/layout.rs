@@ -0,0 +1,53 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+/// The smallest terminal screens are laid out for. Smaller ones get a message instead.
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 12;
+
+/// Areas narrower than this are `Breakpoint::Narrow`.
+const NARROW_BELOW: u16 = 60;
+/// Areas at least this wide are `Breakpoint::Wide`.
+const WIDE_FROM: u16 = 120;
+/// Areas shorter than this are too short for boxed headers.
+const SHORT_BELOW: u16 = 20;
+
+/// How much room there is across, for screens to pick their layout.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Breakpoint {
+    /// Notes lose their boxes and padding.
+    Narrow,
+    Regular,
+    /// Feeds show the selected note in full beside the list.
+    Wide,
+}
+
+impl Breakpoint {
+    pub fn of(area: Rect) -> Self {
+        match area.width {
+            width if width < NARROW_BELOW => Self::Narrow,
+            width if width < WIDE_FROM => Self::Regular,
+            _ => Self::Wide,
+        }
+    }
+}
+
+/// Whether an area is too short for boxed headers, which then take a single row.
+pub fn is_short(area: Rect) -> bool {
+    area.height < SHORT_BELOW
+}
+
+/// Whether the terminal is too small to lay screens out in.
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Centers a box of `width` by `height` in `area`, shrunk to fit it.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height.min(area.height))])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width.min(area.width))])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
@@ -1,55 +1,194 @@
-use std::{sync::Arc, thread};
+use std::{io, mem, sync::Arc, thread};
 
-use crossterm::event::KeyCode;
+use crossterm::{
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        KeyEvent, MouseEvent,
+    },
+    execute,
+    terminal::{self, EnterAlternateScreen},
+};
 use parking_lot::RwLock;
-use ratatui::DefaultTerminal;
-use screens::{home::Home, login::Login, Screen};
+use ratatui::{
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    DefaultTerminal,
+};
+use screens::{Navigation, Screen, Suspend};
+use theme::Theme;
 
+pub mod browser;
+pub mod layout;
 pub mod screens;
+pub mod tasks;
+pub mod theme;
+pub mod time;
 pub mod widgets;
 
 pub struct Ui {
     /// A handle to `ratatui`'s terminal for rendering.
     pub terminal: Arc<RwLock<DefaultTerminal>>,
     /// The screen to render.
-    pub current_screen: Option<Arc<RwLock<Box<dyn Screen + Send + Sync>>>>,
+    pub current_screen: Arc<RwLock<Option<Box<dyn Screen + Send + Sync>>>>,
+    /// Screens to go back to, the most recent last.
+    pub previous_screens: Vec<Box<dyn Screen + Send + Sync>>,
+    /// The question shown over the screen before quitting, if any.
+    pub quit_prompt: Arc<RwLock<Option<String>>>,
+    /// Whether a screen asked to quit while handling the last key.
+    pub quit_requested: bool,
 }
 
 impl Ui {
+    /// Initialises the terminal, starting on `screen`.
+    pub fn new(screen: Box<dyn Screen + Send + Sync>) -> Self {
+        let terminal = Arc::new(RwLock::new(ratatui::init()));
+        execute!(io::stdout(), EnableBracketedPaste, EnableMouseCapture).ok();
+
+        Self {
+            terminal,
+            current_screen: Arc::new(RwLock::new(Some(screen))),
+            previous_screens: Vec::new(),
+            quit_prompt: Arc::new(RwLock::new(None)),
+            quit_requested: false,
+        }
+    }
+
     /// Draws the current screen's contents.
     pub fn draw(&mut self) {
         let terminal = self.terminal.clone();
         let screen = self.current_screen.clone();
+        let quit_prompt = self.quit_prompt.clone();
 
         thread::spawn(move || loop {
-            let screen = screen.clone();
             terminal
                 .write()
                 .draw(|frame| {
-                    if let Some(screen) = screen {
-                        screen.write().view(frame);
+                    let theme = Theme::current();
+                    frame.render_widget(theme.background(), frame.area());
+                    let area = frame.area();
+                    if layout::is_too_small(area) {
+                        let message = format!(
+                            "The terminal is {}x{}, keytan needs at least {}x{}.",
+                            area.width,
+                            area.height,
+                            layout::MIN_WIDTH,
+                            layout::MIN_HEIGHT
+                        );
+                        let message = Paragraph::new(message).wrap(Wrap { trim: true });
+                        let height = message.line_count(area.width) as u16;
+                        frame.render_widget(
+                            message.centered(),
+                            layout::centered(area, area.width, height),
+                        );
+                        return;
+                    }
+                    if let Some(screen) = screen.write().as_mut() {
+                        screen.view(frame);
+                    }
+                    if let Some(prompt) = quit_prompt.read().as_ref() {
+                        let popup = Paragraph::new(prompt.clone())
+                            .wrap(Wrap { trim: true })
+                            .block(
+                                Block::new()
+                                    .borders(Borders::ALL)
+                                    .border_type(BorderType::Plain)
+                                    .border_style(theme.accent)
+                                    .title_top("Quit"),
+                            );
+                        let width = area.width.min(48);
+                        let height = popup.line_count(width.saturating_sub(2)) as u16;
+                        let area = layout::centered(area, width, height);
+                        frame.render_widget(Clear, area);
+                        frame.render_widget(theme.background(), area);
+                        frame.render_widget(popup, area);
                     }
                 })
                 .ok();
         });
     }
 
+    /// Redraws everything after the terminal was resized, as some terminals leave bits
+    /// of the old frame behind.
+    pub fn handle_resize(&mut self) {
+        let mut terminal = self.terminal.write();
+        terminal.autoresize().ok();
+        terminal.clear().ok();
+    }
+
     /// Handles a key press.
-    pub fn handle_key(&mut self, key: KeyCode) {
-        if let Some(screen) = &mut self.current_screen {
-            screen.write().handle_key(key);
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.handle_event(|screen| screen.handle_key_event(key));
+    }
+
+    /// Handles a click or scroll.
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        self.handle_event(|screen| screen.handle_mouse(event));
+    }
+
+    /// Passes an event to the current screen, then does what it asked for.
+    fn handle_event(&mut self, handle: impl FnOnce(&mut Box<dyn Screen + Send + Sync>)) {
+        let (navigation, suspend) = match self.current_screen.write().as_mut() {
+            Some(screen) => {
+                handle(screen);
+                (screen.navigation(), screen.suspend())
+            }
+            None => (None, None),
+        };
+
+        if let Some(suspend) = suspend {
+            self.suspend(suspend);
+        }
+        if let Some(navigation) = navigation {
+            self.navigate(navigation);
         }
     }
-}
 
-impl Default for Ui {
-    fn default() -> Self {
-        let terminal = Arc::new(RwLock::new(ratatui::init()));
+    /// Handles pasted text.
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(screen) = self.current_screen.write().as_mut() {
+            screen.handle_paste(text);
+        }
+    }
 
-        Self {
-            terminal,
-            current_screen: Some(Arc::new(RwLock::new(Box::new(Home::make_dummy())))),
-            // current_screen: Some(Arc::new(RwLock::new(Box::new(Login::default())))),
+    /// Hands the terminal over to `suspend`, then takes it back.
+    fn suspend(&mut self, suspend: Suspend) {
+        // holding the terminal keeps the drawing thread waiting.
+        let mut terminal = self.terminal.write();
+        restore();
+        suspend();
+
+        terminal::enable_raw_mode().ok();
+        execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableBracketedPaste,
+            EnableMouseCapture
+        )
+        .ok();
+        terminal.clear().ok();
+    }
+
+    /// Switches screens.
+    fn navigate(&mut self, navigation: Navigation) {
+        let mut current = self.current_screen.write();
+        match navigation {
+            Navigation::Push(screen) => {
+                if let Some(previous) = mem::replace(&mut *current, Some(screen)) {
+                    self.previous_screens.push(previous);
+                }
+            }
+            Navigation::Replace(screen) => *current = Some(screen),
+            Navigation::Back => {
+                if let Some(previous) = self.previous_screens.pop() {
+                    *current = Some(previous);
+                }
+            }
+            Navigation::Quit => self.quit_requested = true,
         }
     }
 }
+
+/// Gives the terminal back as it was before starting.
+pub fn restore() {
+    execute!(io::stdout(), DisableBracketedPaste, DisableMouseCapture).ok();
+    ratatui::restore();
+}
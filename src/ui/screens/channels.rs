@@ -0,0 +1,353 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        channels::{Channel, ChannelListing},
+        timelines::Timeline,
+        Error,
+    },
+    config::keymap::{ChannelsAction, Keymap},
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        theme::Theme,
+        widgets::{channel::ChannelItem, text_input::TextInput},
+    },
+};
+
+use super::{timeline::TimelineScreen, Direction, Navigation, Screen};
+
+/// Browses featured, followed and owned channels, or searches them.
+pub struct ChannelBrowser {
+    session: Session,
+    tasks: Tasks<Message>,
+    tab: Tab,
+    /// Whether keys go to the query box, on the search tab.
+    editing_query: bool,
+    query: TextInput,
+    channels: Vec<Channel>,
+    list_state: ListState,
+    keys: Keymap<ChannelsAction>,
+    /// Whether a request is running.
+    loading: bool,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tab {
+    Listing(ChannelListing),
+    Search,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [
+        Tab::Listing(ChannelListing::Featured),
+        Tab::Listing(ChannelListing::Followed),
+        Tab::Listing(ChannelListing::Owned),
+        Tab::Search,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Tab::Listing(ChannelListing::Featured) => "Featured",
+            Tab::Listing(ChannelListing::Followed) => "Following",
+            Tab::Listing(ChannelListing::Owned) => "Owned",
+            Tab::Search => "Search",
+        }
+    }
+}
+
+pub enum Message {
+    /// Goes to the next tab and loads it.
+    SwitchTab,
+    /// Edits the query.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the query.
+    Paste(String),
+    /// Searches channels for the query.
+    Submit,
+    /// Goes back to editing the query.
+    FocusQuery,
+    /// Selects the next or previous channel.
+    Select(Direction),
+    /// Opens the selected channel's timeline.
+    Open,
+    /// Follows the selected channel, or unfollows it.
+    ToggleFollow,
+    Refresh,
+    Loaded(Result<Vec<Channel>, Error>),
+    /// Receives the outcome of following or unfollowing a channel.
+    FollowChanged {
+        channel_id: String,
+        following: bool,
+        result: Result<(), Error>,
+    },
+    Close,
+}
+
+impl ChannelBrowser {
+    /// Creates the screen and starts fetching featured channels.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.channels);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            tab: Tab::ALL[0],
+            editing_query: false,
+            query: TextInput::default(),
+            channels: Vec::new(),
+            list_state: ListState::default(),
+            keys,
+            loading: false,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SwitchTab => {
+                let idx = Tab::ALL
+                    .iter()
+                    .position(|tab| *tab == self.tab)
+                    .unwrap_or(0);
+                self.tab = Tab::ALL[(idx + 1) % Tab::ALL.len()];
+                self.channels.clear();
+                self.list_state = ListState::default();
+                self.status = None;
+                self.editing_query = self.tab == Tab::Search;
+                if self.tab != Tab::Search {
+                    self.message(Message::Refresh);
+                }
+            }
+            Message::Edit(key) => {
+                self.query.handle_key(key);
+            }
+            Message::Paste(text) => self.query.insert(&text),
+            Message::Submit => {
+                self.editing_query = false;
+                self.message(Message::Refresh);
+            }
+            Message::FocusQuery => self.editing_query = self.tab == Tab::Search,
+            Message::Select(Direction::Next) => self.list_state.next(),
+            Message::Select(Direction::Previous) => self.list_state.previous(),
+            Message::Open => {
+                if let Some(channel) = self.selected() {
+                    let timeline = Timeline::Channel {
+                        id: channel.id.clone(),
+                        name: channel.name.clone(),
+                    };
+                    let screen = TimelineScreen::new(self.session.clone(), timeline);
+                    self.navigation = Some(Navigation::Push(Box::new(screen)));
+                }
+            }
+            Message::ToggleFollow => {
+                if let Some(channel) = self.selected() {
+                    let client = self.session.client.clone();
+                    let channel_id = channel.id.clone();
+                    let following = !channel.is_following;
+                    self.tasks.spawn(move || Message::FollowChanged {
+                        result: client.set_channel_following(&channel_id, following),
+                        channel_id,
+                        following,
+                    });
+                }
+            }
+            Message::Refresh => {
+                let client = self.session.client.clone();
+                match self.tab {
+                    Tab::Listing(listing) => {
+                        self.tasks
+                            .spawn(move || Message::Loaded(client.channels(listing)));
+                    }
+                    Tab::Search => {
+                        let query = self.query.text().trim().to_owned();
+                        if query.is_empty() {
+                            return;
+                        }
+                        self.tasks
+                            .spawn(move || Message::Loaded(client.search_channels(&query)));
+                    }
+                }
+                self.loading = true;
+            }
+            Message::Loaded(result) => {
+                self.loading = false;
+                match result {
+                    Ok(channels) => {
+                        self.status = None;
+                        self.channels = channels;
+                        self.list_state = ListState::default();
+                        if !self.channels.is_empty() {
+                            self.list_state.select(Some(0));
+                        }
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::FollowChanged {
+                channel_id,
+                following,
+                result,
+            } => match result {
+                Ok(()) => {
+                    if let Some(channel) = self
+                        .channels
+                        .iter_mut()
+                        .find(|channel| channel.id == channel_id)
+                    {
+                        channel.is_following = following;
+                        self.status = Some(if following {
+                            format!("Following {}.", channel.name)
+                        } else {
+                            format!("Unfollowed {}.", channel.name)
+                        });
+                    }
+                }
+                Err(err) => self.status = Some(err.to_string()),
+            },
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    fn selected(&self) -> Option<&Channel> {
+        self.channels.get(self.list_state.selected?)
+    }
+
+    fn tab_bar(&self) -> Line<'static> {
+        let spans = Tab::ALL.iter().flat_map(|tab| {
+            let style = if *tab == self.tab {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            [
+                Span::styled(format!(" {} ", tab.label()), style),
+                Span::raw(" "),
+            ]
+        });
+
+        Line::from(spans.collect::<Vec<_>>())
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: ChannelsAction) {
+        self.message(match action {
+            ChannelsAction::SwitchTab => Message::SwitchTab,
+            ChannelsAction::NextChannel => Message::Select(Direction::Next),
+            ChannelsAction::PrevChannel => Message::Select(Direction::Previous),
+            ChannelsAction::Open => Message::Open,
+            ChannelsAction::ToggleFollow => Message::ToggleFollow,
+            ChannelsAction::Refresh => Message::Refresh,
+            ChannelsAction::FocusQuery => Message::FocusQuery,
+            ChannelsAction::Close => Message::Close,
+        });
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading {
+            return "Loading...".into();
+        }
+        if self.editing_query {
+            let switch = self
+                .keys
+                .help(&[("Switch tab", &[ChannelsAction::SwitchTab])]);
+            return format!("Search (Enter) / {switch}");
+        }
+
+        let follow = match self.selected() {
+            Some(channel) if channel.is_following => "Unfollow",
+            _ => "Follow",
+        };
+        self.keys.help(&[
+            (
+                "Move",
+                &[ChannelsAction::NextChannel, ChannelsAction::PrevChannel],
+            ),
+            ("Open", &[ChannelsAction::Open]),
+            (follow, &[ChannelsAction::ToggleFollow]),
+            ("Switch tab", &[ChannelsAction::SwitchTab]),
+            ("Refresh", &[ChannelsAction::Refresh]),
+            ("Back", &[ChannelsAction::Close]),
+        ])
+    }
+}
+
+impl Screen for ChannelBrowser {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let query_height = if self.tab == Tab::Search { 3 } else { 0 };
+        let [tabs_area, query_area, list_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(query_height),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(self.tab_bar(), tabs_area);
+        if self.tab == Tab::Search {
+            let query_box = Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Plain)
+                .title_top("Search channels");
+            let input_area = query_box.inner(query_area);
+            frame.render_widget(query_box, query_area);
+            self.query.render(frame, input_area, self.editing_query);
+        }
+
+        let channels = &self.channels;
+        let builder = ListBuilder::new(move |ctx| {
+            let mut item = ChannelItem::new(&channels[ctx.index]);
+            item.selected = ctx.is_selected;
+            (item, ChannelItem::HEIGHT)
+        });
+        let list_view = ListView::new(builder, channels.len());
+        frame.render_stateful_widget(list_view, list_area, &mut self.list_state);
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        let action = self.keys.action(key);
+        match (self.editing_query, action, key.code) {
+            (true, Some(ChannelsAction::SwitchTab), _) => self.message(Message::SwitchTab),
+            (true, _, KeyCode::Enter) => self.message(Message::Submit),
+            (true, _, _) => self.message(Message::Edit(key)),
+            (false, Some(action), _) => self.action(action),
+            _ => (),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.editing_query {
+            self.message(Message::Paste(text.to_owned()));
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
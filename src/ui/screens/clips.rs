@@ -0,0 +1,456 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{clips::Clip, timelines::Timeline, Error},
+    config::keymap::{ClipsAction, Keymap},
+    state::session::Session,
+    ui::{tasks::Tasks, theme::Theme, widgets::text_input::TextInput},
+};
+
+use super::{timeline::TimelineScreen, Direction, Navigation, Screen};
+
+/// Lists our clips, to open them as feeds or manage them.
+///
+/// When opened for a note, confirming adds the note to the selected clip or removes it instead.
+pub struct Clips {
+    session: Session,
+    tasks: Tasks<Message>,
+    /// The note to add to clips, if any.
+    note_id: Option<String>,
+    clips: Vec<Clip>,
+    /// The ids of the clips that contain the note.
+    containing: Vec<String>,
+    list_state: ListState,
+    keys: Keymap<ClipsAction>,
+    /// The name being typed, if any.
+    editing: Option<Edit>,
+    /// The id of the clip to delete on the next `d` key.
+    confirm_delete: Option<String>,
+    /// How many requests are running.
+    loading: usize,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+/// A clip name being typed.
+pub struct Edit {
+    /// The clip to rename, or `None` to create one.
+    clip_id: Option<String>,
+    name: TextInput,
+}
+
+pub enum Message {
+    /// Selects the next or previous clip.
+    Select(Direction),
+    /// Opens the selected clip, or adds the note to it or removes it.
+    Confirm,
+    /// Starts typing the name of a new clip.
+    StartCreate,
+    /// Starts typing a new name for the selected clip.
+    StartRename,
+    /// Edits the name.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the name.
+    Paste(String),
+    /// Creates or renames the clip with the typed name.
+    SubmitName,
+    /// Stops typing the name.
+    CancelEdit,
+    /// Asks to confirm deleting the selected clip, or deletes it if already asked.
+    DeleteClip,
+    Refresh,
+    Loaded(Result<Vec<Clip>, Error>),
+    /// Receives the clips that contain the note.
+    LoadedContaining(Result<Vec<Clip>, Error>),
+    /// Receives a created or renamed clip.
+    Saved(Result<Clip, Error>),
+    /// Receives the outcome of deleting a clip.
+    Deleted {
+        clip_id: String,
+        result: Result<(), Error>,
+    },
+    /// Receives the outcome of adding the note to a clip or removing it.
+    ClippedChanged {
+        clip_id: String,
+        clipped: bool,
+        result: Result<(), Error>,
+    },
+    Close,
+}
+
+impl Clips {
+    /// Creates the screen and starts fetching our clips.
+    pub fn new(session: Session) -> Self {
+        Self::with_note(session, None)
+    }
+
+    /// Creates the screen to add a note to clips or remove it from them.
+    pub fn for_note(session: Session, note_id: String) -> Self {
+        Self::with_note(session, Some(note_id))
+    }
+
+    fn with_note(session: Session, note_id: Option<String>) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.clips);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            note_id,
+            clips: Vec::new(),
+            containing: Vec::new(),
+            list_state: ListState::default(),
+            keys,
+            editing: None,
+            confirm_delete: None,
+            loading: 0,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Select(Direction::Next) => self.list_state.next(),
+            Message::Select(Direction::Previous) => self.list_state.previous(),
+            Message::Confirm => self.confirm(),
+            Message::StartCreate => {
+                self.editing = Some(Edit {
+                    clip_id: None,
+                    name: TextInput::default(),
+                })
+            }
+            Message::StartRename => {
+                if let Some(clip) = self.selected() {
+                    self.editing = Some(Edit {
+                        clip_id: Some(clip.id.clone()),
+                        name: TextInput::new(clip.name.clone()),
+                    });
+                }
+            }
+            Message::Edit(key) => {
+                if let Some(edit) = &mut self.editing {
+                    edit.name.handle_key(key);
+                }
+            }
+            Message::Paste(text) => {
+                if let Some(edit) = &mut self.editing {
+                    edit.name.insert(&text);
+                }
+            }
+            Message::SubmitName => {
+                let Some(edit) = self.editing.take() else {
+                    return;
+                };
+                let name = edit.name.text().trim().to_owned();
+                if name.is_empty() {
+                    self.status = Some("A clip needs a name.".into());
+                    return;
+                }
+
+                let client = self.session.client.clone();
+                self.loading += 1;
+                self.tasks.spawn(move || {
+                    Message::Saved(match edit.clip_id {
+                        Some(clip_id) => client.rename_clip(&clip_id, &name),
+                        None => client.create_clip(&name),
+                    })
+                });
+            }
+            Message::CancelEdit => self.editing = None,
+            Message::DeleteClip => {
+                let Some(clip) = self.selected() else {
+                    return;
+                };
+                let clip_id = clip.id.clone();
+                if self.confirm_delete.as_ref() != Some(&clip_id) {
+                    let key = self.keys.keys_for(ClipsAction::Delete).unwrap_or_default();
+                    self.status = Some(format!("Press {key} again to delete {}.", clip.name));
+                    self.confirm_delete = Some(clip_id);
+                    return;
+                }
+
+                let client = self.session.client.clone();
+                self.confirm_delete = None;
+                self.loading += 1;
+                self.tasks.spawn(move || Message::Deleted {
+                    result: client.delete_clip(&clip_id),
+                    clip_id,
+                });
+            }
+            Message::Refresh => {
+                let client = self.session.client.clone();
+                self.tasks.spawn(move || Message::Loaded(client.clips()));
+                self.loading += 1;
+
+                if let Some(note_id) = self.note_id.clone() {
+                    let client = self.session.client.clone();
+                    self.tasks
+                        .spawn(move || Message::LoadedContaining(client.note_clips(&note_id)));
+                    self.loading += 1;
+                }
+            }
+            Message::Loaded(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(clips) => self.clips = clips,
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                self.fix_selection();
+            }
+            Message::LoadedContaining(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(clips) => self.containing = clips.into_iter().map(|clip| clip.id).collect(),
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Saved(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(saved) => match self.clips.iter_mut().find(|clip| clip.id == saved.id) {
+                        Some(clip) => {
+                            self.status = Some(format!("Renamed to {}.", saved.name));
+                            *clip = saved;
+                        }
+                        None => {
+                            self.status = Some(format!("Created {}.", saved.name));
+                            self.clips.insert(0, saved);
+                            self.list_state.select(Some(0));
+                        }
+                    },
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Deleted { clip_id, result } => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(()) => {
+                        self.clips.retain(|clip| clip.id != clip_id);
+                        self.status = Some("Clip deleted.".into());
+                        self.fix_selection();
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::ClippedChanged {
+                clip_id,
+                clipped,
+                result,
+            } => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(()) => {
+                        self.containing.retain(|id| *id != clip_id);
+                        if clipped {
+                            self.containing.push(clip_id);
+                        }
+                        self.status = Some(if clipped {
+                            "Added to the clip.".into()
+                        } else {
+                            "Removed from the clip.".into()
+                        });
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    fn confirm(&mut self) {
+        let Some(clip) = self.selected() else {
+            return;
+        };
+
+        match &self.note_id {
+            Some(note_id) => {
+                let client = self.session.client.clone();
+                let clip_id = clip.id.clone();
+                let note_id = note_id.clone();
+                let clipped = !self.containing.contains(&clip_id);
+                self.loading += 1;
+                self.tasks.spawn(move || Message::ClippedChanged {
+                    result: client.set_clipped(&clip_id, &note_id, clipped),
+                    clip_id,
+                    clipped,
+                });
+            }
+            None => {
+                let timeline = Timeline::Clip {
+                    id: clip.id.clone(),
+                    name: clip.name.clone(),
+                };
+                let screen = TimelineScreen::new(self.session.clone(), timeline);
+                self.navigation = Some(Navigation::Push(Box::new(screen)));
+            }
+        }
+    }
+
+    fn selected(&self) -> Option<&Clip> {
+        self.clips.get(self.list_state.selected?)
+    }
+
+    /// Keeps the selection within the clips after they changed.
+    fn fix_selection(&mut self) {
+        let len = self.clips.len();
+        let selected = match self.list_state.selected {
+            _ if len == 0 => None,
+            Some(idx) => Some(idx.min(len - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    /// Describes a clip, e.g. `Recipes (public) [contains note]`.
+    fn label(&self, clip: &Clip) -> String {
+        let public = if clip.is_public { " (public)" } else { "" };
+        let contains = if self.containing.contains(&clip.id) {
+            " [contains note]"
+        } else {
+            ""
+        };
+        format!("{}{public}{contains}", clip.name)
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: ClipsAction) {
+        self.message(match action {
+            ClipsAction::NextClip => Message::Select(Direction::Next),
+            ClipsAction::PrevClip => Message::Select(Direction::Previous),
+            ClipsAction::Confirm => Message::Confirm,
+            ClipsAction::NewClip => Message::StartCreate,
+            ClipsAction::Rename => Message::StartRename,
+            ClipsAction::Delete => Message::DeleteClip,
+            ClipsAction::Refresh => Message::Refresh,
+            ClipsAction::Close => Message::Close,
+        });
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading > 0 {
+            return "Loading...".into();
+        }
+        if self.editing.is_some() {
+            return "Save (Enter) / Cancel (Tab)".into();
+        }
+
+        let confirm = if self.note_id.is_some() {
+            "Add or remove note"
+        } else {
+            "Open"
+        };
+        self.keys.help(&[
+            ("Move", &[ClipsAction::NextClip, ClipsAction::PrevClip]),
+            (confirm, &[ClipsAction::Confirm]),
+            ("New", &[ClipsAction::NewClip]),
+            ("Rename", &[ClipsAction::Rename]),
+            ("Delete", &[ClipsAction::Delete]),
+            ("Refresh", &[ClipsAction::Refresh]),
+            ("Back", &[ClipsAction::Close]),
+        ])
+    }
+}
+
+impl Screen for Clips {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let edit_height = if self.editing.is_some() { 3 } else { 0 };
+        let [list_area, edit_area, status_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(edit_height),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let title = if self.note_id.is_some() {
+            "Add to clips"
+        } else {
+            "Clips"
+        };
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_type(BorderType::Plain)
+            .title_top(title);
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+
+        let labels: Vec<String> = self.clips.iter().map(|clip| self.label(clip)).collect();
+        let count = labels.len();
+        let builder = ListBuilder::new(move |ctx| {
+            let style = if ctx.is_selected {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            (Line::styled(labels[ctx.index].clone(), style), 1)
+        });
+        frame.render_stateful_widget(ListView::new(builder, count), inner, &mut self.list_state);
+
+        if let Some(edit) = &mut self.editing {
+            let title = if edit.clip_id.is_some() {
+                "Rename clip"
+            } else {
+                "New clip"
+            };
+            let name_box = Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Plain)
+                .title_top(title);
+            let input_area = name_box.inner(edit_area);
+            frame.render_widget(name_box, edit_area);
+            edit.name.render(frame, input_area, true);
+        }
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.editing.is_some() {
+            match key.code {
+                KeyCode::Enter => self.message(Message::SubmitName),
+                KeyCode::Tab => self.message(Message::CancelEdit),
+                _ => self.message(Message::Edit(key)),
+            }
+            return;
+        }
+        let action = self.keys.action(key);
+        // any other key cancels a pending delete.
+        if action != Some(ClipsAction::Delete) && self.confirm_delete.take().is_some() {
+            self.status = None;
+        }
+
+        if let Some(action) = action {
+            self.action(action);
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.editing.is_some() {
+            self.message(Message::Paste(text.to_owned()));
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fs, path::PathBuf};
+
+use crate::{api::drive::DriveFile, ui::screens::drive::format_size};
+
+/// A file attached to the note being written.
+pub struct Attachment {
+    pub name: String,
+    pub file: AttachmentFile,
+    /// The alt text to set on the file before posting.
+    pub alt: String,
+    /// Whether to mark the file as sensitive before posting.
+    pub sensitive: bool,
+}
+
+pub enum AttachmentFile {
+    /// A local file still being sent to the Drive.
+    Uploading {
+        id: usize,
+        sent: u64,
+        size: u64,
+    },
+    Ready(DriveFile),
+}
+
+impl Attachment {
+    /// Attaches a file that's already in the Drive.
+    pub fn from_drive(file: DriveFile) -> Self {
+        Self {
+            name: file.name.clone(),
+            alt: file.comment.clone().unwrap_or_default(),
+            sensitive: file.is_sensitive,
+            file: AttachmentFile::Ready(file),
+        }
+    }
+
+    /// The Drive file, once uploaded.
+    pub fn drive_file(&self) -> Option<&DriveFile> {
+        match &self.file {
+            AttachmentFile::Ready(file) => Some(file),
+            AttachmentFile::Uploading { .. } => None,
+        }
+    }
+
+    /// Whether the alt text or the sensitive flag differ from the Drive file's.
+    pub fn is_changed(&self) -> bool {
+        self.drive_file().is_some_and(|file| {
+            file.is_sensitive != self.sensitive
+                || file.comment.as_deref().unwrap_or_default() != self.alt
+        })
+    }
+
+    /// Describes the attachment, e.g. `cat.png (uploading 1.2 MB of 3.4 MB) [sensitive] alt: A cat`.
+    pub fn label(&self) -> String {
+        let mut label = self.name.clone();
+        if let AttachmentFile::Uploading { sent, size, .. } = self.file {
+            label.push_str(&format!(
+                " (uploading {} of {})",
+                format_size(sent),
+                format_size(size)
+            ));
+        }
+        if self.sensitive {
+            label.push_str(" [sensitive]");
+        }
+        if !self.alt.is_empty() {
+            label.push_str(&format!(" alt: {}", self.alt));
+        }
+        label
+    }
+}
+
+/// Expands a leading `~` to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Completes a local path as far as it's unambiguous.
+///
+/// Returns the completed input, and the names of the candidates if there are several.
+pub fn complete_path(input: &str) -> (String, Vec<String>) {
+    let (dir, prefix) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+    let search_dir = if dir.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(dir)
+    };
+    let Ok(entries) = fs::read_dir(search_dir) else {
+        return (input.to_owned(), Vec::new());
+    };
+
+    let mut candidates: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let mut name = entry.file_name().to_str()?.to_owned();
+            // hidden files only show up when asked for.
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            if entry.path().is_dir() {
+                name.push('/');
+            }
+            Some(name)
+        })
+        .collect();
+    candidates.sort();
+
+    match candidates.as_slice() {
+        [] => (input.to_owned(), Vec::new()),
+        [only] => (format!("{dir}{only}"), Vec::new()),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, name| {
+                let len = common
+                    .char_indices()
+                    .zip(name.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((idx, c), _)| idx + c.len_utf8());
+                &common[..len]
+            });
+            (format!("{dir}{common}"), candidates)
+        }
+    }
+}
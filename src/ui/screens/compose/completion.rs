@@ -0,0 +1,127 @@
+use crate::api::{emojis::Emoji, models::User};
+
+/// How many completions to show at once.
+pub const MAX_COMPLETIONS: usize = 8;
+
+/// What is being completed.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// A user, after `@`.
+    Mention,
+    /// A hashtag, after `#`.
+    Hashtag,
+    /// A custom emoji, after `:`.
+    Emoji,
+}
+
+/// The word being typed before the cursor, when it can be completed.
+#[derive(Clone, PartialEq)]
+pub struct Query {
+    pub trigger: Trigger,
+    /// What's typed after the trigger character.
+    pub text: String,
+    /// Where the word starts in the text, in bytes.
+    pub start: usize,
+}
+
+/// A way to finish the word being typed.
+pub struct Completion {
+    pub label: String,
+    /// What replaces the word, including the trigger character.
+    pub replacement: String,
+}
+
+impl Query {
+    /// Finds the word ending at `cursor`, if it starts with a trigger character.
+    pub fn at(text: &str, cursor: usize) -> Option<Self> {
+        let before = &text[..cursor];
+        let start = before
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+            .map_or(0, |(idx, c)| idx + c.len_utf8());
+        let word = &before[start..];
+
+        let mut chars = word.chars();
+        let trigger = match chars.next()? {
+            '@' => Trigger::Mention,
+            '#' => Trigger::Hashtag,
+            ':' => Trigger::Emoji,
+            _ => return None,
+        };
+        let query = chars.as_str();
+        let valid = match trigger {
+            // the second `@` starts the host.
+            Trigger::Mention => query
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_-.@".contains(c)),
+            Trigger::Hashtag => !query.contains(|c: char| "#@:,.!?\"'()[]".contains(c)),
+            // a closing colon means the emoji is already complete.
+            Trigger::Emoji => query
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_+-".contains(c)),
+        };
+        // emoji names are too common a pattern to complete from a single character.
+        let long_enough = match trigger {
+            Trigger::Emoji => query.chars().count() >= 2,
+            _ => !query.is_empty(),
+        };
+
+        (valid && long_enough).then(|| Self {
+            trigger,
+            text: query.to_owned(),
+            start,
+        })
+    }
+}
+
+impl Completion {
+    pub fn user(user: &User) -> Self {
+        Self {
+            label: format!("@{}  {}", user.acct(), user.display_name()),
+            replacement: format!("@{}", user.acct()),
+        }
+    }
+
+    pub fn hashtag(tag: &str) -> Self {
+        Self {
+            label: format!("#{tag}"),
+            replacement: format!("#{tag}"),
+        }
+    }
+
+    pub fn emoji(emoji: &Emoji) -> Self {
+        let label = match &emoji.category {
+            Some(category) => format!(":{}:  {category}", emoji.name),
+            None => format!(":{}:", emoji.name),
+        };
+        Self {
+            label,
+            replacement: format!(":{}:", emoji.name),
+        }
+    }
+}
+
+/// Picks the emoji matching `query`, those whose name starts with it first.
+pub fn match_emojis(emojis: &[Emoji], query: &str) -> Vec<Completion> {
+    let query = query.to_lowercase();
+    let matches = |emoji: &&Emoji| {
+        emoji.name.to_lowercase().contains(&query)
+            || emoji
+                .aliases
+                .iter()
+                .any(|alias| alias.to_lowercase().contains(&query))
+    };
+
+    let mut found: Vec<&Emoji> = emojis.iter().filter(matches).collect();
+    found.sort_by_key(|emoji| {
+        (
+            !emoji.name.to_lowercase().starts_with(&query),
+            emoji.name.len(),
+        )
+    });
+    found
+        .into_iter()
+        .take(MAX_COMPLETIONS)
+        .map(Completion::emoji)
+        .collect()
+}
@@ -0,0 +1,85 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Starts the front-matter line holding the content warning.
+const CW_PREFIX: &str = "CW:";
+
+/// Opens `$VISUAL` or `$EDITOR` on a draft, and returns the edited content warning and text.
+///
+/// Blocks until the editor exits, so the TUI must be suspended first.
+pub fn edit(cw: &str, text: &str) -> Result<(String, String), String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".into());
+    // editors are often set with arguments, e.g. `code --wait`.
+    let mut words = editor.split_whitespace();
+    let program = words.next().ok_or("$EDITOR is empty.")?;
+
+    let path =
+        create_draft(&seed(cw, text)).map_err(|err| format!("Can't write the draft: {err}"))?;
+
+    let status = Command::new(program).args(words).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    fs::remove_file(&path).ok();
+
+    match status {
+        Ok(status) if status.success() => (),
+        Ok(status) => return Err(format!("{program} exited with {status}, draft unchanged.")),
+        Err(err) => return Err(format!("Can't run {program}: {err}")),
+    }
+    let edited = edited.map_err(|err| format!("Can't read the draft back: {err}"))?;
+    Ok(parse(&edited))
+}
+
+/// Writes a draft to a new file in the temp dir that only we can read, and returns its path.
+///
+/// A file already at the path is never written to, as anyone can create files there.
+fn create_draft(contents: &str) -> io::Result<PathBuf> {
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos());
+        let path = env::temp_dir().join(format!("keytan-{}-{nanos}.md", std::process::id()));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(mut file) => {
+                if let Err(err) = file.write_all(contents.as_bytes()) {
+                    fs::remove_file(&path).ok();
+                    return Err(err);
+                }
+                return Ok(path);
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Lays out a draft for editing, with the content warning on the first line.
+fn seed(cw: &str, text: &str) -> String {
+    format!("{CW_PREFIX} {cw}\n\n{text}")
+}
+
+/// Splits an edited draft into its content warning and text.
+///
+/// Without a front-matter line, everything is text.
+fn parse(edited: &str) -> (String, String) {
+    let Some(rest) = edited.strip_prefix(CW_PREFIX) else {
+        return (String::new(), edited.trim_end().to_owned());
+    };
+    let (cw, text) = rest.split_once('\n').unwrap_or((rest, ""));
+    // the blank line after the front-matter isn't part of the text.
+    let text = text.strip_prefix('\n').unwrap_or(text);
+
+    (cw.trim().to_owned(), text.trim_end().to_owned())
+}
@@ -0,0 +1,1387 @@
+mod attachments;
+mod completion;
+mod editor;
+mod poll;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::Style,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    api::{
+        drive::DriveFile,
+        meta::Meta,
+        models::{Note, NoteChannel, User, Visibility},
+        notes::{NewNote, NewPoll, MAX_POLL_CHOICES, MAX_POLL_CHOICE_LENGTH},
+        timelines::Timeline,
+        Error,
+    },
+    config::{
+        drafts::{Draft, DraftFile, DraftTarget},
+        keymap::{ComposeAction, Keymap},
+    },
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        theme::Theme,
+        widgets::{text_area::TextArea, text_input::TextInput},
+    },
+};
+
+use attachments::{complete_path, expand_home, Attachment, AttachmentFile};
+use completion::{match_emojis, Completion, Query, Trigger, MAX_COMPLETIONS};
+use poll::Expiry;
+
+use super::{
+    drive::{format_size, Drive},
+    Direction, Navigation, Screen, Suspend,
+};
+
+/// How many files a note can have.
+const MAX_FILES: usize = 16;
+
+/// Prompts shown under the attachments.
+const FILE_PROMPTS: [PromptKind; 2] = [PromptKind::Path, PromptKind::Alt];
+/// Prompts shown under the poll.
+const POLL_PROMPTS: [PromptKind; 3] = [
+    PromptKind::NewChoice,
+    PromptKind::EditChoice,
+    PromptKind::Expiry,
+];
+
+/// A form to write and post a note.
+pub struct Compose {
+    session: Session,
+    tasks: Tasks<Message>,
+    text: TextArea,
+    /// The text filled in when opening the form, which alone isn't worth keeping as a draft.
+    prefill: String,
+    /// The content warning, posted only if not empty.
+    cw: TextInput,
+    visibility: Visibility,
+    /// Who can see the note if it's `specified`, as ids and as handles to show.
+    visible_users: Vec<(String, String)>,
+    /// The note being replied to, if any.
+    reply: Option<Note>,
+    /// The note being quoted, if any.
+    quote: Option<Note>,
+    /// The channel to post in, if any.
+    channel: Option<NoteChannel>,
+    attachments: Vec<Attachment>,
+    selected_attachment: usize,
+    /// The poll to attach, if any.
+    poll: Option<NewPoll>,
+    selected_choice: usize,
+    /// The path, alt text, poll choice or poll end being typed, if any.
+    prompt: Option<Prompt>,
+    /// The files matching the path being typed, when completing it was ambiguous.
+    completions: Vec<String>,
+    /// The mention, hashtag or emoji being typed in the text, if any.
+    suggestion_query: Option<Query>,
+    /// Ways to finish `suggestion_query`, shown in a popup.
+    suggestions: Vec<Completion>,
+    selected_suggestion: usize,
+    /// How many uploads were started, to tell them apart.
+    upload_count: usize,
+    /// The largest file the instance accepts, in bytes, once known.
+    max_upload_size: Option<u64>,
+    selected: Field,
+    /// The keys bound to the form's actions. In the text fields, other keys edit the text.
+    keys: Keymap<ComposeAction>,
+    /// Whether the note is being sent.
+    posting: bool,
+    /// Whether to post as soon as every upload finished.
+    waiting_uploads: bool,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+    suspend: Option<Suspend>,
+}
+
+pub enum Message {
+    /// Selects the next field.
+    NextField,
+    /// Selects the previous field.
+    PrevField,
+    /// Edits the selected text field.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the prompt or the selected text field.
+    Paste(String),
+    /// Cycles through visibilities.
+    CycleVisibility,
+    /// Opens the Drive to pick a file to attach.
+    PickFile,
+    /// Attaches a file picked from the Drive.
+    Attach(DriveFile),
+    /// Selects the next or previous attachment.
+    SelectAttachment(Direction),
+    /// Removes the selected attachment.
+    Detach,
+    /// Marks the selected attachment as sensitive, or unmarks it.
+    ToggleSensitive,
+    /// Adds a poll, or removes it.
+    TogglePoll,
+    /// Selects the next or previous poll choice.
+    SelectChoice(Direction),
+    /// Removes the selected poll choice.
+    RemoveChoice,
+    /// Allows picking several poll choices, or only one.
+    ToggleMultiple,
+    /// Starts typing what the prompt is for.
+    StartPrompt(PromptKind),
+    /// Edits the prompt, or closes it on Backspace if empty.
+    EditPrompt(KeyEvent),
+    /// Closes the prompt without using what was typed.
+    ClosePrompt,
+    /// Completes the path being typed.
+    CompletePath,
+    /// Uploads the typed path, or sets what else was typed.
+    SubmitPrompt,
+    /// Receives how many bytes of an upload were sent.
+    UploadProgress {
+        id: usize,
+        sent: u64,
+    },
+    Uploaded {
+        id: usize,
+        result: Result<DriveFile, Error>,
+    },
+    LoadedMeta(Result<Meta, Error>),
+    /// Edits the content warning and text in an external editor.
+    OpenEditor,
+    /// Receives the content warning and text from the external editor.
+    Edited(Result<(String, String), String>),
+    /// Receives the completions of a mention, hashtag or emoji.
+    Suggested {
+        query: Query,
+        result: Result<Vec<Completion>, Error>,
+    },
+    /// Selects the next or previous completion.
+    SelectSuggestion(Direction),
+    /// Replaces the word being typed with the selected completion.
+    AcceptSuggestion,
+    /// Hides the completions until another word is typed.
+    DismissSuggestions,
+    Post,
+    Posted(Result<Arc<Note>, Error>),
+    Cancel,
+}
+
+/// What the typed text is for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    /// The path of a local file to upload.
+    Path,
+    /// The selected attachment's alt text.
+    Alt,
+    /// A poll choice to add.
+    NewChoice,
+    /// The selected poll choice.
+    EditChoice,
+    /// When the poll ends.
+    Expiry,
+}
+
+pub struct Prompt {
+    kind: PromptKind,
+    input: TextInput,
+}
+
+impl Compose {
+    /// Creates a form for a new public note, restoring its draft if any.
+    pub fn new(session: Session) -> Self {
+        let mut compose = Self::empty(session);
+        compose.restore_draft();
+        compose
+    }
+
+    fn empty(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.compose);
+        let compose = Self {
+            session,
+            tasks: Tasks::default(),
+            text: TextArea::default(),
+            prefill: String::new(),
+            cw: TextInput::default(),
+            visibility: Visibility::default(),
+            visible_users: Vec::new(),
+            reply: None,
+            quote: None,
+            channel: None,
+            attachments: Vec::new(),
+            selected_attachment: 0,
+            poll: None,
+            selected_choice: 0,
+            prompt: None,
+            completions: Vec::new(),
+            suggestion_query: None,
+            suggestions: Vec::new(),
+            selected_suggestion: 0,
+            upload_count: 0,
+            max_upload_size: None,
+            selected: Field::default(),
+            keys,
+            posting: false,
+            waiting_uploads: false,
+            status: None,
+            navigation: None,
+            suspend: None,
+        };
+
+        let client = compose.session.client.clone();
+        compose
+            .tasks
+            .spawn(move || Message::LoadedMeta(client.meta()));
+        compose
+    }
+
+    /// Creates a form replying to `note` with the same visibility, mentioning its author.
+    /// Restores the draft of the reply instead if any.
+    ///
+    /// A reply to a direct note stays direct, visible to its author and everyone it
+    /// was visible to or mentioned. `users` are the ones already looked up, to show
+    /// their handles; the others are looked up in the store, or shown by id.
+    pub fn reply(session: Session, note: &Note, users: &[User], me: Option<&User>) -> Self {
+        let mut compose = Self::empty(session);
+        if me.map_or(true, |me| me.id != note.user.id) {
+            compose.prefill = format!("@{} ", note.user.acct());
+            compose.text = TextArea::new(compose.prefill.clone());
+        }
+        compose.visibility = note.visibility;
+        if note.visibility == Visibility::Specified {
+            compose.visible_users = visible_users(&compose.session, note, users, me);
+        }
+        compose.channel = note.channel.clone();
+        compose.reply = Some(note.clone());
+        compose.restore_draft();
+        compose
+    }
+
+    /// Creates a form for a note quoting `note`, restoring its draft if any.
+    pub fn quote(session: Session, note: &Note) -> Self {
+        let mut compose = Self::empty(session);
+        compose.quote = Some(note.clone());
+        compose.restore_draft();
+        compose
+    }
+
+    /// Creates a form for a note in a channel, restoring its draft if any.
+    pub fn in_channel(session: Session, channel: NoteChannel) -> Self {
+        let mut compose = Self::empty(session);
+        compose.channel = Some(channel);
+        compose.restore_draft();
+        compose
+    }
+
+    /// Creates a form to continue writing `draft`.
+    pub fn resume(session: Session, draft: &Draft) -> Self {
+        let mut compose = Self::empty(session);
+        if let Some(note) = &draft.reply {
+            compose.prefill = format!("@{} ", note.user.acct());
+        }
+        compose.reply = draft.reply.clone();
+        compose.quote = draft.quote.clone();
+        compose.channel = draft.channel.clone();
+        // a draft saved before the user was known moves to it.
+        let target = compose.draft_target();
+        if draft.target != target {
+            let moved = Draft {
+                target,
+                ..draft.clone()
+            };
+            compose.session.update_drafts(|drafts| {
+                drafts.remove(&draft.target);
+                drafts.put(moved);
+            });
+        }
+        compose.restore_draft();
+        compose
+    }
+
+    /// Creates a form for a note posted from a timeline, in its channel if it has one.
+    pub fn for_timeline(session: Session, timeline: &Timeline) -> Self {
+        match timeline {
+            Timeline::Channel { id, name } => Self::in_channel(
+                session,
+                NoteChannel {
+                    id: id.clone(),
+                    name: name.clone(),
+                },
+            ),
+            _ => Self::new(session),
+        }
+    }
+
+    fn message(&mut self, message: Message) {
+        let edits = matches!(
+            message,
+            Message::Edit(_)
+                | Message::Paste(_)
+                | Message::CycleVisibility
+                | Message::Attach(_)
+                | Message::Detach
+                | Message::ToggleSensitive
+                | Message::TogglePoll
+                | Message::RemoveChoice
+                | Message::ToggleMultiple
+                | Message::SubmitPrompt
+                | Message::Uploaded { .. }
+                | Message::Edited(_)
+                | Message::AcceptSuggestion
+        );
+
+        match message {
+            Message::NextField => self.selected = self.selected.next(),
+            Message::PrevField => self.selected = self.selected.prev(),
+            Message::Edit(key) => {
+                match self.selected {
+                    Field::Text => self.text.handle_key(key),
+                    Field::Cw => self.cw.handle_key(key),
+                    _ => false,
+                };
+            }
+            Message::Paste(text) => match (&mut self.prompt, self.selected) {
+                (Some(prompt), _) => prompt.input.insert(&text),
+                (None, Field::Text) => self.text.insert(&text),
+                (None, Field::Cw) => self.cw.insert(&text),
+                _ => (),
+            },
+            Message::CycleVisibility => {
+                // a reply to a direct note stays direct.
+                let direct_reply = self
+                    .reply
+                    .as_ref()
+                    .is_some_and(|note| note.visibility == Visibility::Specified);
+                if !direct_reply {
+                    self.visibility = self.visibility.next();
+                }
+            }
+            Message::PickFile => {
+                let sender = self.tasks.sender();
+                let drive = Drive::picker(self.session.clone(), move |file| {
+                    sender.send(Message::Attach(file)).ok();
+                });
+                self.navigation = Some(Navigation::Push(Box::new(drive)));
+            }
+            Message::Attach(file) => {
+                let attached = self
+                    .attachments
+                    .iter()
+                    .any(|attachment| attachment.drive_file().is_some_and(|f| f.id == file.id));
+                if !attached && self.can_attach() {
+                    self.attachments.push(Attachment::from_drive(file));
+                    self.selected_attachment = self.attachments.len() - 1;
+                }
+            }
+            Message::SelectAttachment(direction) => {
+                let last = self.attachments.len().saturating_sub(1);
+                self.selected_attachment = match direction {
+                    Direction::Next => (self.selected_attachment + 1).min(last),
+                    Direction::Previous => self.selected_attachment.saturating_sub(1),
+                };
+            }
+            Message::Detach => {
+                if self.selected_attachment < self.attachments.len() {
+                    self.attachments.remove(self.selected_attachment);
+                    self.selected_attachment = self
+                        .selected_attachment
+                        .min(self.attachments.len().saturating_sub(1));
+                }
+            }
+            Message::ToggleSensitive => {
+                if let Some(attachment) = self.attachments.get_mut(self.selected_attachment) {
+                    attachment.sensitive = !attachment.sensitive;
+                }
+            }
+            Message::TogglePoll => {
+                if self.poll.take().is_none() {
+                    self.poll = Some(NewPoll::default());
+                    self.selected_choice = 0;
+                    self.message(Message::StartPrompt(PromptKind::NewChoice));
+                }
+            }
+            Message::SelectChoice(direction) => {
+                let last = self
+                    .poll
+                    .as_ref()
+                    .map_or(0, |poll| poll.choices.len().saturating_sub(1));
+                self.selected_choice = match direction {
+                    Direction::Next => (self.selected_choice + 1).min(last),
+                    Direction::Previous => self.selected_choice.saturating_sub(1),
+                };
+            }
+            Message::RemoveChoice => {
+                if let Some(poll) = &mut self.poll {
+                    if self.selected_choice < poll.choices.len() {
+                        poll.choices.remove(self.selected_choice);
+                        self.selected_choice = self
+                            .selected_choice
+                            .min(poll.choices.len().saturating_sub(1));
+                    }
+                }
+            }
+            Message::ToggleMultiple => {
+                if let Some(poll) = &mut self.poll {
+                    poll.multiple = !poll.multiple;
+                }
+            }
+            Message::StartPrompt(kind) => {
+                let input = match (kind, &self.poll) {
+                    (PromptKind::Path, _) => String::new(),
+                    (PromptKind::Alt, _) => match self.attachments.get(self.selected_attachment) {
+                        Some(attachment) => attachment.alt.clone(),
+                        None => return,
+                    },
+                    (PromptKind::NewChoice, Some(poll)) => {
+                        if poll.choices.len() >= MAX_POLL_CHOICES {
+                            self.status = Some(format!(
+                                "A poll can't have more than {MAX_POLL_CHOICES} choices."
+                            ));
+                            return;
+                        }
+                        String::new()
+                    }
+                    (PromptKind::EditChoice, Some(poll)) => {
+                        match poll.choices.get(self.selected_choice) {
+                            Some(choice) => choice.clone(),
+                            None => return,
+                        }
+                    }
+                    (PromptKind::Expiry, Some(_)) => String::new(),
+                    (_, None) => return,
+                };
+                self.prompt = Some(Prompt {
+                    kind,
+                    input: TextInput::new(input),
+                });
+            }
+            Message::ClosePrompt => {
+                self.prompt = None;
+                self.completions.clear();
+            }
+            Message::EditPrompt(key) => {
+                if let Some(prompt) = &mut self.prompt {
+                    if key.code == KeyCode::Backspace && prompt.input.is_empty() {
+                        self.prompt = None;
+                    } else {
+                        prompt.input.handle_key(key);
+                    }
+                }
+                self.completions.clear();
+            }
+            Message::CompletePath => {
+                if let Some(prompt) = &mut self.prompt {
+                    if prompt.kind == PromptKind::Path {
+                        let (input, completions) = complete_path(prompt.input.text());
+                        prompt.input.set_text(input);
+                        self.completions = completions;
+                    }
+                }
+            }
+            Message::SubmitPrompt => {
+                self.completions.clear();
+                let Some(prompt) = self.prompt.take() else {
+                    return;
+                };
+                match prompt.kind {
+                    PromptKind::Path => self.upload(prompt.input.text()),
+                    PromptKind::Alt => {
+                        if let Some(attachment) = self.attachments.get_mut(self.selected_attachment)
+                        {
+                            attachment.alt = prompt.input.text().trim().to_owned();
+                        }
+                    }
+                    PromptKind::NewChoice | PromptKind::EditChoice => {
+                        self.submit_choice(prompt.kind, prompt.input.text().trim())
+                    }
+                    PromptKind::Expiry => match Expiry::parse(prompt.input.text()) {
+                        Ok(expiry) => {
+                            if let Some(poll) = &mut self.poll {
+                                expiry.apply(poll);
+                            }
+                        }
+                        Err(err) => self.status = Some(err),
+                    },
+                }
+            }
+            Message::UploadProgress { id, sent } => {
+                if let Some(AttachmentFile::Uploading { sent: progress, .. }) = self
+                    .uploading_mut(id)
+                    .map(|attachment| &mut attachment.file)
+                {
+                    *progress = sent;
+                }
+            }
+            Message::Uploaded { id, result } => {
+                let Some(idx) = self.uploading(id) else {
+                    return;
+                };
+                match result {
+                    Ok(file) => self.attachments[idx].file = AttachmentFile::Ready(file),
+                    Err(err) => {
+                        let attachment = self.attachments.remove(idx);
+                        self.selected_attachment = self
+                            .selected_attachment
+                            .min(self.attachments.len().saturating_sub(1));
+                        self.status = Some(format!("Couldn't upload {}: {err}", attachment.name));
+                        self.waiting_uploads = false;
+                        return;
+                    }
+                }
+                if self.waiting_uploads && self.pending_uploads() == 0 {
+                    self.waiting_uploads = false;
+                    self.message(Message::Post);
+                }
+            }
+            Message::LoadedMeta(result) => {
+                // the instance still checks sizes, so this is only a convenience.
+                if let Ok(meta) = result {
+                    self.max_upload_size = meta.max_upload_size();
+                }
+            }
+            Message::OpenEditor => {
+                let sender = self.tasks.sender();
+                let (cw, text) = (self.cw.text().to_owned(), self.text.text().to_owned());
+                self.suspend = Some(Box::new(move || {
+                    sender.send(Message::Edited(editor::edit(&cw, &text))).ok();
+                }));
+            }
+            Message::Edited(result) => match result {
+                Ok((cw, text)) => {
+                    self.cw.set_text(cw);
+                    self.text.set_text(text);
+                    self.status = None;
+                }
+                Err(err) => self.status = Some(err),
+            },
+            Message::Suggested { query, result } => {
+                // completions are only a convenience, and may be for a word typed since.
+                if self.suggestion_query.as_ref() == Some(&query) {
+                    self.suggestions = result.unwrap_or_default();
+                    self.selected_suggestion = 0;
+                }
+            }
+            Message::DismissSuggestions => self.suggestions.clear(),
+            Message::SelectSuggestion(direction) => {
+                let last = self.suggestions.len().saturating_sub(1);
+                self.selected_suggestion = match direction {
+                    Direction::Next => (self.selected_suggestion + 1).min(last),
+                    Direction::Previous => self.selected_suggestion.saturating_sub(1),
+                };
+            }
+            Message::AcceptSuggestion => {
+                if let (Some(query), Some(suggestion)) = (
+                    &self.suggestion_query,
+                    self.suggestions.get(self.selected_suggestion),
+                ) {
+                    self.text.replace_before_cursor(
+                        query.start,
+                        &format!("{} ", suggestion.replacement),
+                    );
+                }
+            }
+            Message::Post => {
+                if self.posting {
+                    return;
+                }
+                if self.text.text().trim().is_empty()
+                    && self.attachments.is_empty()
+                    && self.poll.is_none()
+                {
+                    self.status = Some("Can't post an empty note.".into());
+                    return;
+                }
+                if let Some(Err(err)) = self.poll.as_ref().map(poll::validate) {
+                    self.status = Some(err);
+                    return;
+                }
+                let pending = self.pending_uploads();
+                if pending > 0 {
+                    self.waiting_uploads = true;
+                    self.status = Some(format!(
+                        "Waiting for {pending} upload(s) to finish before posting..."
+                    ));
+                    return;
+                }
+
+                let note = self.new_note();
+                // alt texts and sensitive flags are saved on the files themselves.
+                let changes: Vec<(String, String, bool)> = self
+                    .attachments
+                    .iter()
+                    .filter(|attachment| attachment.is_changed())
+                    .filter_map(|attachment| {
+                        let file = attachment.drive_file()?;
+                        Some((
+                            file.id.clone(),
+                            attachment.alt.clone(),
+                            attachment.sensitive,
+                        ))
+                    })
+                    .collect();
+                let client = self.session.client.clone();
+                self.posting = true;
+                self.status = Some("Posting...".into());
+                self.tasks.spawn(move || {
+                    for (file_id, alt, sensitive) in changes {
+                        let alt = (!alt.is_empty()).then_some(alt.as_str());
+                        if let Err(err) = client.describe_file(&file_id, alt, sensitive) {
+                            return Message::Posted(Err(err));
+                        }
+                    }
+                    Message::Posted(client.create_note(&note))
+                });
+            }
+            Message::Posted(result) => {
+                self.posting = false;
+                match result {
+                    Ok(_) => {
+                        let target = self.draft_target();
+                        self.session.update_drafts(|drafts| drafts.remove(&target));
+                        self.navigation = Some(Navigation::Back);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            // the draft stays, to be discarded from the drafts screen.
+            Message::Cancel => self.navigation = Some(Navigation::Back),
+        }
+
+        if edits {
+            self.save_draft();
+        }
+        self.update_suggestions();
+    }
+
+    /// Runs the message an action stands for on the selected field, if it has one.
+    fn action(&mut self, action: ComposeAction) {
+        self.message(match (action, self.selected) {
+            (ComposeAction::NextField, _) => Message::NextField,
+            (ComposeAction::PrevField, _) => Message::PrevField,
+            (ComposeAction::OpenEditor, _) => Message::OpenEditor,
+            (ComposeAction::Confirm | ComposeAction::CycleVisibility, Field::Visibility) => {
+                Message::CycleVisibility
+            }
+            (ComposeAction::Confirm, Field::Files) => Message::PickFile,
+            (ComposeAction::AttachPath, Field::Files) => Message::StartPrompt(PromptKind::Path),
+            (ComposeAction::EditAlt, Field::Files) => Message::StartPrompt(PromptKind::Alt),
+            (ComposeAction::Next, Field::Files) => Message::SelectAttachment(Direction::Next),
+            (ComposeAction::Prev, Field::Files) => Message::SelectAttachment(Direction::Previous),
+            (ComposeAction::ToggleSensitive, Field::Files) => Message::ToggleSensitive,
+            (ComposeAction::Remove, Field::Files) => Message::Detach,
+            (ComposeAction::Confirm, Field::Poll) if self.poll.is_none() => Message::TogglePoll,
+            (ComposeAction::Confirm, Field::Poll) => Message::StartPrompt(PromptKind::NewChoice),
+            (ComposeAction::EditChoice, Field::Poll) => {
+                Message::StartPrompt(PromptKind::EditChoice)
+            }
+            (ComposeAction::SetExpiry, Field::Poll) => Message::StartPrompt(PromptKind::Expiry),
+            (ComposeAction::Next, Field::Poll) => Message::SelectChoice(Direction::Next),
+            (ComposeAction::Prev, Field::Poll) => Message::SelectChoice(Direction::Previous),
+            (ComposeAction::Remove, Field::Poll) => Message::RemoveChoice,
+            (ComposeAction::ToggleMultiple, Field::Poll) => Message::ToggleMultiple,
+            (ComposeAction::TogglePoll, Field::Poll) => Message::TogglePoll,
+            (ComposeAction::Confirm, Field::Post) => Message::Post,
+            (ComposeAction::Confirm, Field::Cancel) => Message::Cancel,
+            _ => return,
+        });
+    }
+
+    /// Looks for completions when a different mention, hashtag or emoji is being typed.
+    fn update_suggestions(&mut self) {
+        let query = match self.selected {
+            Field::Text => Query::at(self.text.text(), self.text.cursor()),
+            _ => None,
+        };
+        if query == self.suggestion_query {
+            return;
+        }
+        self.suggestion_query = query.clone();
+        self.suggestions.clear();
+        self.selected_suggestion = 0;
+        let Some(query) = query else {
+            return;
+        };
+
+        let client = self.session.client.clone();
+        self.tasks.spawn(move || {
+            let result = match query.trigger {
+                Trigger::Mention => client.search_users_by_acct(&query.text).map(|users| {
+                    users
+                        .iter()
+                        .take(MAX_COMPLETIONS)
+                        .map(Completion::user)
+                        .collect()
+                }),
+                Trigger::Hashtag => client.search_hashtags(&query.text).map(|tags| {
+                    tags.iter()
+                        .take(MAX_COMPLETIONS)
+                        .map(|tag| Completion::hashtag(tag))
+                        .collect()
+                }),
+                Trigger::Emoji => client
+                    .custom_emojis()
+                    .map(|emojis| match_emojis(&emojis, &query.text)),
+            };
+            Message::Suggested { query, result }
+        });
+    }
+
+    /// Draws the completions over the bottom of the text.
+    fn render_suggestions(&self, frame: &mut Frame, text_area: Rect) {
+        let lines: Vec<Line> = self
+            .suggestions
+            .iter()
+            .enumerate()
+            .map(|(idx, suggestion)| {
+                let style = if idx == self.selected_suggestion {
+                    Theme::current().selection
+                } else {
+                    Style::new()
+                };
+                Line::styled(suggestion.label.clone(), style)
+            })
+            .collect();
+        let height = (lines.len() as u16 + 2).min(text_area.height);
+        let width = lines
+            .iter()
+            .map(|line| line.width() as u16 + 2)
+            .max()
+            .unwrap_or_default()
+            .min(text_area.width);
+        let area = Rect {
+            y: text_area.bottom().saturating_sub(height),
+            height,
+            width,
+            ..text_area
+        };
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(Theme::current().background(), area);
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Plain),
+            ),
+            area,
+        );
+    }
+
+    fn draft_target(&self) -> DraftTarget {
+        DraftTarget {
+            host: self.session.client.instance.clone(),
+            user_id: self.session.user_id(),
+            reply_id: self.reply.as_ref().map(|note| note.id.clone()),
+            quote_id: self.quote.as_ref().map(|note| note.id.clone()),
+            channel_id: self.channel.as_ref().map(|channel| channel.id.clone()),
+        }
+    }
+
+    /// Keeps what's written so far, or discards the draft if nothing is.
+    /// The drafts file is written once typing pauses, or when leaving the form.
+    fn save_draft(&self) {
+        let files: Vec<DraftFile> = self
+            .attachments
+            .iter()
+            .filter_map(|attachment| {
+                Some(DraftFile {
+                    file: attachment.drive_file()?.clone(),
+                    alt: attachment.alt.clone(),
+                    sensitive: attachment.sensitive,
+                })
+            })
+            .collect();
+        let target = self.draft_target();
+        if self.text.text().trim() == self.prefill.trim()
+            && self.cw.is_empty()
+            && files.is_empty()
+            && self.poll.is_none()
+        {
+            self.session.edit_drafts(|drafts| drafts.remove(&target));
+            return;
+        }
+
+        let draft = Draft {
+            target,
+            reply: self.reply.clone(),
+            quote: self.quote.clone(),
+            channel: self.channel.clone(),
+            cw: self.cw.text().to_owned(),
+            text: self.text.text().to_owned(),
+            visibility: self.visibility,
+            visible_users: self.visible_users.clone(),
+            files,
+            poll: self.poll.clone(),
+            saved_at: Utc::now().timestamp(),
+        };
+        self.session.edit_drafts(|drafts| drafts.put(draft));
+    }
+
+    /// Fills the form with the draft saved for its target, if any.
+    fn restore_draft(&mut self) {
+        let Some(draft) = self
+            .session
+            .drafts
+            .read()
+            .get(&self.draft_target())
+            .cloned()
+        else {
+            return;
+        };
+
+        self.status = Some(format!(
+            "Restored the draft from {}.",
+            draft.saved_at_text()
+        ));
+        self.cw.set_text(draft.cw);
+        self.text.set_text(draft.text);
+        self.visibility = draft.visibility;
+        self.visible_users = draft.visible_users;
+        self.poll = draft.poll;
+        self.attachments = draft
+            .files
+            .into_iter()
+            .map(|file| Attachment {
+                alt: file.alt,
+                sensitive: file.sensitive,
+                ..Attachment::from_drive(file.file)
+            })
+            .collect();
+    }
+
+    /// Adds the typed poll choice, or replaces the selected one with it.
+    fn submit_choice(&mut self, kind: PromptKind, choice: &str) {
+        let Some(poll) = &mut self.poll else {
+            return;
+        };
+        if choice.is_empty() {
+            self.status = Some("A poll choice can't be empty.".into());
+            return;
+        }
+        if choice.chars().count() > MAX_POLL_CHOICE_LENGTH {
+            self.status = Some(format!(
+                "A poll choice can't be longer than {MAX_POLL_CHOICE_LENGTH} characters."
+            ));
+            return;
+        }
+
+        match poll.choices.get_mut(self.selected_choice) {
+            Some(selected) if kind == PromptKind::EditChoice => *selected = choice.to_owned(),
+            _ => {
+                poll.choices.push(choice.to_owned());
+                self.selected_choice = poll.choices.len() - 1;
+            }
+        }
+        self.status = None;
+    }
+
+    /// Checks the note can have one more file, explaining why not otherwise.
+    fn can_attach(&mut self) -> bool {
+        if self.attachments.len() >= MAX_FILES {
+            self.status = Some(format!("Can't attach more than {MAX_FILES} files."));
+            return false;
+        }
+        true
+    }
+
+    /// Starts uploading a local file to attach it.
+    fn upload(&mut self, input: &str) {
+        let path = expand_home(input.trim());
+        let size = match path.metadata() {
+            Ok(metadata) if metadata.is_file() => metadata.len(),
+            Ok(_) => {
+                self.status = Some(format!("{} is not a file.", path.display()));
+                return;
+            }
+            Err(err) => {
+                self.status = Some(format!("Can't read {}: {err}", path.display()));
+                return;
+            }
+        };
+        if let Some(max) = self.max_upload_size.filter(|max| size > *max) {
+            self.status = Some(format!(
+                "{} is {}, over the instance's limit of {}.",
+                path.display(),
+                format_size(size),
+                format_size(max)
+            ));
+            return;
+        }
+        if !self.can_attach() {
+            return;
+        }
+
+        let id = self.upload_count;
+        self.upload_count += 1;
+        self.attachments.push(Attachment {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            file: AttachmentFile::Uploading { id, sent: 0, size },
+            alt: String::new(),
+            sensitive: false,
+        });
+        self.selected_attachment = self.attachments.len() - 1;
+        self.status = None;
+
+        let client = self.session.client.clone();
+        self.tasks.spawn_with(move |sender| {
+            let result = client.upload_file(&path, None, |sent| {
+                sender.send(Message::UploadProgress { id, sent }).ok();
+            });
+            Message::Uploaded { id, result }
+        });
+    }
+
+    /// Finds the attachment being uploaded as `id`.
+    fn uploading(&self, id: usize) -> Option<usize> {
+        self.attachments.iter().position(|attachment| {
+            matches!(attachment.file, AttachmentFile::Uploading { id: upload, .. } if upload == id)
+        })
+    }
+
+    fn uploading_mut(&mut self, id: usize) -> Option<&mut Attachment> {
+        let idx = self.uploading(id)?;
+        self.attachments.get_mut(idx)
+    }
+
+    fn pending_uploads(&self) -> usize {
+        self.attachments
+            .iter()
+            .filter(|attachment| attachment.drive_file().is_none())
+            .count()
+    }
+
+    fn new_note(&self) -> NewNote {
+        NewNote {
+            text: (!self.text.text().trim().is_empty()).then(|| self.text.text().to_owned()),
+            cw: (!self.cw.is_empty()).then(|| self.cw.text().to_owned()),
+            visibility: self.visibility,
+            visible_user_ids: if self.visibility == Visibility::Specified {
+                self.visible_users
+                    .iter()
+                    .map(|(id, _)| id.clone())
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            reply_id: self.reply.as_ref().map(|note| note.id.clone()),
+            renote_id: self.quote.as_ref().map(|note| note.id.clone()),
+            channel_id: self.channel.as_ref().map(|channel| channel.id.clone()),
+            file_ids: self
+                .attachments
+                .iter()
+                .filter_map(|attachment| attachment.drive_file())
+                .map(|file| file.id.clone())
+                .collect(),
+            poll: self.poll.clone(),
+        }
+    }
+
+    fn label(&self, field: Field, text: &str) -> Line<'static> {
+        Line::from(if self.selected == field {
+            format!("> {text}")
+        } else {
+            text.to_owned()
+        })
+    }
+
+    /// Lists the attachments.
+    fn attachment_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line> = self
+            .attachments
+            .iter()
+            .enumerate()
+            .map(|(idx, attachment)| {
+                let marker = if self.selected == Field::Files && idx == self.selected_attachment {
+                    "* "
+                } else {
+                    "  "
+                };
+                Line::from(format!("{marker}{}", attachment.label()))
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from("  None"));
+        }
+        lines
+    }
+
+    /// Lists the poll's choices and settings.
+    fn poll_lines(&self) -> Vec<Line<'static>> {
+        let Some(poll) = &self.poll else {
+            return vec![Line::from("  None")];
+        };
+
+        let mut lines: Vec<Line> = poll
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(idx, choice)| {
+                let marker = if self.selected == Field::Poll && idx == self.selected_choice {
+                    "* "
+                } else {
+                    "  "
+                };
+                Line::from(format!("{marker}{}. {choice}", idx + 1))
+            })
+            .collect();
+        let multiple = if poll.multiple { "yes" } else { "no" };
+        lines.push(Line::from(format!("  Multiple choices: {multiple}")));
+        lines.push(Line::from(format!("  Ends: {}", poll::expiry_text(poll))));
+        lines
+    }
+
+    /// Draws a field's label and lines, then the prompt if it's one of `kinds`, then `after`.
+    fn render_section(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        lines: Vec<Line<'static>>,
+        kinds: &[PromptKind],
+        after: Vec<Line<'static>>,
+    ) {
+        let prompt = self
+            .prompt
+            .as_mut()
+            .filter(|prompt| kinds.contains(&prompt.kind));
+        let [lines_area, prompt_area, after_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Length(prompt.is_some().into()),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        frame.render_widget(Text::from(lines), lines_area);
+        frame.render_widget(Text::from(after), after_area);
+
+        if let Some(prompt) = prompt {
+            let label = match prompt.kind {
+                PromptKind::Path => "Path: ",
+                PromptKind::Alt => "Alt text: ",
+                PromptKind::NewChoice => "New choice: ",
+                PromptKind::EditChoice => "Choice: ",
+                PromptKind::Expiry => "Ends: ",
+            };
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(label.len() as u16), Constraint::Fill(1)])
+                    .areas(prompt_area);
+            frame.render_widget(Line::from(label), label_area);
+            prompt.input.render(frame, input_area, true);
+        }
+    }
+
+    /// How many rows a field takes, with its label and the prompt if it's one of `kinds`.
+    fn section_height(&self, lines: usize, kinds: &[PromptKind]) -> u16 {
+        let prompt = self
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| kinds.contains(&prompt.kind));
+        (1 + lines + usize::from(prompt)) as u16
+    }
+
+    fn help_text(&self) -> String {
+        match (&self.prompt, self.selected) {
+            (Some(prompt), _) if prompt.kind == PromptKind::Path => {
+                "Upload (Enter) / Complete (Tab) / Cancel (Backspace when empty)".into()
+            }
+            (Some(prompt), _) if prompt.kind == PromptKind::Expiry => {
+                "Set (Enter), e.g. 1d12h or 2024-10-04 18:00, empty for never / \
+                 Cancel (Backspace when empty)"
+                    .into()
+            }
+            (Some(_), _) => "Save (Enter) / Cancel (Backspace when empty)".into(),
+            (None, Field::Files) => self.keys.help(&[
+                ("Pick from Drive", &[ComposeAction::Confirm]),
+                ("Upload", &[ComposeAction::AttachPath]),
+                ("Select", &[ComposeAction::Next, ComposeAction::Prev]),
+                ("Alt text", &[ComposeAction::EditAlt]),
+                ("Sensitive", &[ComposeAction::ToggleSensitive]),
+                ("Remove", &[ComposeAction::Remove]),
+                ("Next", &[ComposeAction::NextField]),
+            ]),
+            (None, Field::Poll) if self.poll.is_none() => self.keys.help(&[
+                ("Add a poll", &[ComposeAction::Confirm]),
+                ("Next", &[ComposeAction::NextField]),
+            ]),
+            (None, Field::Poll) => self.keys.help(&[
+                ("Add choice", &[ComposeAction::Confirm]),
+                ("Edit", &[ComposeAction::EditChoice]),
+                ("Remove", &[ComposeAction::Remove]),
+                ("Select", &[ComposeAction::Next, ComposeAction::Prev]),
+                ("Multiple", &[ComposeAction::ToggleMultiple]),
+                ("Ends", &[ComposeAction::SetExpiry]),
+                ("Remove poll", &[ComposeAction::TogglePoll]),
+                ("Next", &[ComposeAction::NextField]),
+            ]),
+            (None, Field::Text) if !self.suggestions.is_empty() => {
+                let editor = self.keys.help(&[("Editor", &[ComposeAction::OpenEditor])]);
+                format!("Complete (Tab/Enter) / Choose (Up/Down) / {editor}")
+            }
+            (None, _) => self.keys.help(&[
+                ("Next", &[ComposeAction::NextField]),
+                ("Back", &[ComposeAction::PrevField]),
+                ("Confirm", &[ComposeAction::Confirm]),
+                ("Editor", &[ComposeAction::OpenEditor]),
+            ]),
+        }
+    }
+
+    fn visibility_text(&self) -> String {
+        if self.visibility != Visibility::Specified {
+            return self.visibility.as_str().into();
+        }
+
+        let users: Vec<String> = self
+            .visible_users
+            .iter()
+            .map(|(_, acct)| format!("@{acct}"))
+            .collect();
+        if users.is_empty() {
+            "specified (mentioned users only)".into()
+        } else {
+            format!("specified (to {})", users.join(", "))
+        }
+    }
+}
+
+impl Screen for Compose {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+        self.session.save_settled_drafts();
+
+        let area = frame.area();
+        let attachment_lines = self.attachment_lines();
+        let completion_lines: Vec<Line> = if self.completions.is_empty() {
+            Vec::new()
+        } else {
+            vec![Line::from(self.completions.join("  "))]
+        };
+        let poll_lines = self.poll_lines();
+        let files_height = self.section_height(
+            attachment_lines.len() + completion_lines.len(),
+            &FILE_PROMPTS,
+        );
+        let poll_height = self.section_height(poll_lines.len(), &POLL_PROMPTS);
+        let mut title = match (&self.reply, &self.quote) {
+            (Some(note), _) => format!("Reply to @{}", note.user.acct()),
+            (None, Some(note)) => format!("Quote @{}", note.user.acct()),
+            (None, None) => "New note".into(),
+        };
+        if let Some(channel) = &self.channel {
+            title.push_str(&format!(" in {}", channel.name));
+        }
+        let screen_frame = Block::new()
+            .borders(Borders::TOP | Borders::BOTTOM)
+            .border_type(BorderType::Plain)
+            .title_top(title)
+            .title_bottom(match &self.status {
+                Some(status) => status.clone(),
+                None => self.help_text(),
+            });
+        frame.render_widget(screen_frame, area);
+
+        let context = self.reply.as_ref().or(self.quote.as_ref());
+        let [context_area, cw_area, text_area, files_area, poll_area, visibility_area, buttons_area] =
+            Layout::vertical([
+                Constraint::Length(if context.is_some() { 2 } else { 0 }),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+                Constraint::Length(files_height),
+                Constraint::Length(poll_height),
+                Constraint::Length(2),
+                Constraint::Length(1),
+            ])
+            .areas(area.inner(Margin {
+                horizontal: 1,
+                vertical: 1,
+            }));
+
+        if let Some(note) = context {
+            let excerpt = note.text.as_deref().and_then(|text| text.lines().next());
+            frame.render_widget(Line::from(excerpt.unwrap_or("").to_owned()), context_area);
+        }
+
+        let typing = self.prompt.is_none();
+        let [cw_label_area, cw_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(cw_area);
+        frame.render_widget(self.label(Field::Cw, "Content warning"), cw_label_area);
+        self.cw
+            .render(frame, cw_area, typing && self.selected == Field::Cw);
+
+        let [text_label_area, text_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(text_area);
+        frame.render_widget(self.label(Field::Text, "Text"), text_label_area);
+        self.text
+            .render(frame, text_area, typing && self.selected == Field::Text);
+        if !self.suggestions.is_empty() {
+            self.render_suggestions(frame, text_area);
+        }
+
+        let files_label = self.label(Field::Files, "Attachments");
+        self.render_section(
+            frame,
+            files_area,
+            [vec![files_label], attachment_lines].concat(),
+            &FILE_PROMPTS,
+            completion_lines,
+        );
+
+        let poll_label = self.label(Field::Poll, "Poll");
+        self.render_section(
+            frame,
+            poll_area,
+            [vec![poll_label], poll_lines].concat(),
+            &POLL_PROMPTS,
+            Vec::new(),
+        );
+
+        frame.render_widget(
+            Text::from(vec![
+                self.label(Field::Visibility, "Visibility"),
+                Line::from(self.visibility_text()),
+            ]),
+            visibility_area,
+        );
+
+        let [post_area, cancel_area] =
+            Layout::horizontal([Constraint::Length(12), Constraint::Length(12)])
+                .areas(buttons_area);
+        frame.render_widget(self.label(Field::Post, "[Post]"), post_area);
+        frame.render_widget(self.label(Field::Cancel, "[Cancel]"), cancel_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.prompt.is_some() {
+            match key.code {
+                KeyCode::Enter => self.message(Message::SubmitPrompt),
+                KeyCode::Esc => self.message(Message::ClosePrompt),
+                KeyCode::Tab => self.message(Message::CompletePath),
+                _ => self.message(Message::EditPrompt(key)),
+            }
+            return;
+        }
+        if self.selected == Field::Text && !self.suggestions.is_empty() {
+            match key.code {
+                KeyCode::Tab | KeyCode::Enter => return self.message(Message::AcceptSuggestion),
+                KeyCode::Down => return self.message(Message::SelectSuggestion(Direction::Next)),
+                KeyCode::Up => return self.message(Message::SelectSuggestion(Direction::Previous)),
+                KeyCode::Esc => return self.message(Message::DismissSuggestions),
+                _ => (),
+            }
+        }
+
+        match (self.keys.action(key), self.selected) {
+            (
+                Some(
+                    action @ (ComposeAction::NextField
+                    | ComposeAction::PrevField
+                    | ComposeAction::OpenEditor),
+                ),
+                _,
+            ) => self.action(action),
+            (_, Field::Text | Field::Cw) => self.message(Message::Edit(key)),
+            (Some(action), _) => self.action(action),
+            (None, _) => (),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.message(Message::Paste(text.to_owned()));
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        let navigation = self.navigation.take();
+        // the form is left or covered, so the draft can't wait for typing to pause.
+        if navigation.is_some() {
+            self.session.save_changed_drafts();
+        }
+        navigation
+    }
+
+    fn suspend(&mut self) -> Option<Suspend> {
+        self.suspend.take()
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Default)]
+enum Field {
+    Cw,
+    #[default]
+    Text,
+    Files,
+    Poll,
+    Visibility,
+    Post,
+    Cancel,
+}
+
+impl Field {
+    fn next(&self) -> Self {
+        match self {
+            Self::Cw => Self::Text,
+            Self::Text => Self::Files,
+            Self::Files => Self::Poll,
+            Self::Poll => Self::Visibility,
+            Self::Visibility => Self::Post,
+            Self::Post => Self::Cancel,
+            Self::Cancel => Self::Cw,
+        }
+    }
+    fn prev(&self) -> Self {
+        match self {
+            Self::Cw => Self::Cancel,
+            Self::Text => Self::Cw,
+            Self::Files => Self::Text,
+            Self::Poll => Self::Files,
+            Self::Visibility => Self::Poll,
+            Self::Post => Self::Visibility,
+            Self::Cancel => Self::Post,
+        }
+    }
+}
+
+/// Returns who can see a reply to `note`: its author and everyone it was visible to or
+/// mentioned, except us, as ids and as handles to show.
+fn visible_users(
+    session: &Session,
+    note: &Note,
+    users: &[User],
+    me: Option<&User>,
+) -> Vec<(String, String)> {
+    let store = session.client.store.read();
+    let acct = |id: &str| {
+        let known = users.iter().chain([&note.user]).find(|user| user.id == id);
+        match known {
+            Some(user) => user.acct(),
+            None => store
+                .user(id)
+                .map_or_else(|| id.to_owned(), |user| user.acct()),
+        }
+    };
+    let mut visible_users: Vec<(String, String)> = Vec::new();
+    for id in std::iter::once(&note.user.id)
+        .chain(&note.visible_user_ids)
+        .chain(&note.mentions)
+    {
+        let is_me = me.is_some_and(|me| &me.id == id);
+        if !is_me && !visible_users.iter().any(|(known, _)| known == id) {
+            visible_users.push((id.clone(), acct(id)));
+        }
+    }
+    visible_users
+}
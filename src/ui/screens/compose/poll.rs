@@ -0,0 +1,292 @@
+use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+
+use crate::api::notes::{NewPoll, MAX_POLL_CHOICES, MAX_POLL_CHOICE_LENGTH, MIN_POLL_CHOICES};
+
+/// Formats accepted for an absolute end date, in local time.
+const DATE_TIME_FORMATS: [&str; 3] = ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S"];
+
+/// When voting on a poll ends.
+pub enum Expiry {
+    Never,
+    /// Some time after posting.
+    After(TimeDelta),
+    At(DateTime<Utc>),
+}
+
+impl Expiry {
+    /// Parses a duration like `1d12h` or `90m`, a local date and time like `2024-10-04 18:00`,
+    /// a date alone for its midnight, or nothing for a poll that never ends.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        Self::parse_in(input, &Local)
+    }
+
+    /// Parses like [`Expiry::parse`], with dates and times in the time zone `tz`.
+    fn parse_in<Tz: TimeZone>(input: &str, tz: &Tz) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() || input == "never" {
+            return Ok(Self::Never);
+        }
+        if input.starts_with(|c: char| c.is_ascii_digit()) && !input.contains('-') {
+            return parse_duration(input).map(Self::After);
+        }
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+            return Ok(Self::At(time.with_timezone(&Utc)));
+        }
+        let naive = DATE_TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+            .ok_or_else(|| format!("Can't read \"{input}\" as a duration or a date."))?;
+        // a time repeated when DST ends is taken the first time it comes.
+        let time = match tz.from_local_datetime(&naive) {
+            LocalResult::Single(time) => time,
+            LocalResult::Ambiguous(first, second) => first.min(second),
+            LocalResult::None => {
+                return Err(format!("{input} doesn't exist in the local time zone."))
+            }
+        };
+        Ok(Self::At(time.with_timezone(&Utc)))
+    }
+
+    /// Sets when `poll` ends.
+    pub fn apply(self, poll: &mut NewPoll) {
+        (poll.expires_at, poll.expired_after) = match self {
+            Self::Never => (None, None),
+            Self::After(duration) => (None, Some(duration.num_milliseconds())),
+            Self::At(time) => (Some(time.timestamp_millis()), None),
+        };
+    }
+}
+
+/// Parses a sequence of numbers with units, e.g. `1w2d`, `12h` or `1h30m`.
+fn parse_duration(input: &str) -> Result<TimeDelta, String> {
+    let invalid = || format!("Can't read \"{input}\", use units like 1w, 2d, 3h or 30m.");
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: i64 = number.parse().map_err(|_| invalid())?;
+        let part = match c {
+            'w' => TimeDelta::try_weeks(amount),
+            'd' => TimeDelta::try_days(amount),
+            'h' => TimeDelta::try_hours(amount),
+            'm' => TimeDelta::try_minutes(amount),
+            's' => TimeDelta::try_seconds(amount),
+            _ => None,
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .ok_or_else(invalid)?;
+        number.clear();
+    }
+    // a number without a unit is ambiguous.
+    if !number.is_empty() || total <= TimeDelta::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Checks a poll can be posted, explaining why not otherwise.
+pub fn validate(poll: &NewPoll) -> Result<(), String> {
+    let count = poll.choices.len();
+    if !(MIN_POLL_CHOICES..=MAX_POLL_CHOICES).contains(&count) {
+        return Err(format!(
+            "A poll needs {MIN_POLL_CHOICES} to {MAX_POLL_CHOICES} choices, not {count}."
+        ));
+    }
+    if let Some(choice) = poll
+        .choices
+        .iter()
+        .find(|choice| choice.chars().count() > MAX_POLL_CHOICE_LENGTH)
+    {
+        return Err(format!(
+            "\"{choice}\" is over the limit of {MAX_POLL_CHOICE_LENGTH} characters."
+        ));
+    }
+    if poll
+        .expires_at
+        .is_some_and(|time| time <= Local::now().timestamp_millis())
+    {
+        return Err("The poll would end before it's posted.".into());
+    }
+    Ok(())
+}
+
+/// Describes when a poll ends, e.g. `1d 12h after posting`.
+pub fn expiry_text(poll: &NewPoll) -> String {
+    if let Some(time) = poll
+        .expires_at
+        .and_then(|time| Local.timestamp_millis_opt(time).single())
+    {
+        return format!("on {}", time.format("%Y-%m-%d %H:%M"));
+    }
+    let Some(duration) = poll.expired_after.map(TimeDelta::milliseconds) else {
+        return "never".into();
+    };
+
+    let parts: Vec<String> = [
+        (duration.num_days(), "d"),
+        (duration.num_hours() % 24, "h"),
+        (duration.num_minutes() % 60, "m"),
+    ]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, unit)| format!("{amount}{unit}"))
+    .collect();
+    if parts.is_empty() {
+        format!("{}s after posting", duration.num_seconds())
+    } else {
+        format!("{} after posting", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, MappedLocalTime, NaiveTime};
+
+    use super::*;
+
+    /// Berlin's time in 2024, with DST from 2024-03-31 02:00 to 2024-10-27 03:00.
+    #[derive(Clone, Copy)]
+    struct Berlin;
+
+    impl Berlin {
+        const WINTER: i32 = 3600;
+        const SUMMER: i32 = 2 * 3600;
+
+        fn offset(seconds: i32) -> FixedOffset {
+            FixedOffset::east_opt(seconds).unwrap()
+        }
+
+        fn time(date: &str) -> NaiveDateTime {
+            NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap()
+        }
+    }
+
+    impl TimeZone for Berlin {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Self
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let (summer, winter) = (Self::offset(Self::SUMMER), Self::offset(Self::WINTER));
+            let gap = Self::time("2024-03-31 02:00")..Self::time("2024-03-31 03:00");
+            let repeated = Self::time("2024-10-27 02:00")..Self::time("2024-10-27 03:00");
+            if gap.contains(local) {
+                MappedLocalTime::None
+            } else if repeated.contains(local) {
+                MappedLocalTime::Ambiguous(summer, winter)
+            } else if (gap.end..repeated.start).contains(local) {
+                MappedLocalTime::Single(summer)
+            } else {
+                MappedLocalTime::Single(winter)
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            let summer = Self::time("2024-03-31 01:00")..Self::time("2024-10-27 01:00");
+            Self::offset(if summer.contains(utc) {
+                Self::SUMMER
+            } else {
+                Self::WINTER
+            })
+        }
+    }
+
+    /// Parses an end date in Berlin, written back in RFC 3339.
+    fn at(input: &str) -> String {
+        match Expiry::parse_in(input, &Berlin) {
+            Ok(Expiry::At(time)) => time.with_timezone(&Berlin).to_rfc3339(),
+            _ => panic!("{input} should read as a date"),
+        }
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90m"), Ok(TimeDelta::minutes(90)));
+        assert_eq!(
+            parse_duration("1d12h"),
+            Ok(TimeDelta::days(1) + TimeDelta::hours(12))
+        );
+        assert_eq!(
+            parse_duration("1w 2d"),
+            Ok(TimeDelta::weeks(1) + TimeDelta::days(2))
+        );
+        assert_eq!(parse_duration("30s"), Ok(TimeDelta::seconds(30)));
+    }
+
+    #[test]
+    fn rejects_bad_durations() {
+        // a number without a unit is ambiguous.
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("99999999999999w").is_err());
+    }
+
+    #[test]
+    fn parses_expiries() {
+        assert!(matches!(Expiry::parse(""), Ok(Expiry::Never)));
+        assert!(matches!(Expiry::parse(" never "), Ok(Expiry::Never)));
+        assert!(matches!(
+            Expiry::parse("90m"),
+            Ok(Expiry::After(after)) if after == TimeDelta::minutes(90)
+        ));
+        assert_eq!(at("2024-10-04 18:00"), "2024-10-04T18:00:00+02:00");
+        assert_eq!(at("2024-10-04T18:00"), "2024-10-04T18:00:00+02:00");
+        assert_eq!(at("2024-10-04 18:00:30"), "2024-10-04T18:00:30+02:00");
+        assert_eq!(at("2024-10-04T16:00:00Z"), "2024-10-04T18:00:00+02:00");
+        assert!(Expiry::parse("tomorrow").is_err());
+        assert!(Expiry::parse("90").is_err());
+    }
+
+    #[test]
+    fn reads_a_date_alone_as_its_midnight() {
+        assert_eq!(at("2024-10-04"), "2024-10-04T00:00:00+02:00");
+        assert_eq!(at("2024-12-24"), "2024-12-24T00:00:00+01:00");
+    }
+
+    #[test]
+    fn handles_dst_changes() {
+        assert_eq!(
+            Expiry::parse_in("2024-03-31 02:30", &Berlin).err(),
+            Some("2024-03-31 02:30 doesn't exist in the local time zone.".into())
+        );
+        // an hour repeated when DST ends is taken the first time it comes.
+        assert_eq!(at("2024-10-27 02:30"), "2024-10-27T02:30:00+02:00");
+    }
+
+    #[test]
+    fn describes_expiries() {
+        let mut poll = NewPoll::default();
+        assert_eq!(expiry_text(&poll), "never");
+        Expiry::After(TimeDelta::days(1) + TimeDelta::hours(12)).apply(&mut poll);
+        assert_eq!(expiry_text(&poll), "1d 12h after posting");
+        Expiry::After(TimeDelta::seconds(30)).apply(&mut poll);
+        assert_eq!(expiry_text(&poll), "30s after posting");
+    }
+}
@@ -0,0 +1,581 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Layout, Position, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        antennas::Antenna, lists::UserList, models, notifications::NotificationQuery,
+        timelines::Timeline, Error,
+    },
+    config::{
+        deck::{self, ColumnSource, DeckColumn},
+        keymap::{DeckAction, Keymap},
+    },
+    state::session::Session,
+    ui::{layout, tasks::Tasks, theme::Theme},
+};
+
+use super::{
+    compose::Compose, notifications::NotificationFeed, timeline::TimelineFeed, Direction,
+    Navigation, Screen,
+};
+
+/// Timelines and notifications side by side in columns, each with its own selection.
+/// The columns are saved in the config as they are added, removed, moved and resized.
+pub struct Deck {
+    session: Session,
+    tasks: Tasks<Message>,
+    columns: Vec<Column>,
+    /// The index of the column keys go to.
+    focused: usize,
+    /// The sources to add a column for, while picking one.
+    picker: Option<ColumnPicker>,
+    keys: Keymap<DeckAction>,
+    /// Where each column was last drawn, to find the one clicked.
+    column_areas: Vec<Rect>,
+    /// The outcome of the last action, shown at the bottom until the next key.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+/// A column's settings, and the feed showing its source.
+struct Column {
+    config: DeckColumn,
+    feed: ColumnFeed,
+}
+
+enum ColumnFeed {
+    Timeline(TimelineFeed),
+    Notifications(NotificationFeed),
+}
+
+/// A list of sources to add a column for.
+struct ColumnPicker {
+    /// Notifications, the main timelines, mentions and the pinned timelines.
+    known: Vec<ColumnSource>,
+    /// The timelines of our user lists, once fetched.
+    lists: Vec<Timeline>,
+    /// The timelines of our antennas, once fetched.
+    antennas: Vec<Timeline>,
+    /// How many requests are running.
+    loading: usize,
+    list_state: ListState,
+}
+
+pub enum Message {
+    /// Focuses the column on the right or left.
+    SetColumn(Direction),
+    /// Selects the next or previous entry in the focused column.
+    SetNote(Direction),
+    /// Goes to the next or previous page of the focused column, if it has pages.
+    SetPage(Direction),
+    /// Fetches the focused column's newest entries again.
+    Refresh,
+    /// Replies to the selected note, or the note of the selected notification.
+    Reply,
+    OpenPicker,
+    /// Selects the next or previous source in the picker.
+    SetPickerSource(Direction),
+    /// Adds a column for the source selected in the picker, after the focused one.
+    AddColumn,
+    LoadedLists(Result<Vec<UserList>, Error>),
+    LoadedAntennas(Result<Vec<Antenna>, Error>),
+    ClosePicker,
+    RemoveColumn,
+    /// Swaps the focused column with the one on the right or left.
+    MoveColumn(Direction),
+    /// Makes the focused column wider or narrower.
+    Resize(Direction),
+    /// Focuses the column clicked, selecting what was clicked in it.
+    Click(Position),
+    /// Scrolls the column under the pointer.
+    Scroll(Position, Direction),
+    Close,
+}
+
+impl Column {
+    /// Creates a column and starts fetching its source.
+    fn new(session: &Session, config: DeckColumn) -> Self {
+        let client = session.client.clone();
+        let feed = match &config.source {
+            ColumnSource::Timeline { timeline } => {
+                let mut feed = TimelineFeed::new(client, timeline.clone());
+                feed.start();
+                ColumnFeed::Timeline(feed)
+            }
+            ColumnSource::Notifications => {
+                let query = NotificationQuery {
+                    grouped: true,
+                    ..NotificationQuery::default()
+                };
+                let mut feed = NotificationFeed::new(client, query);
+                feed.refresh();
+                ColumnFeed::Notifications(feed)
+            }
+        };
+        Self { config, feed }
+    }
+
+    fn select(&mut self, direction: Direction) {
+        match (&mut self.feed, direction) {
+            (ColumnFeed::Timeline(feed), Direction::Next) => feed.select_next(),
+            (ColumnFeed::Timeline(feed), Direction::Previous) => feed.select_prev(),
+            (ColumnFeed::Notifications(feed), Direction::Next) => feed.select_next(),
+            (ColumnFeed::Notifications(feed), Direction::Previous) => feed.select_prev(),
+        }
+    }
+
+    /// Returns the selected note, or the note of the selected notification.
+    fn selected_note(&self) -> Option<&models::Note> {
+        match &self.feed {
+            ColumnFeed::Timeline(feed) => feed.selected_note(),
+            ColumnFeed::Notifications(feed) => feed.selected()?.note.as_ref(),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let theme = Theme::current();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .border_style(if focused {
+                theme.selected_border
+            } else {
+                theme.border
+            })
+            .title_top(self.config.source.title());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        match &mut self.feed {
+            ColumnFeed::Timeline(feed) => {
+                feed.update();
+                feed.render_column(frame, inner);
+            }
+            ColumnFeed::Notifications(feed) => {
+                feed.update();
+                let [list_area, status_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+                feed.render(frame, list_area);
+                frame.render_widget(
+                    Line::from(feed.status_text().unwrap_or_default()),
+                    status_area,
+                );
+            }
+        }
+    }
+}
+
+impl ColumnPicker {
+    /// The sources known without fetching, then the lists and antennas not pinned already.
+    fn sources(&self) -> Vec<ColumnSource> {
+        let fetched = self
+            .lists
+            .iter()
+            .chain(&self.antennas)
+            .map(|timeline| ColumnSource::Timeline {
+                timeline: timeline.clone(),
+            })
+            .filter(|source| !self.known.contains(source));
+        self.known.iter().cloned().chain(fetched).collect()
+    }
+}
+
+impl Deck {
+    /// Creates the screen with the columns in the config, or the default ones.
+    pub fn new(session: Session) -> Self {
+        let mut configs = session.config.read().deck.clone();
+        if configs.is_empty() {
+            configs = DeckColumn::defaults();
+        }
+        let columns = configs
+            .into_iter()
+            .map(|config| Column::new(&session, config))
+            .collect();
+        let keys = Keymap::new(&session.config.read().keys.deck);
+
+        Self {
+            session,
+            tasks: Tasks::default(),
+            columns,
+            focused: 0,
+            picker: None,
+            keys,
+            column_areas: Vec::new(),
+            status: None,
+            navigation: None,
+        }
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SetColumn(direction) => {
+                self.focused = match direction {
+                    Direction::Next => (self.focused + 1).min(self.columns.len() - 1),
+                    Direction::Previous => self.focused.saturating_sub(1),
+                };
+            }
+            Message::SetNote(direction) => self.columns[self.focused].select(direction),
+            Message::SetPage(direction) => {
+                if let ColumnFeed::Timeline(feed) = &mut self.columns[self.focused].feed {
+                    match direction {
+                        Direction::Next => feed.next_page(),
+                        Direction::Previous => feed.feed_state.select_prev_page(),
+                    }
+                }
+            }
+            Message::Refresh => match &mut self.columns[self.focused].feed {
+                ColumnFeed::Timeline(feed) => feed.refresh(),
+                ColumnFeed::Notifications(feed) => feed.refresh(),
+            },
+            Message::Reply => {
+                if let Some(note) = self.columns[self.focused].selected_note() {
+                    let compose =
+                        Compose::reply(self.session.clone(), note, &[note.user.clone()], None);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::OpenPicker => {
+                let mut list_state = ListState::default();
+                list_state.select(Some(0));
+                self.picker = Some(ColumnPicker {
+                    known: self.sources(),
+                    lists: Vec::new(),
+                    antennas: Vec::new(),
+                    loading: 2,
+                    list_state,
+                });
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedLists(client.user_lists()));
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedAntennas(client.antennas()));
+            }
+            Message::SetPickerSource(direction) => {
+                if let Some(picker) = &mut self.picker {
+                    match direction {
+                        Direction::Next => picker.list_state.next(),
+                        Direction::Previous => picker.list_state.previous(),
+                    }
+                }
+            }
+            Message::AddColumn => {
+                let Some(picker) = self.picker.take() else {
+                    return;
+                };
+                let Some(source) = picker
+                    .list_state
+                    .selected
+                    .and_then(|idx| picker.sources().get(idx).cloned())
+                else {
+                    return;
+                };
+                let column = Column::new(&self.session, DeckColumn::new(source));
+                let idx = (self.focused + 1).min(self.columns.len());
+                self.columns.insert(idx, column);
+                self.focused = idx;
+                self.save();
+            }
+            // a picker closed since has no use for them.
+            Message::LoadedLists(result) => {
+                let Some(picker) = &mut self.picker else {
+                    return;
+                };
+                picker.loading = picker.loading.saturating_sub(1);
+                match result {
+                    Ok(lists) => {
+                        picker.lists = lists
+                            .into_iter()
+                            .map(|list| Timeline::UserList {
+                                id: list.id,
+                                name: list.name,
+                            })
+                            .collect();
+                    }
+                    Err(err) => self.status = Some(format!("Couldn't load lists: {err}")),
+                }
+            }
+            Message::LoadedAntennas(result) => {
+                let Some(picker) = &mut self.picker else {
+                    return;
+                };
+                picker.loading = picker.loading.saturating_sub(1);
+                match result {
+                    Ok(antennas) => {
+                        picker.antennas = antennas
+                            .into_iter()
+                            .map(|antenna| Timeline::Antenna {
+                                id: antenna.id,
+                                name: antenna.name,
+                            })
+                            .collect();
+                    }
+                    Err(err) => self.status = Some(format!("Couldn't load antennas: {err}")),
+                }
+            }
+            Message::ClosePicker => self.picker = None,
+            Message::RemoveColumn => {
+                if self.columns.len() == 1 {
+                    self.status = Some("The deck needs at least one column.".into());
+                    return;
+                }
+                self.columns.remove(self.focused);
+                self.focused = self.focused.min(self.columns.len() - 1);
+                self.save();
+            }
+            Message::MoveColumn(direction) => {
+                let other = match direction {
+                    Direction::Next => self.focused + 1,
+                    Direction::Previous => match self.focused.checked_sub(1) {
+                        Some(idx) => idx,
+                        None => return,
+                    },
+                };
+                if other < self.columns.len() {
+                    self.columns.swap(self.focused, other);
+                    self.focused = other;
+                    self.save();
+                }
+            }
+            Message::Resize(direction) => {
+                let width = &mut self.columns[self.focused].config.width;
+                *width = match direction {
+                    Direction::Next => (*width + 1).min(deck::MAX_WIDTH),
+                    Direction::Previous => width.saturating_sub(1).max(deck::MIN_WIDTH),
+                };
+                self.save();
+            }
+            Message::Click(position) => {
+                let Some(idx) = self.column_at(position) else {
+                    return;
+                };
+                self.focused = idx;
+                if let ColumnFeed::Timeline(feed) = &mut self.columns[idx].feed {
+                    self.navigation = feed.click(&self.session, position);
+                }
+            }
+            Message::Scroll(position, direction) => {
+                if let Some(idx) = self.column_at(position) {
+                    self.columns[idx].select(direction);
+                }
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: DeckAction) {
+        self.message(match action {
+            DeckAction::NextColumn => Message::SetColumn(Direction::Next),
+            DeckAction::PrevColumn => Message::SetColumn(Direction::Previous),
+            DeckAction::NextNote => Message::SetNote(Direction::Next),
+            DeckAction::PrevNote => Message::SetNote(Direction::Previous),
+            DeckAction::NextPage => Message::SetPage(Direction::Next),
+            DeckAction::PrevPage => Message::SetPage(Direction::Previous),
+            DeckAction::Refresh => Message::Refresh,
+            DeckAction::Reply => Message::Reply,
+            DeckAction::AddColumn => Message::OpenPicker,
+            DeckAction::RemoveColumn => Message::RemoveColumn,
+            DeckAction::MoveColumnRight => Message::MoveColumn(Direction::Next),
+            DeckAction::MoveColumnLeft => Message::MoveColumn(Direction::Previous),
+            DeckAction::Widen => Message::Resize(Direction::Next),
+            DeckAction::Narrow => Message::Resize(Direction::Previous),
+            DeckAction::Close => Message::Close,
+        });
+    }
+
+    /// Lists what a column can be added for without fetching anything: notifications,
+    /// the main timelines, mentions, then the pinned timelines.
+    fn sources(&self) -> Vec<ColumnSource> {
+        let pinned = self.session.config.read().pinned_timelines.clone();
+        let timelines = Timeline::MAIN
+            .into_iter()
+            .chain([Timeline::Mentions])
+            .chain(pinned)
+            .map(|timeline| ColumnSource::Timeline { timeline });
+
+        [ColumnSource::Notifications]
+            .into_iter()
+            .chain(timelines)
+            .collect()
+    }
+
+    /// Saves the columns as they are now in the config.
+    fn save(&self) {
+        let deck = self
+            .columns
+            .iter()
+            .map(|column| column.config.clone())
+            .collect();
+        self.session.update_config(|config| config.deck = deck);
+    }
+
+    fn column_at(&self, position: Position) -> Option<usize> {
+        self.column_areas
+            .iter()
+            .position(|area| area.contains(position))
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.picker.is_some() {
+            return "Move (j/k) / Add (Enter) / Cancel (q)".into();
+        }
+        self.keys.help(&[
+            ("Focus", &[DeckAction::PrevColumn, DeckAction::NextColumn]),
+            ("Move", &[DeckAction::NextNote, DeckAction::PrevNote]),
+            ("Reply", &[DeckAction::Reply]),
+            ("Add", &[DeckAction::AddColumn]),
+            ("Remove", &[DeckAction::RemoveColumn]),
+            (
+                "Reorder",
+                &[DeckAction::MoveColumnLeft, DeckAction::MoveColumnRight],
+            ),
+            ("Resize", &[DeckAction::Narrow, DeckAction::Widen]),
+            ("Back", &[DeckAction::Close]),
+        ])
+    }
+
+    fn render_picker(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(picker) = &mut self.picker else {
+            return;
+        };
+        let labels: Vec<String> = picker.sources().iter().map(ColumnSource::title).collect();
+        let area = layout::centered(area, 40, labels.len() as u16 + 2);
+
+        let theme = Theme::current();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .border_style(theme.accent)
+            .title_top(if picker.loading > 0 {
+                "Add a column (loading...)"
+            } else {
+                "Add a column"
+            });
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(theme.background(), area);
+        frame.render_widget(block, area);
+
+        let count = labels.len();
+        let builder = ListBuilder::new(move |ctx| {
+            let style = if ctx.is_selected {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            (Line::styled(labels[ctx.index].clone(), style), 1)
+        });
+        frame.render_stateful_widget(ListView::new(builder, count), inner, &mut picker.list_state);
+    }
+}
+
+impl Screen for Deck {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let [columns_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+
+        let constraints = self
+            .columns
+            .iter()
+            .map(|column| Constraint::Fill(column.config.width));
+        self.column_areas = Layout::horizontal(constraints).split(columns_area).to_vec();
+        for (idx, (column, area)) in self.columns.iter_mut().zip(&self.column_areas).enumerate() {
+            column.render(frame, *area, idx == self.focused);
+        }
+
+        self.render_picker(frame, columns_area);
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        self.status = None;
+        if self.picker.is_some() {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.message(Message::SetPickerSource(Direction::Next));
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.message(Message::SetPickerSource(Direction::Previous));
+                }
+                KeyCode::Enter => self.message(Message::AddColumn),
+                KeyCode::Char('q') | KeyCode::Esc => self.message(Message::ClosePicker),
+                _ => (),
+            }
+            return;
+        }
+
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        if self.picker.is_some() {
+            return;
+        }
+        let position = Position::new(event.column, event.row);
+        match event.kind {
+            MouseEventKind::ScrollDown => self.message(Message::Scroll(position, Direction::Next)),
+            MouseEventKind::ScrollUp => {
+                self.message(Message::Scroll(position, Direction::Previous));
+            }
+            MouseEventKind::Down(MouseButton::Left) => self.message(Message::Click(position)),
+            _ => (),
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_fetched_sources_after_the_known_ones_once() {
+        let list = Timeline::UserList {
+            id: "1".into(),
+            name: "Friends".into(),
+        };
+        let antenna = Timeline::Antenna {
+            id: "2".into(),
+            name: "Rust".into(),
+        };
+        let picker = ColumnPicker {
+            known: vec![
+                ColumnSource::Notifications,
+                ColumnSource::Timeline {
+                    timeline: list.clone(),
+                },
+            ],
+            lists: vec![list],
+            antennas: vec![antenna],
+            loading: 0,
+            list_state: ListState::default(),
+        };
+
+        let titles: Vec<String> = picker.sources().iter().map(ColumnSource::title).collect();
+        assert_eq!(titles, ["Notifications", "List: Friends", "Antenna: Rust"]);
+    }
+}
@@ -0,0 +1,219 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    config::{
+        drafts::{Draft, DraftTarget},
+        keymap::{DraftsAction, Keymap},
+    },
+    state::session::Session,
+    ui::theme::Theme,
+};
+
+use super::{compose::Compose, Direction, Navigation, Screen};
+
+/// Lists the notes of the account that were being written but not posted, to resume or discard them.
+pub struct Drafts {
+    session: Session,
+    /// The drafts, the most recently saved first.
+    drafts: Vec<Draft>,
+    list_state: ListState,
+    keys: Keymap<DraftsAction>,
+    /// The draft to discard if discarding is asked again.
+    confirm_discard: Option<DraftTarget>,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous draft.
+    Select(Direction),
+    /// Opens the selected draft in the composer.
+    Resume,
+    /// Asks to confirm discarding the selected draft, or discards it if already asked.
+    Discard,
+    Close,
+}
+
+impl Drafts {
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.drafts);
+        let mut screen = Self {
+            session,
+            drafts: Vec::new(),
+            list_state: ListState::default(),
+            keys,
+            confirm_discard: None,
+            status: None,
+            navigation: None,
+        };
+        screen.reload();
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Select(Direction::Next) => self.list_state.next(),
+            Message::Select(Direction::Previous) => self.list_state.previous(),
+            Message::Resume => {
+                if let Some(draft) = self.selected() {
+                    let compose = Compose::resume(self.session.clone(), draft);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::Discard => {
+                let Some(draft) = self.selected() else {
+                    return;
+                };
+                let target = draft.target.clone();
+                if self.confirm_discard.as_ref() != Some(&target) {
+                    let key = self
+                        .keys
+                        .keys_for(DraftsAction::Discard)
+                        .unwrap_or_default();
+                    self.status = Some(format!("Press {key} again to discard the draft."));
+                    self.confirm_discard = Some(target);
+                    return;
+                }
+
+                self.confirm_discard = None;
+                self.session.update_drafts(|drafts| drafts.remove(&target));
+                self.status = Some("Draft discarded.".into());
+                self.reload();
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: DraftsAction) {
+        self.message(match action {
+            DraftsAction::NextDraft => Message::Select(Direction::Next),
+            DraftsAction::PrevDraft => Message::Select(Direction::Previous),
+            DraftsAction::Resume => Message::Resume,
+            DraftsAction::Discard => Message::Discard,
+            DraftsAction::Close => Message::Close,
+        });
+    }
+
+    /// Reads the drafts again, as the composer saves them while they're being written.
+    fn reload(&mut self) {
+        let user_id = self.session.user_id();
+        self.drafts = self
+            .session
+            .drafts
+            .read()
+            .of_account(&self.session.client.instance, user_id.as_deref());
+        self.fix_selection();
+    }
+
+    fn selected(&self) -> Option<&Draft> {
+        self.drafts.get(self.list_state.selected?)
+    }
+
+    /// Keeps the selection within the drafts after they changed.
+    fn fix_selection(&mut self) {
+        let len = self.drafts.len();
+        let selected = match self.list_state.selected {
+            _ if len == 0 => None,
+            Some(idx) => Some(idx.min(len - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.drafts.is_empty() {
+            let back = self.keys.help(&[("Back", &[DraftsAction::Close])]);
+            return format!("No drafts. / {back}");
+        }
+        self.keys.help(&[
+            ("Move", &[DraftsAction::NextDraft, DraftsAction::PrevDraft]),
+            ("Resume", &[DraftsAction::Resume]),
+            ("Discard", &[DraftsAction::Discard]),
+            ("Back", &[DraftsAction::Close]),
+        ])
+    }
+}
+
+/// Describes a draft, e.g. `2024-10-04 18:30  Reply to @alice@misskey.io: Sounds good`.
+fn label(draft: &Draft) -> String {
+    let mut target = match (&draft.reply, &draft.quote) {
+        (Some(note), _) => format!("Reply to @{}", note.user.acct()),
+        (None, Some(note)) => format!("Quote @{}", note.user.acct()),
+        (None, None) => "New note".into(),
+    };
+    if let Some(channel) = &draft.channel {
+        target.push_str(&format!(" in {}", channel.name));
+    }
+    let excerpt = match draft.cw.as_str() {
+        "" => draft.text.lines().next().unwrap_or_default(),
+        cw => cw,
+    };
+    let files = match draft.files.len() {
+        0 => String::new(),
+        count => format!(" [{count} file(s)]"),
+    };
+    format!("{}  {target}: {excerpt}{files}", draft.saved_at_text())
+}
+
+impl Screen for Drafts {
+    fn view(&mut self, frame: &mut Frame) {
+        self.reload();
+
+        let [list_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_type(BorderType::Plain)
+            .title_top("Drafts");
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+
+        let labels: Vec<String> = self.drafts.iter().map(label).collect();
+        let count = labels.len();
+        let builder = ListBuilder::new(move |ctx| {
+            let style = if ctx.is_selected {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            (Line::styled(labels[ctx.index].clone(), style), 1)
+        });
+        frame.render_stateful_widget(ListView::new(builder, count), inner, &mut self.list_state);
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        let action = self.keys.action(key);
+        // any other key cancels a pending discard.
+        if action != Some(DraftsAction::Discard) && self.confirm_discard.take().is_some() {
+            self.status = None;
+        }
+
+        if let Some(action) = action {
+            self.action(action);
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
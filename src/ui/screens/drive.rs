@@ -0,0 +1,681 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Gauge},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        drive::{DriveFile, DriveFolder, DriveUsage},
+        Error,
+    },
+    config::keymap::{DriveAction, Keymap},
+    state::session::Session,
+    ui::{tasks::Tasks, theme::Theme, widgets::text_input::TextInput},
+};
+
+use super::{Direction, Navigation, Screen};
+
+/// Called with the file picked from the Drive.
+pub type OnPick = Box<dyn Fn(DriveFile) + Send + Sync>;
+
+/// Browses our Drive's folders and files, to manage them or pick one.
+pub struct Drive {
+    session: Session,
+    tasks: Tasks<Message>,
+    /// The folders from the root to the current one, which is the root if empty.
+    path: Vec<DriveFolder>,
+    folders: Vec<DriveFolder>,
+    files: Vec<DriveFile>,
+    list_state: ListState,
+    keys: Keymap<DriveAction>,
+    usage: Option<DriveUsage>,
+    /// The text being typed, if any.
+    prompt: Option<Prompt>,
+    /// The entry to move into the next folder pasted in.
+    cut: Option<Entry>,
+    /// The entry to delete on the next `d` key.
+    confirm_delete: Option<Entry>,
+    uploads: Vec<Upload>,
+    /// How many uploads were started, to tell them apart.
+    upload_count: usize,
+    /// Where picked files go, when picking attachments.
+    on_pick: Option<OnPick>,
+    /// How many requests are running, uploads aside.
+    loading: usize,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+/// A folder or file, by id.
+#[derive(Clone, PartialEq)]
+pub enum Entry {
+    Folder(String),
+    File(String),
+}
+
+/// What the typed text is for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    NewFolder,
+    Rename,
+    /// The path of a local file to upload.
+    Upload,
+}
+
+pub struct Prompt {
+    kind: PromptKind,
+    input: TextInput,
+}
+
+/// A file being uploaded.
+pub struct Upload {
+    id: usize,
+    name: String,
+    size: u64,
+    sent: u64,
+}
+
+pub enum Message {
+    /// Selects the next or previous entry.
+    Select(Direction),
+    /// Opens the selected folder, or picks the selected file.
+    Open,
+    /// Goes to the parent folder.
+    Up,
+    /// Starts typing the text for an action.
+    StartPrompt(PromptKind),
+    /// Edits the prompt.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the prompt.
+    PasteText(String),
+    /// Runs the action the prompt is for.
+    SubmitPrompt,
+    CancelPrompt,
+    /// Marks the selected entry to be moved.
+    Cut,
+    /// Moves the marked entry into the current folder.
+    Paste,
+    /// Asks to confirm deleting the selected entry, or deletes it if already asked.
+    DeleteEntry,
+    Refresh,
+    LoadedFolders(Result<Vec<DriveFolder>, Error>),
+    LoadedFiles(Result<Vec<DriveFile>, Error>),
+    LoadedUsage(Result<DriveUsage, Error>),
+    /// Receives the outcome of changing an entry, refreshing on success.
+    Changed(Result<String, Error>),
+    /// Receives how many bytes of an upload were sent.
+    UploadProgress {
+        id: usize,
+        sent: u64,
+    },
+    Uploaded {
+        id: usize,
+        result: Result<DriveFile, Error>,
+    },
+    Close,
+}
+
+impl Drive {
+    /// Creates the screen at the Drive's root and starts fetching it.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.drive);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            path: Vec::new(),
+            folders: Vec::new(),
+            files: Vec::new(),
+            list_state: ListState::default(),
+            keys,
+            usage: None,
+            prompt: None,
+            cut: None,
+            confirm_delete: None,
+            uploads: Vec::new(),
+            upload_count: 0,
+            on_pick: None,
+            loading: 0,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    /// Creates the screen to pick a file, which is passed to `on_pick` before going back.
+    pub fn picker(session: Session, on_pick: impl Fn(DriveFile) + Send + Sync + 'static) -> Self {
+        let mut screen = Self::new(session);
+        screen.on_pick = Some(Box::new(on_pick));
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Select(Direction::Next) => self.list_state.next(),
+            Message::Select(Direction::Previous) => self.list_state.previous(),
+            Message::Open => match self.selected() {
+                Some(Entry::Folder(id)) => {
+                    if let Some(folder) = self.folders.iter().find(|folder| folder.id == id) {
+                        self.path.push(folder.clone());
+                        self.status = None;
+                        self.message(Message::Refresh);
+                    }
+                }
+                Some(Entry::File(id)) => {
+                    let file = self.files.iter().find(|file| file.id == id);
+                    if let (Some(on_pick), Some(file)) = (&self.on_pick, file) {
+                        on_pick(file.clone());
+                        self.navigation = Some(Navigation::Back);
+                    }
+                }
+                None => (),
+            },
+            Message::Up => {
+                if self.path.pop().is_some() {
+                    self.status = None;
+                    self.message(Message::Refresh);
+                }
+            }
+            Message::StartPrompt(kind) => {
+                let input = match (kind, self.selected()) {
+                    (PromptKind::Rename, Some(entry)) => self.entry_name(&entry),
+                    (PromptKind::Rename, None) => return,
+                    _ => String::new(),
+                };
+                self.prompt = Some(Prompt {
+                    kind,
+                    input: TextInput::new(input),
+                });
+            }
+            Message::Edit(key) => {
+                if let Some(prompt) = &mut self.prompt {
+                    prompt.input.handle_key(key);
+                }
+            }
+            Message::PasteText(text) => {
+                if let Some(prompt) = &mut self.prompt {
+                    prompt.input.insert(&text);
+                }
+            }
+            Message::SubmitPrompt => {
+                if let Some(prompt) = self.prompt.take() {
+                    self.submit(prompt);
+                }
+            }
+            Message::CancelPrompt => self.prompt = None,
+            Message::Cut => {
+                if let Some(entry) = self.selected() {
+                    let paste = self.keys.help(&[("Paste", &[DriveAction::Paste])]);
+                    self.status = Some(format!(
+                        "Cut {}. {paste} in another folder to move it.",
+                        self.entry_name(&entry)
+                    ));
+                    self.cut = Some(entry);
+                }
+            }
+            Message::Paste => {
+                let Some(entry) = self.cut.take() else {
+                    return;
+                };
+                let client = self.session.client.clone();
+                let folder_id = self.folder_id();
+                self.loading += 1;
+                self.tasks.spawn(move || {
+                    Message::Changed(match entry {
+                        Entry::Folder(id) => client
+                            .move_folder(&id, folder_id.as_deref())
+                            .map(|folder| format!("Moved {}.", folder.name)),
+                        Entry::File(id) => client
+                            .move_file(&id, folder_id.as_deref())
+                            .map(|file| format!("Moved {}.", file.name)),
+                    })
+                });
+            }
+            Message::DeleteEntry => {
+                let Some(entry) = self.selected() else {
+                    return;
+                };
+                if self.confirm_delete.as_ref() != Some(&entry) {
+                    let key = self.keys.keys_for(DriveAction::Delete).unwrap_or_default();
+                    self.status = Some(format!(
+                        "Press {key} again to delete {}.",
+                        self.entry_name(&entry)
+                    ));
+                    self.confirm_delete = Some(entry);
+                    return;
+                }
+
+                let client = self.session.client.clone();
+                let name = self.entry_name(&entry);
+                self.confirm_delete = None;
+                self.loading += 1;
+                self.tasks.spawn(move || {
+                    let result = match entry {
+                        Entry::Folder(id) => client.delete_folder(&id),
+                        Entry::File(id) => client.delete_file(&id),
+                    };
+                    Message::Changed(result.map(|()| format!("Deleted {name}.")))
+                });
+            }
+            Message::Refresh => {
+                let folder_id = self.folder_id();
+                let client = self.session.client.clone();
+                let id = folder_id.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedFolders(client.drive_folders(id.as_deref())));
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedFiles(client.drive_files(folder_id.as_deref())));
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedUsage(client.drive_usage()));
+                self.loading += 3;
+            }
+            Message::LoadedFolders(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(folders) => self.folders = folders,
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                self.fix_selection();
+            }
+            Message::LoadedFiles(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(files) => self.files = files,
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                self.fix_selection();
+            }
+            Message::LoadedUsage(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(usage) => self.usage = Some(usage),
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Changed(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(status) => {
+                        self.status = Some(status);
+                        self.message(Message::Refresh);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::UploadProgress { id, sent } => {
+                if let Some(upload) = self.uploads.iter_mut().find(|upload| upload.id == id) {
+                    upload.sent = sent;
+                }
+            }
+            Message::Uploaded { id, result } => {
+                self.uploads.retain(|upload| upload.id != id);
+                match result {
+                    Ok(file) => {
+                        self.status = Some(format!("Uploaded {}.", file.name));
+                        self.message(Message::Refresh);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: DriveAction) {
+        self.message(match action {
+            DriveAction::NextEntry => Message::Select(Direction::Next),
+            DriveAction::PrevEntry => Message::Select(Direction::Previous),
+            DriveAction::Open => Message::Open,
+            DriveAction::Up => Message::Up,
+            DriveAction::NewFolder => Message::StartPrompt(PromptKind::NewFolder),
+            DriveAction::Rename => Message::StartPrompt(PromptKind::Rename),
+            DriveAction::Upload => Message::StartPrompt(PromptKind::Upload),
+            DriveAction::Cut => Message::Cut,
+            DriveAction::Paste => Message::Paste,
+            DriveAction::Delete => Message::DeleteEntry,
+            DriveAction::Refresh => Message::Refresh,
+            DriveAction::Close => Message::Close,
+        });
+    }
+
+    /// Runs the action a prompt was for.
+    fn submit(&mut self, prompt: Prompt) {
+        let input = prompt.input.text().trim().to_owned();
+        if input.is_empty() {
+            return;
+        }
+        let client = self.session.client.clone();
+        let folder_id = self.folder_id();
+
+        match prompt.kind {
+            PromptKind::NewFolder => {
+                self.loading += 1;
+                self.tasks.spawn(move || {
+                    Message::Changed(
+                        client
+                            .create_folder(&input, folder_id.as_deref())
+                            .map(|folder| format!("Created {}.", folder.name)),
+                    )
+                });
+            }
+            PromptKind::Rename => {
+                let Some(entry) = self.selected() else {
+                    return;
+                };
+                self.loading += 1;
+                self.tasks.spawn(move || {
+                    Message::Changed(match entry {
+                        Entry::Folder(id) => client
+                            .rename_folder(&id, &input)
+                            .map(|folder| format!("Renamed to {}.", folder.name)),
+                        Entry::File(id) => client
+                            .rename_file(&id, &input)
+                            .map(|file| format!("Renamed to {}.", file.name)),
+                    })
+                });
+            }
+            PromptKind::Upload => {
+                let path = PathBuf::from(input);
+                let size = match path.metadata() {
+                    Ok(metadata) if metadata.is_file() => metadata.len(),
+                    Ok(_) => {
+                        self.status = Some(format!("{} is not a file.", path.display()));
+                        return;
+                    }
+                    Err(err) => {
+                        self.status = Some(format!("Can't read {}: {err}", path.display()));
+                        return;
+                    }
+                };
+
+                let id = self.upload_count;
+                self.upload_count += 1;
+                self.uploads.push(Upload {
+                    id,
+                    name: path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    size,
+                    sent: 0,
+                });
+                self.tasks.spawn_with(move |sender| {
+                    let result = client.upload_file(&path, folder_id.as_deref(), |sent| {
+                        sender.send(Message::UploadProgress { id, sent }).ok();
+                    });
+                    Message::Uploaded { id, result }
+                });
+            }
+        }
+    }
+
+    /// The current folder's id, or `None` at the root.
+    fn folder_id(&self) -> Option<String> {
+        self.path.last().map(|folder| folder.id.clone())
+    }
+
+    /// The selected entry, with folders listed before files.
+    fn selected(&self) -> Option<Entry> {
+        let idx = self.list_state.selected?;
+        match idx.checked_sub(self.folders.len()) {
+            None => Some(Entry::Folder(self.folders[idx].id.clone())),
+            Some(idx) => self.files.get(idx).map(|file| Entry::File(file.id.clone())),
+        }
+    }
+
+    fn entry_name(&self, entry: &Entry) -> String {
+        let name = match entry {
+            Entry::Folder(id) => self
+                .folders
+                .iter()
+                .find(|folder| folder.id == *id)
+                .map(|folder| &folder.name),
+            Entry::File(id) => self
+                .files
+                .iter()
+                .find(|file| file.id == *id)
+                .map(|file| &file.name),
+        };
+        name.cloned().unwrap_or_default()
+    }
+
+    /// Keeps the selection within the entries after they changed.
+    fn fix_selection(&mut self) {
+        let len = self.folders.len() + self.files.len();
+        let selected = match self.list_state.selected {
+            _ if len == 0 => None,
+            Some(idx) => Some(idx.min(len - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    /// Describes each entry, e.g. `cat.png  image/png  1.2 MB  [sensitive]  alt: A cat`.
+    fn labels(&self) -> Vec<String> {
+        let folders = self
+            .folders
+            .iter()
+            .map(|folder| format!("{}/", folder.name));
+        let files = self.files.iter().map(|file| {
+            let mut label = format!(
+                "{}  {}  {}",
+                file.name,
+                file.file_type,
+                format_size(file.size)
+            );
+            if file.is_sensitive {
+                label.push_str("  [sensitive]");
+            }
+            if let Some(alt) = file.comment.as_deref().filter(|alt| !alt.is_empty()) {
+                label.push_str(&format!("  alt: {alt}"));
+            }
+            label
+        });
+
+        folders.chain(files).collect()
+    }
+
+    fn title(&self) -> String {
+        let path: Vec<&str> = self
+            .path
+            .iter()
+            .map(|folder| folder.name.as_str())
+            .collect();
+        let mode = if self.on_pick.is_some() {
+            "Pick a file"
+        } else {
+            "Drive"
+        };
+        format!("{mode}: /{}", path.join("/"))
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading > 0 {
+            return "Loading...".into();
+        }
+        match self.prompt.as_ref().map(|prompt| prompt.kind) {
+            Some(PromptKind::Upload) => return "Upload (Enter) / Cancel (Tab)".into(),
+            Some(_) => return "Save (Enter) / Cancel (Tab)".into(),
+            None => (),
+        }
+
+        let open = if self.on_pick.is_some() {
+            "Open or pick"
+        } else {
+            "Open"
+        };
+        let paste: &[DriveAction] = if self.cut.is_some() {
+            &[DriveAction::Paste]
+        } else {
+            &[]
+        };
+        self.keys.help(&[
+            ("Move", &[DriveAction::NextEntry, DriveAction::PrevEntry]),
+            (open, &[DriveAction::Open]),
+            ("Up", &[DriveAction::Up]),
+            ("New folder", &[DriveAction::NewFolder]),
+            ("Rename", &[DriveAction::Rename]),
+            ("Cut", &[DriveAction::Cut]),
+            ("Paste", paste),
+            ("Delete", &[DriveAction::Delete]),
+            ("Upload", &[DriveAction::Upload]),
+            ("Refresh", &[DriveAction::Refresh]),
+            ("Back", &[DriveAction::Close]),
+        ])
+    }
+
+    fn view_usage(&self, frame: &mut Frame, area: Rect) {
+        let Some(usage) = self.usage else {
+            return;
+        };
+        let ratio = if usage.capacity == 0 {
+            0.0
+        } else {
+            (usage.usage as f64 / usage.capacity as f64).clamp(0.0, 1.0)
+        };
+        let gauge = Gauge::default().ratio(ratio).label(format!(
+            "{} of {} used",
+            format_size(usage.usage),
+            format_size(usage.capacity)
+        ));
+        frame.render_widget(gauge, area);
+    }
+}
+
+impl Screen for Drive {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let prompt_height = if self.prompt.is_some() { 3 } else { 0 };
+        let [usage_area, list_area, uploads_area, prompt_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(self.uploads.len() as u16),
+            Constraint::Length(prompt_height),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.view_usage(frame, usage_area);
+
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_type(BorderType::Plain)
+            .title_top(self.title());
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+
+        let labels = self.labels();
+        let count = labels.len();
+        let builder = ListBuilder::new(move |ctx| {
+            let style = if ctx.is_selected {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            (Line::styled(labels[ctx.index].clone(), style), 1)
+        });
+        frame.render_stateful_widget(ListView::new(builder, count), inner, &mut self.list_state);
+
+        let upload_areas =
+            Layout::vertical(vec![Constraint::Length(1); self.uploads.len()]).split(uploads_area);
+        for (upload, area) in self.uploads.iter().zip(upload_areas.iter()) {
+            let ratio = if upload.size == 0 {
+                1.0
+            } else {
+                (upload.sent as f64 / upload.size as f64).clamp(0.0, 1.0)
+            };
+            let gauge = Gauge::default().ratio(ratio).label(format!(
+                "Uploading {} ({} of {})",
+                upload.name,
+                format_size(upload.sent),
+                format_size(upload.size)
+            ));
+            frame.render_widget(gauge, *area);
+        }
+
+        if let Some(prompt) = &mut self.prompt {
+            let title = match prompt.kind {
+                PromptKind::NewFolder => "New folder",
+                PromptKind::Rename => "Rename",
+                PromptKind::Upload => "Path of the file to upload",
+            };
+            let prompt_box = Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Plain)
+                .title_top(title);
+            let input_area = prompt_box.inner(prompt_area);
+            frame.render_widget(prompt_box, prompt_area);
+            prompt.input.render(frame, input_area, true);
+        }
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.prompt.is_some() {
+            match key.code {
+                KeyCode::Enter => self.message(Message::SubmitPrompt),
+                KeyCode::Tab => self.message(Message::CancelPrompt),
+                _ => self.message(Message::Edit(key)),
+            }
+            return;
+        }
+        let action = self.keys.action(key);
+        // any other key cancels a pending delete.
+        if action != Some(DriveAction::Delete) && self.confirm_delete.take().is_some() {
+            self.status = None;
+        }
+
+        if let Some(action) = action {
+            self.action(action);
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.prompt.is_some() {
+            self.message(Message::PasteText(text.to_owned()));
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
+
+/// Formats a size in bytes for people, e.g. `1.2 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,258 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+
+use crate::{
+    api::{notes::Favorite, Error},
+    config::keymap::{FavoritesAction, Keymap},
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        widgets::feed::page::{NotePage, NotePageState},
+    },
+};
+
+use super::{clips::Clips, compose::Compose, Direction, Navigation, Screen};
+
+/// Notes we added to our favorites, newest first.
+pub struct Favorites {
+    session: Session,
+    tasks: Tasks<Message>,
+    favorites: Vec<Favorite>,
+    page: NotePageState,
+    keys: Keymap<FavoritesAction>,
+    /// Whether a request is running.
+    loading: bool,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous note, fetching more at the end.
+    Select(Direction),
+    /// Replies to the selected note.
+    Reply,
+    /// Removes the selected note from our favorites.
+    Unfavorite,
+    /// Adds the selected note to clips or removes it from them.
+    OpenClips,
+    Refresh,
+    /// Fetches favorites older than the last one.
+    LoadMore,
+    /// Receives fetched favorites.
+    /// `append` is whether they come after the current ones.
+    Loaded {
+        result: Result<Vec<Favorite>, Error>,
+        append: bool,
+    },
+    /// Receives the outcome of unfavoriting a note.
+    Unfavorited {
+        note_id: String,
+        result: Result<bool, Error>,
+    },
+    Close,
+}
+
+impl Favorites {
+    /// Creates the screen and starts fetching favorites.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.favorites);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            favorites: Vec::new(),
+            page: NotePageState::default(),
+            keys,
+            loading: false,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Select(Direction::Next) => {
+                let at_end = self
+                    .page
+                    .selected
+                    .map_or(true, |idx| idx + 1 >= self.favorites.len());
+                if at_end {
+                    self.message(Message::LoadMore);
+                } else {
+                    self.page.next();
+                }
+            }
+            Message::Select(Direction::Previous) => self.page.prev(),
+            Message::Reply => {
+                if let Some(favorite) = self.selected() {
+                    let note = &favorite.note;
+                    let compose =
+                        Compose::reply(self.session.clone(), note, &[note.user.clone()], None);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::Unfavorite => {
+                if let Some(favorite) = self.selected() {
+                    let client = self.session.client.clone();
+                    let note_id = favorite.note.id.clone();
+                    self.tasks.spawn(move || Message::Unfavorited {
+                        result: client.toggle_favorite(&note_id),
+                        note_id,
+                    });
+                }
+            }
+            Message::OpenClips => {
+                if let Some(favorite) = self.selected() {
+                    let clips = Clips::for_note(self.session.clone(), favorite.note.id.clone());
+                    self.navigation = Some(Navigation::Push(Box::new(clips)));
+                }
+            }
+            Message::Refresh => self.fetch(None),
+            Message::LoadMore => {
+                if !self.loading {
+                    if let Some(last) = self.favorites.last() {
+                        self.fetch(Some(last.id.clone()));
+                    }
+                }
+            }
+            Message::Loaded { result, append } => {
+                self.loading = false;
+                match result {
+                    Ok(favorites) => {
+                        self.status = None;
+                        if !append {
+                            self.favorites.clear();
+                        }
+                        self.favorites.extend(favorites);
+                        self.rebuild_page(append);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Unfavorited { note_id, result } => match result {
+                Ok(false) => {
+                    self.favorites
+                        .retain(|favorite| favorite.note.id != note_id);
+                    self.rebuild_page(true);
+                    self.status = Some("Removed from favorites.".into());
+                }
+                // it was already gone, so it's a favorite again.
+                Ok(true) => self.status = Some("Added back to favorites.".into()),
+                Err(err) => self.status = Some(err.to_string()),
+            },
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    fn selected(&self) -> Option<&Favorite> {
+        self.favorites.get(self.page.selected?)
+    }
+
+    /// Fetches favorites, older than `until_id` if given.
+    fn fetch(&mut self, until_id: Option<String>) {
+        let client = self.session.client.clone();
+        let append = until_id.is_some();
+
+        self.loading = true;
+        self.tasks.spawn(move || Message::Loaded {
+            result: client.favorites(until_id.as_deref()),
+            append,
+        });
+    }
+
+    /// Rebuilds the page from the favorites, keeping the selection if `keep_selected`.
+    fn rebuild_page(&mut self, keep_selected: bool) {
+        let selected = self.page.selected;
+        self.page = NotePageState::new(
+            self.session.client.store.clone(),
+            self.favorites
+                .iter()
+                .map(|favorite| favorite.note.clone())
+                .collect(),
+        );
+        if keep_selected {
+            self.page.selected =
+                selected.map(|idx| idx.min(self.favorites.len().saturating_sub(1)));
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: FavoritesAction) {
+        self.message(match action {
+            FavoritesAction::NextNote => Message::Select(Direction::Next),
+            FavoritesAction::PrevNote => Message::Select(Direction::Previous),
+            FavoritesAction::Reply => Message::Reply,
+            FavoritesAction::Unfavorite => Message::Unfavorite,
+            FavoritesAction::ClipNote => Message::OpenClips,
+            FavoritesAction::Refresh => Message::Refresh,
+            FavoritesAction::Close => Message::Close,
+        });
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading {
+            return "Loading...".into();
+        }
+        let back = self.keys.help(&[("Back", &[FavoritesAction::Close])]);
+        if self.favorites.is_empty() {
+            return format!("No favorites yet. {back}");
+        }
+
+        let help = self.keys.help(&[
+            (
+                "Move",
+                &[FavoritesAction::NextNote, FavoritesAction::PrevNote],
+            ),
+            ("Reply", &[FavoritesAction::Reply]),
+            ("Unfavorite", &[FavoritesAction::Unfavorite]),
+            ("Clips", &[FavoritesAction::ClipNote]),
+            ("Refresh", &[FavoritesAction::Refresh]),
+        ]);
+        format!("{help} / {back}")
+    }
+}
+
+impl Screen for Favorites {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let [list_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_type(BorderType::Plain)
+            .title_top("Favorites");
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+        frame.render_stateful_widget(NotePage::new(), inner, &mut self.page);
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
@@ -0,0 +1,246 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{antennas::Antenna, lists::UserList, timelines::Timeline, Error},
+    config::keymap::{FeedsAction, Keymap},
+    state::session::Session,
+    ui::{tasks::Tasks, theme::Theme},
+};
+
+use super::{timeline::TimelineScreen, Direction, Navigation, Screen};
+
+/// Lists our user lists and antennas, to open or pin as timelines.
+pub struct FeedPicker {
+    session: Session,
+    tasks: Tasks<Message>,
+    lists: Vec<UserList>,
+    antennas: Vec<Antenna>,
+    list_state: ListState,
+    keys: Keymap<FeedsAction>,
+    /// How many requests are running.
+    loading: usize,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous entry.
+    Select(Direction),
+    /// Opens the selected entry's timeline.
+    Open,
+    /// Adds the selected entry to the home screen's tabs, or removes it.
+    TogglePin,
+    Refresh,
+    LoadedLists(Result<Vec<UserList>, Error>),
+    LoadedAntennas(Result<Vec<Antenna>, Error>),
+    Close,
+}
+
+impl FeedPicker {
+    /// Creates the screen and starts fetching lists and antennas.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.feeds);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            lists: Vec::new(),
+            antennas: Vec::new(),
+            list_state: ListState::default(),
+            keys,
+            loading: 0,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Select(Direction::Next) => self.list_state.next(),
+            Message::Select(Direction::Previous) => self.list_state.previous(),
+            Message::Open => {
+                if let Some(timeline) = self.selected_timeline() {
+                    let screen = TimelineScreen::new(self.session.clone(), timeline);
+                    self.navigation = Some(Navigation::Push(Box::new(screen)));
+                }
+            }
+            Message::TogglePin => {
+                if let Some(timeline) = self.selected_timeline() {
+                    self.session
+                        .update_config(|config| config.toggle_pinned(timeline));
+                }
+            }
+            Message::Refresh => {
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedLists(client.user_lists()));
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::LoadedAntennas(client.antennas()));
+                self.loading = 2;
+            }
+            Message::LoadedLists(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(lists) => self.lists = lists,
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                self.fix_selection();
+            }
+            Message::LoadedAntennas(result) => {
+                self.loading = self.loading.saturating_sub(1);
+                match result {
+                    Ok(antennas) => self.antennas = antennas,
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                self.fix_selection();
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Every entry as a timeline, lists first.
+    fn timelines(&self) -> Vec<Timeline> {
+        let lists = self.lists.iter().map(|list| Timeline::UserList {
+            id: list.id.clone(),
+            name: list.name.clone(),
+        });
+        let antennas = self.antennas.iter().map(|antenna| Timeline::Antenna {
+            id: antenna.id.clone(),
+            name: antenna.name.clone(),
+        });
+
+        lists.chain(antennas).collect()
+    }
+
+    fn selected_timeline(&self) -> Option<Timeline> {
+        self.timelines().get(self.list_state.selected?).cloned()
+    }
+
+    /// Keeps the selection within the entries after they changed.
+    fn fix_selection(&mut self) {
+        let len = self.lists.len() + self.antennas.len();
+        let selected = match self.list_state.selected {
+            _ if len == 0 => None,
+            Some(idx) => Some(idx.min(len - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    /// Describes an entry, e.g. `List: Friends (12 members) [pinned]`.
+    fn label(&self, idx: usize, timeline: &Timeline) -> String {
+        let detail = match idx.checked_sub(self.lists.len()) {
+            None => format!(" ({} members)", self.lists[idx].user_ids.len()),
+            Some(antenna) if self.antennas[antenna].has_unread_note => " (unread)".into(),
+            Some(_) => String::new(),
+        };
+        let pinned = if self
+            .session
+            .config
+            .read()
+            .pinned_timelines
+            .contains(timeline)
+        {
+            " [pinned]"
+        } else {
+            ""
+        };
+
+        format!("{}{detail}{pinned}", timeline.title())
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: FeedsAction) {
+        self.message(match action {
+            FeedsAction::NextFeed => Message::Select(Direction::Next),
+            FeedsAction::PrevFeed => Message::Select(Direction::Previous),
+            FeedsAction::Open => Message::Open,
+            FeedsAction::TogglePin => Message::TogglePin,
+            FeedsAction::Refresh => Message::Refresh,
+            FeedsAction::Close => Message::Close,
+        });
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading > 0 {
+            return "Loading...".into();
+        }
+        if self.lists.is_empty() && self.antennas.is_empty() {
+            let back = self.keys.help(&[("Back", &[FeedsAction::Close])]);
+            return format!("No lists or antennas yet. {back}");
+        }
+
+        self.keys.help(&[
+            ("Move", &[FeedsAction::NextFeed, FeedsAction::PrevFeed]),
+            ("Open", &[FeedsAction::Open]),
+            ("Pin", &[FeedsAction::TogglePin]),
+            ("Refresh", &[FeedsAction::Refresh]),
+            ("Back", &[FeedsAction::Close]),
+        ])
+    }
+}
+
+impl Screen for FeedPicker {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let [list_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_type(BorderType::Plain)
+            .title_top("Lists and antennas");
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+
+        let labels: Vec<String> = self
+            .timelines()
+            .iter()
+            .enumerate()
+            .map(|(idx, timeline)| self.label(idx, timeline))
+            .collect();
+        let count = labels.len();
+        let builder = ListBuilder::new(move |ctx| {
+            let style = if ctx.is_selected {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            (Line::styled(labels[ctx.index].clone(), style), 1)
+        });
+        frame.render_stateful_widget(ListView::new(builder, count), inner, &mut self.list_state);
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
@@ -0,0 +1,181 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+
+use crate::{
+    api::timelines::{TagQuery, Timeline},
+    config::keymap::{HashtagAction, Keymap},
+    state::session::Session,
+    ui::{theme::Theme, widgets::text_input::TextInput},
+};
+
+use super::{timeline::TimelineScreen, Direction, Navigation, Screen};
+
+/// Asks which hashtag timeline to open, suggesting the tags of a note.
+pub struct HashtagPrompt {
+    session: Session,
+    /// A tag, or an expression like `#foo #bar | #baz`.
+    input: TextInput,
+    suggestions: Vec<String>,
+    selected: Option<usize>,
+    keys: Keymap<HashtagAction>,
+    /// Why the input couldn't be opened, if it couldn't.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Edits the input, or cancels on Backspace if it's empty.
+    Edit(KeyEvent),
+    /// Inserts pasted text.
+    Paste(String),
+    /// Picks the next or previous suggestion.
+    Select(Direction),
+    /// Opens the timeline for the input.
+    Open,
+}
+
+impl HashtagPrompt {
+    pub fn new(session: Session, suggestions: Vec<String>) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.hashtag);
+        let mut prompt = Self {
+            session,
+            input: TextInput::default(),
+            suggestions,
+            selected: None,
+            keys,
+            status: None,
+            navigation: None,
+        };
+        if !prompt.suggestions.is_empty() {
+            prompt.message(Message::Select(Direction::Next));
+        }
+        prompt
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Edit(key) => {
+                if key.code == KeyCode::Backspace && self.input.is_empty() {
+                    self.navigation = Some(Navigation::Back);
+                } else {
+                    self.input.handle_key(key);
+                }
+            }
+            Message::Paste(text) => self.input.insert(&text),
+            Message::Select(direction) => {
+                if self.suggestions.is_empty() {
+                    return;
+                }
+                let last = self.suggestions.len() - 1;
+                let idx = match (direction, self.selected) {
+                    (Direction::Next, Some(idx)) => (idx + 1).min(last),
+                    (Direction::Previous, Some(idx)) => idx.saturating_sub(1),
+                    (_, None) => 0,
+                };
+                self.selected = Some(idx);
+                self.input.set_text(format!("#{}", self.suggestions[idx]));
+            }
+            Message::Open => match TagQuery::parse(self.input.text()) {
+                Some(query) => {
+                    let screen =
+                        TimelineScreen::new(self.session.clone(), Timeline::Hashtag { query });
+                    self.navigation = Some(Navigation::Replace(Box::new(screen)));
+                }
+                None => self.status = Some("Type a hashtag, like #misskey.".into()),
+            },
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: HashtagAction) {
+        self.message(match action {
+            HashtagAction::NextSuggestion => Message::Select(Direction::Next),
+            HashtagAction::PrevSuggestion => Message::Select(Direction::Previous),
+            HashtagAction::Open => Message::Open,
+        });
+    }
+
+    fn help(&self) -> String {
+        let help = self.keys.help(&[
+            ("Open", &[HashtagAction::Open]),
+            (
+                "Suggestions",
+                &[HashtagAction::PrevSuggestion, HashtagAction::NextSuggestion],
+            ),
+        ]);
+        format!("{help} / Cancel (Backspace on empty)")
+    }
+}
+
+impl Screen for HashtagPrompt {
+    fn view(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let screen_frame = Block::new()
+            .borders(Borders::TOP | Borders::BOTTOM)
+            .border_type(BorderType::Plain)
+            .title_top("Hashtag timeline")
+            .title_bottom(match &self.status {
+                Some(status) => status.clone(),
+                None => self.help(),
+            });
+        frame.render_widget(screen_frame, area);
+
+        let [input_area, hint_area, suggestions_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        }));
+
+        let [marker_area, input_area] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(input_area);
+        frame.render_widget(Line::from("> "), marker_area);
+        self.input.render(frame, input_area, true);
+        frame.render_widget(
+            Line::from("Combine tags with spaces (all of them) and | (any group)."),
+            hint_area,
+        );
+
+        for (idx, (tag, row)) in self
+            .suggestions
+            .iter()
+            .zip(suggestions_area.rows())
+            .enumerate()
+        {
+            let style = if self.selected == Some(idx) {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            frame.render_widget(Line::styled(format!("#{tag}"), style), row);
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        match (self.keys.action(key), key.code) {
+            (Some(action), _) => self.action(action),
+            (None, _) => self.message(Message::Edit(key)),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.message(Message::Paste(text.to_owned()));
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
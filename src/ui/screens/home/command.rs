@@ -0,0 +1,132 @@
+/// A command typed on the home screen's command line, after `:`.
+pub enum Command {
+    /// Shows a timeline: one of the tabs by its name, or hashtags like `#foo #bar`.
+    Timeline(String),
+    /// Searches a user's notes, e.g. `foo@misskey.io`.
+    User(String),
+    /// Searches notes, or opens the search screen without a query.
+    Search(String),
+    /// Posts a public note, or opens the composer without text.
+    Post(String),
+    Quit,
+}
+
+/// The commands' names, each with its short form.
+const NAMES: [(&str, &str); 5] = [
+    ("timeline", "tl"),
+    ("user", "u"),
+    ("search", "s"),
+    ("post", "p"),
+    ("quit", "q"),
+];
+
+impl Command {
+    /// Reads a command line like `tl local` or `user @foo@bar`.
+    /// Returns the message to show if it isn't a valid command.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+
+        Ok(match name {
+            "tl" | "timeline" if args.is_empty() => {
+                return Err("Usage: :tl home|local|social|global|<tab>|#tag".into());
+            }
+            "tl" | "timeline" => Self::Timeline(args.to_owned()),
+            "u" | "user" if args.is_empty() => return Err("Usage: :user @name@host".into()),
+            "u" | "user" => Self::User(args.trim_start_matches('@').to_owned()),
+            "s" | "search" => Self::Search(args.to_owned()),
+            "p" | "post" => Self::Post(args.to_owned()),
+            "q" | "quit" => Self::Quit,
+            _ => return Err(format!("Unknown command: {name}")),
+        })
+    }
+}
+
+/// Lists the full lines `line` could be completed to: command names,
+/// then `timelines` after `:tl` and `users` after `:user`.
+pub fn complete(line: &str, timelines: &[String], users: &[String]) -> Vec<String> {
+    let Some((name, arg)) = line.split_once(' ') else {
+        return NAMES
+            .iter()
+            .filter(|(long, short)| long.starts_with(line) || *short == line)
+            .map(|(long, _)| match *long {
+                "quit" => long.to_string(),
+                long => format!("{long} "),
+            })
+            .collect();
+    };
+
+    let arg = arg.trim_start().to_lowercase();
+    let (candidates, prefix) = match name {
+        "tl" | "timeline" => (timelines, ""),
+        "u" | "user" => (users, "@"),
+        _ => return Vec::new(),
+    };
+    let arg = arg.trim_start_matches(prefix);
+    candidates
+        .iter()
+        .filter(|candidate| candidate.to_lowercase().starts_with(arg))
+        .map(|candidate| format!("{name} {prefix}{candidate}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(Command::parse("tl local"), Ok(Command::Timeline(tl)) if tl == "local"));
+        assert!(
+            matches!(Command::parse("  timeline  #foo #bar "), Ok(Command::Timeline(tl)) if tl == "#foo #bar")
+        );
+        assert!(
+            matches!(Command::parse("u @foo@misskey.io"), Ok(Command::User(user)) if user == "foo@misskey.io")
+        );
+        assert!(matches!(Command::parse("s cats"), Ok(Command::Search(query)) if query == "cats"));
+        assert!(matches!(Command::parse("search"), Ok(Command::Search(query)) if query.is_empty()));
+        assert!(
+            matches!(Command::parse("p hello world"), Ok(Command::Post(text)) if text == "hello world")
+        );
+        assert!(matches!(Command::parse("q"), Ok(Command::Quit)));
+    }
+
+    fn error(line: &str) -> String {
+        Command::parse(line)
+            .err()
+            .expect("the line should be rejected")
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        assert!(error("tl").starts_with("Usage: :tl"));
+        assert!(error("user  ").starts_with("Usage: :user"));
+        assert_eq!(error("frobnicate now"), "Unknown command: frobnicate");
+        assert_eq!(error(""), "Unknown command: ");
+    }
+
+    #[test]
+    fn completes_names() {
+        assert_eq!(complete("t", &[], &[]), ["timeline "]);
+        assert_eq!(complete("q", &[], &[]), ["quit"]);
+        // short forms complete to their long one.
+        assert_eq!(complete("u", &[], &[]), ["user "]);
+        assert_eq!(complete("", &[], &[]).len(), NAMES.len());
+        assert!(complete("x", &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn completes_arguments() {
+        let timelines = ["home".to_owned(), "Local".to_owned(), "#rust".to_owned()];
+        let users = ["foo@misskey.io".to_owned(), "bar".to_owned()];
+        assert_eq!(complete("tl lo", &timelines, &users), ["tl Local"]);
+        assert_eq!(
+            complete("timeline #", &timelines, &users),
+            ["timeline #rust"]
+        );
+        assert_eq!(complete("u @fo", &timelines, &users), ["u @foo@misskey.io"]);
+        assert_eq!(complete("user b", &timelines, &users), ["user @bar"]);
+        assert!(complete("search fo", &timelines, &users).is_empty());
+    }
+}
@@ -0,0 +1,713 @@
+mod command;
+
+use std::{mem, sync::Arc};
+
+use chrono::Utc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Layout, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Clear,
+    Frame,
+};
+
+use crate::{
+    api::{
+        models,
+        notes::NewNote,
+        timelines::{TagQuery, Timeline},
+        Error,
+    },
+    config::{
+        keymap::{HomeAction, Keymap},
+        Config,
+    },
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        theme::Theme,
+        widgets::{
+            command_line::CommandLine,
+            feed::{
+                page::{NotePage, NotePageState},
+                NoteFeed, NoteFeedState,
+            },
+        },
+    },
+};
+
+use super::{
+    channels::ChannelBrowser,
+    clips::Clips,
+    compose::Compose,
+    deck::Deck,
+    drafts::Drafts,
+    drive::Drive,
+    favorites::Favorites,
+    feeds::FeedPicker,
+    hashtag::HashtagPrompt,
+    mentions::Mentions,
+    notifications::Notifications,
+    search::Search,
+    timeline::{TimelineFeed, TimelineScreen},
+    Direction, Navigation, Screen,
+};
+use command::Command;
+
+/// The largest count that can prefix a key, as repeating more is never useful.
+const MAX_COUNT: usize = 999;
+
+#[derive(Default)]
+pub struct Home {
+    /// The keys bound to the screen's actions.
+    keys: Keymap<HomeAction>,
+    /// The count typed before a key, like the `5` of `5j`.
+    count: Option<usize>,
+    command_line: CommandLine,
+    tasks: Tasks<Message>,
+    /// The outcome of the last command, shown in the status row until the next key.
+    status: Option<String>,
+    /// The feed shown when signed out.
+    pub feed_state: NoteFeedState,
+    /// The session of the signed in account, if any.
+    pub session: Option<Session>,
+    /// The main timelines, then pinned ones. Empty when signed out.
+    pub tabs: Vec<TimelineFeed>,
+    pub selected_tab: usize,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous note in the current page.
+    SetNote(Direction),
+    /// Goes to the next of previous page.
+    SetPage(Direction),
+    /// Goes to the next or previous timeline tab.
+    SetTab(Direction),
+    /// Goes to the tab with this number, counting from 1.
+    GoToTab(usize),
+    /// Selects the first note.
+    JumpToStart,
+    /// Selects the last note.
+    JumpToEnd,
+    /// Selects the note clicked, and opens what was clicked in it.
+    Click(Position),
+    /// Fetches the current timeline's newest notes again.
+    Refresh,
+    /// Removes the current tab, if it's a pinned one.
+    Unpin,
+    /// Replies to the selected note.
+    Reply,
+    /// Quotes the selected note in a new one.
+    Quote,
+    /// Opens a hashtag timeline, suggesting the selected note's tags.
+    OpenHashtags,
+    /// Opens the notifications screen.
+    OpenNotifications,
+    /// Opens the mentions and direct notes screen.
+    OpenMentions,
+    /// Opens the deck of columns.
+    OpenDeck,
+    /// Opens the composer for a new note, in the current tab's channel if it is one.
+    Compose,
+    /// Opens the search screen.
+    OpenSearch,
+    /// Opens the list of user lists and antennas.
+    OpenFeeds,
+    /// Opens the channel browser.
+    OpenChannels,
+    /// Favorites the selected note, or unfavorites it.
+    ToggleFavorite,
+    /// Adds the selected note to clips or removes it from them.
+    ClipNote,
+    /// Opens the list of favorite notes.
+    OpenFavorites,
+    /// Opens the list of clips.
+    OpenClips,
+    /// Opens the Drive.
+    OpenDrive,
+    /// Opens the list of unposted drafts.
+    OpenDrafts,
+    /// Opens the command line in the status row.
+    OpenCommandLine,
+    /// Runs a line typed in the command line.
+    RunCommand(String),
+    /// Quits keytan, asking first if drafts weren't posted.
+    Quit,
+    Posted(Result<Arc<models::Note>, Error>),
+    /// Receives the signed in user, to remember its id.
+    LoadedMe(Result<models::User, Error>),
+}
+
+impl Home {
+    /// Creates the home screen of a signed in account, with its main and pinned timelines.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.home);
+        let status = session.drafts.write().problem.take();
+        let user_known = session.user_id().is_some();
+        let client = session.client.clone();
+        let mut home = Self {
+            keys,
+            status,
+            session: Some(session),
+            ..Self::default()
+        };
+        home.sync_pinned();
+        // drafts are kept per user, so the id is looked up once and stored with the account.
+        if !user_known {
+            home.tasks.spawn(move || Message::LoadedMe(client.me()));
+        }
+        home
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SetNote(Direction::Next) => match self.tabs.get_mut(self.selected_tab) {
+                Some(tab) => tab.select_next(),
+                None => self.feed_state.select_next_note(),
+            },
+            Message::SetNote(Direction::Previous) => match self.tabs.get_mut(self.selected_tab) {
+                Some(tab) => tab.select_prev(),
+                None => self.feed_state.select_prev_note(),
+            },
+            Message::SetPage(Direction::Next) => match self.tabs.get_mut(self.selected_tab) {
+                Some(tab) => tab.next_page(),
+                None => self.feed_state.select_next_page(),
+            },
+            Message::SetPage(Direction::Previous) => self.feed_state_mut().select_prev_page(),
+            Message::SetTab(direction) => {
+                if !self.tabs.is_empty() {
+                    let len = self.tabs.len();
+                    self.selected_tab = match direction {
+                        Direction::Next => (self.selected_tab + 1) % len,
+                        Direction::Previous => (self.selected_tab + len - 1) % len,
+                    };
+                }
+            }
+            Message::GoToTab(number) => match number.checked_sub(1) {
+                Some(idx) if idx < self.tabs.len() => self.selected_tab = idx,
+                _ => self.status = Some(format!("No tab {number}")),
+            },
+            Message::JumpToStart => self.feed_state_mut().select_first_note(),
+            Message::JumpToEnd => self.feed_state_mut().select_last_note(),
+            Message::Click(position) => {
+                match (&self.session, self.tabs.get_mut(self.selected_tab)) {
+                    (Some(session), Some(tab)) => self.navigation = tab.click(session, position),
+                    _ => {
+                        self.feed_state.click(position);
+                    }
+                }
+            }
+            Message::Refresh => {
+                if let Some(tab) = self.tabs.get_mut(self.selected_tab) {
+                    tab.refresh();
+                }
+            }
+            Message::Unpin => {
+                if let (Some(session), Some(tab)) =
+                    (&self.session, self.tabs.get(self.selected_tab))
+                {
+                    let timeline = tab.timeline.clone();
+                    session.update_config(|config| {
+                        config.pinned_timelines.retain(|pinned| *pinned != timeline);
+                    });
+                }
+            }
+            Message::Reply => {
+                let note = self
+                    .tabs
+                    .get(self.selected_tab)
+                    .and_then(TimelineFeed::selected_note);
+                if let (Some(session), Some(note)) = (&self.session, note) {
+                    let compose = Compose::reply(session.clone(), note, &[note.user.clone()], None);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::Quote => {
+                let note = self
+                    .tabs
+                    .get(self.selected_tab)
+                    .and_then(TimelineFeed::selected_note);
+                if let (Some(session), Some(note)) = (&self.session, note) {
+                    let compose = Compose::quote(session.clone(), note);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::OpenHashtags => {
+                if let Some(session) = &self.session {
+                    let tags = self
+                        .tabs
+                        .get(self.selected_tab)
+                        .and_then(TimelineFeed::selected_note)
+                        .map(|note| note.tags.clone())
+                        .unwrap_or_default();
+                    let prompt = HashtagPrompt::new(session.clone(), tags);
+                    self.navigation = Some(Navigation::Push(Box::new(prompt)));
+                }
+            }
+            Message::OpenNotifications => {
+                if let Some(session) = &self.session {
+                    self.navigation = Some(Navigation::Push(Box::new(Notifications::new(
+                        session.clone(),
+                    ))));
+                }
+            }
+            Message::OpenMentions => {
+                if let Some(session) = &self.session {
+                    self.navigation =
+                        Some(Navigation::Push(Box::new(Mentions::new(session.clone()))));
+                }
+            }
+            Message::OpenDeck => {
+                if let Some(session) = &self.session {
+                    self.navigation = Some(Navigation::Push(Box::new(Deck::new(session.clone()))));
+                }
+            }
+            Message::Compose => {
+                if let Some(session) = &self.session {
+                    let compose = match self.tabs.get(self.selected_tab) {
+                        Some(tab) => Compose::for_timeline(session.clone(), &tab.timeline),
+                        None => Compose::new(session.clone()),
+                    };
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::OpenSearch => {
+                if let Some(session) = &self.session {
+                    self.navigation =
+                        Some(Navigation::Push(Box::new(Search::new(session.clone()))));
+                }
+            }
+            Message::OpenFeeds => {
+                if let Some(session) = &self.session {
+                    self.navigation =
+                        Some(Navigation::Push(Box::new(FeedPicker::new(session.clone()))));
+                }
+            }
+            Message::OpenChannels => {
+                if let Some(session) = &self.session {
+                    let browser = ChannelBrowser::new(session.clone());
+                    self.navigation = Some(Navigation::Push(Box::new(browser)));
+                }
+            }
+            Message::ToggleFavorite => {
+                if let Some(tab) = self.tabs.get_mut(self.selected_tab) {
+                    tab.toggle_favorite();
+                }
+            }
+            Message::ClipNote => {
+                let note = self
+                    .tabs
+                    .get(self.selected_tab)
+                    .and_then(TimelineFeed::selected_note);
+                if let (Some(session), Some(note)) = (&self.session, note) {
+                    let clips = Clips::for_note(session.clone(), note.id.clone());
+                    self.navigation = Some(Navigation::Push(Box::new(clips)));
+                }
+            }
+            Message::OpenFavorites => {
+                if let Some(session) = &self.session {
+                    self.navigation =
+                        Some(Navigation::Push(Box::new(Favorites::new(session.clone()))));
+                }
+            }
+            Message::OpenClips => {
+                if let Some(session) = &self.session {
+                    self.navigation = Some(Navigation::Push(Box::new(Clips::new(session.clone()))));
+                }
+            }
+            Message::OpenDrive => {
+                if let Some(session) = &self.session {
+                    self.navigation = Some(Navigation::Push(Box::new(Drive::new(session.clone()))));
+                }
+            }
+            Message::OpenDrafts => {
+                if let Some(session) = &self.session {
+                    self.navigation =
+                        Some(Navigation::Push(Box::new(Drafts::new(session.clone()))));
+                }
+            }
+            Message::OpenCommandLine => self.command_line.open(),
+            Message::Quit => self.navigation = Some(Navigation::Quit),
+            Message::RunCommand(line) => match Command::parse(&line) {
+                Ok(command) => self.run(command),
+                Err(err) => self.status = Some(err),
+            },
+            Message::Posted(result) => {
+                self.status = Some(match result {
+                    Ok(_) => "Posted.".into(),
+                    Err(err) => err.to_string(),
+                });
+            }
+            Message::LoadedMe(result) => {
+                // without it, drafts are saved for the instance only until the next launch.
+                if let (Some(session), Ok(me)) = (&self.session, result) {
+                    session.update_config(|config| {
+                        if let Some(account) = &mut config.account {
+                            account.user_id = Some(me.id);
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    fn run(&mut self, command: Command) {
+        if matches!(command, Command::Quit) {
+            self.navigation = Some(Navigation::Quit);
+            return;
+        }
+        let Some(session) = &self.session else {
+            self.status = Some("Sign in to use this command.".into());
+            return;
+        };
+
+        match command {
+            Command::Timeline(name) if name.starts_with('#') => match TagQuery::parse(&name) {
+                Some(query) => {
+                    let screen = TimelineScreen::new(session.clone(), Timeline::Hashtag { query });
+                    self.navigation = Some(Navigation::Push(Box::new(screen)));
+                }
+                None => self.status = Some(format!("No hashtag in {name}")),
+            },
+            Command::Timeline(name) => {
+                let name = name.to_lowercase();
+                match self
+                    .tabs
+                    .iter()
+                    .position(|tab| tab.timeline.title().to_lowercase().starts_with(&name))
+                {
+                    Some(idx) => self.selected_tab = idx,
+                    None => self.status = Some(format!("No timeline named {name}")),
+                }
+            }
+            Command::User(acct) => {
+                let search = Search::with_query(session.clone(), &format!("from:@{acct}"));
+                self.navigation = Some(Navigation::Push(Box::new(search)));
+            }
+            Command::Search(query) if query.is_empty() => {
+                self.navigation = Some(Navigation::Push(Box::new(Search::new(session.clone()))));
+            }
+            Command::Search(query) => {
+                let search = Search::with_query(session.clone(), &query);
+                self.navigation = Some(Navigation::Push(Box::new(search)));
+            }
+            Command::Post(text) if text.is_empty() => self.message(Message::Compose),
+            Command::Post(text) => {
+                let client = session.client.clone();
+                let note = NewNote {
+                    text: Some(text),
+                    ..NewNote::default()
+                };
+                self.status = Some("Posting...".into());
+                self.tasks
+                    .spawn(move || Message::Posted(client.create_note(&note)));
+            }
+            Command::Quit => (),
+        }
+    }
+
+    /// Lists the full commands the command line's `line` could become.
+    fn complete(&self, line: &str) -> Vec<String> {
+        let timelines: Vec<String> = self
+            .tabs
+            .iter()
+            .map(|tab| tab.timeline.title().to_lowercase())
+            .collect();
+        let mut users: Vec<String> = self
+            .tabs
+            .get(self.selected_tab)
+            .into_iter()
+            .flat_map(TimelineFeed::notes)
+            .map(|note| note.user.acct())
+            .collect();
+        users.sort();
+        users.dedup();
+        command::complete(line, &timelines, &users)
+    }
+
+    /// Whether `action` can be repeated by a count, like `5j`.
+    fn repeats(action: HomeAction) -> bool {
+        matches!(
+            action,
+            HomeAction::NextNote
+                | HomeAction::PrevNote
+                | HomeAction::NextPage
+                | HomeAction::PrevPage
+                | HomeAction::NextTab
+                | HomeAction::PrevTab
+        )
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: HomeAction) {
+        self.message(match action {
+            HomeAction::NextNote => Message::SetNote(Direction::Next),
+            HomeAction::PrevNote => Message::SetNote(Direction::Previous),
+            HomeAction::NextPage => Message::SetPage(Direction::Next),
+            HomeAction::PrevPage => Message::SetPage(Direction::Previous),
+            HomeAction::NextTab => Message::SetTab(Direction::Next),
+            HomeAction::PrevTab => Message::SetTab(Direction::Previous),
+            HomeAction::GoToTab => Message::SetTab(Direction::Next),
+            HomeAction::JumpToStart => Message::JumpToStart,
+            HomeAction::JumpToEnd => Message::JumpToEnd,
+            HomeAction::Refresh => Message::Refresh,
+            HomeAction::Unpin => Message::Unpin,
+            HomeAction::Reply => Message::Reply,
+            HomeAction::Quote => Message::Quote,
+            HomeAction::OpenHashtags => Message::OpenHashtags,
+            HomeAction::OpenNotifications => Message::OpenNotifications,
+            HomeAction::OpenMentions => Message::OpenMentions,
+            HomeAction::OpenDeck => Message::OpenDeck,
+            HomeAction::Compose => Message::Compose,
+            HomeAction::OpenSearch => Message::OpenSearch,
+            HomeAction::OpenFeeds => Message::OpenFeeds,
+            HomeAction::OpenChannels => Message::OpenChannels,
+            HomeAction::ToggleFavorite => Message::ToggleFavorite,
+            HomeAction::ClipNote => Message::ClipNote,
+            HomeAction::OpenFavorites => Message::OpenFavorites,
+            HomeAction::OpenClips => Message::OpenClips,
+            HomeAction::OpenDrive => Message::OpenDrive,
+            HomeAction::OpenDrafts => Message::OpenDrafts,
+            HomeAction::CommandLine => Message::OpenCommandLine,
+            HomeAction::Quit => Message::Quit,
+        });
+    }
+
+    /// Returns the state of the feed being shown.
+    fn feed_state_mut(&mut self) -> &mut NoteFeedState {
+        match self.tabs.get_mut(self.selected_tab) {
+            Some(tab) => &mut tab.feed_state,
+            None => &mut self.feed_state,
+        }
+    }
+
+    /// Makes the tabs match the main timelines and the pinned ones in the config,
+    /// keeping the feeds that are already open.
+    fn sync_pinned(&mut self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let wanted: Vec<Timeline> = Timeline::MAIN
+            .iter()
+            .chain(&session.config.read().pinned_timelines)
+            .cloned()
+            .collect();
+        if wanted.len() == self.tabs.len()
+            && wanted
+                .iter()
+                .zip(&self.tabs)
+                .all(|(t, tab)| *t == tab.timeline)
+        {
+            return;
+        }
+
+        let current = self
+            .tabs
+            .get(self.selected_tab)
+            .map(|tab| tab.timeline.clone());
+        let mut open: Vec<TimelineFeed> = self.tabs.drain(..).collect();
+        self.tabs = wanted
+            .into_iter()
+            .map(
+                |timeline| match open.iter().position(|tab| tab.timeline == timeline) {
+                    Some(idx) => open.swap_remove(idx),
+                    None => TimelineFeed::new(session.client.clone(), timeline),
+                },
+            )
+            .collect();
+        self.selected_tab = current
+            .and_then(|current| self.tabs.iter().position(|tab| tab.timeline == current))
+            .unwrap_or(0)
+            .min(self.tabs.len().saturating_sub(1));
+    }
+
+    /// Draws the command line over the status row when it's open,
+    /// or the last command's outcome and the count being typed.
+    fn render_status(&mut self, frame: &mut Frame, area: Rect) {
+        if self.command_line.is_open() {
+            frame.render_widget(Clear, area);
+            frame.render_widget(Theme::current().background(), area);
+            self.command_line.render(frame, area);
+            return;
+        }
+        if let Some(status) = &self.status {
+            frame.render_widget(Clear, area);
+            frame.render_widget(Theme::current().background(), area);
+            frame.render_widget(Line::from(status.clone()), area);
+        }
+        if let Some(count) = self.count {
+            frame.render_widget(Line::from(count.to_string()).right_aligned(), area);
+        }
+    }
+
+    /// Lists the tabs, leaving out the first ones if the selected tab wouldn't fit in `width`.
+    fn tab_bar(&self, width: u16) -> Line<'static> {
+        let tabs: Vec<[Span<'static>; 2]> = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(idx, tab)| {
+                let style = if idx == self.selected_tab {
+                    Theme::current().selection
+                } else {
+                    Style::new()
+                };
+                [
+                    Span::styled(format!(" {} {} ", idx + 1, tab.timeline.title()), style),
+                    Span::raw(" "),
+                ]
+            })
+            .collect();
+
+        let mut first = 0;
+        while first < self.selected_tab
+            && tabs[first..=self.selected_tab]
+                .iter()
+                .flatten()
+                .map(Span::width)
+                .sum::<usize>()
+                > width as usize
+        {
+            first += 1;
+        }
+        Line::from(tabs[first..].concat())
+    }
+}
+
+impl Home {
+    /// Creates the home screen shown when signed out, with the keys bound in `config`.
+    pub fn make_dummy(config: &Config) -> Self {
+        let short = "Lorem ipsum dolor sit amet, qui minim labore adipisicing minim sint cillum sint consectetur cupidatat.";
+        let long = "Lorem ipsum dolor sit amet, officia excepteur ex fugiat reprehenderit enim labore culpa sint ad nisi Lorem pariatur mollit ex esse exercitation amet. Nisi anim cupidatat excepteur officia. Reprehenderit nostrud nostrud ipsum Lorem est aliquip amet voluptate voluptate dolor minim nulla est proident. Nostrud officia pariatur ut officia. Sit irure elit esse ea nulla sunt ex occaecat reprehenderit commodo officia dolor Lorem duis laboris cupidatat officia voluptate. Culpa proident adipisicing id nulla nisi laboris ex in Lorem sunt duis officia eiusmod. Aliqua reprehenderit commodo ex non excepteur duis sunt velit enim. Voluptate laboris sint cupidatat ullamco ut ea consectetur et est culpa et culpa duis.";
+        let page = |texts: &[&str]| {
+            let notes = texts.iter().map(|text| dummy_note(text)).collect();
+            (NotePage::new(), NotePageState::new(Arc::default(), notes))
+        };
+        Self {
+            feed_state: NoteFeedState {
+                selected_page_idx: Some(0),
+                pages: vec![
+                    page(&[
+                        short, short, short, short, short, short, long, short, long, short,
+                    ]),
+                    page(&[short, short, long, short]),
+                ],
+            },
+            keys: Keymap::new(&config.keys.home),
+            ..Self::default()
+        }
+    }
+}
+
+/// A note by a made up user, for the feed shown when signed out.
+fn dummy_note(text: &str) -> Arc<models::Note> {
+    let user = models::User {
+        name: Some("John Misskey".into()),
+        username: "johnmisskey".into(),
+        host: Some("misskey.io".into()),
+        ..models::User::default()
+    };
+    Arc::new(models::Note {
+        created_at: Utc::now(),
+        user,
+        text: Some(text.into()),
+        ..models::Note::default()
+    })
+}
+
+impl Screen for Home {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+        let area = frame.area();
+
+        let feed_area = if self.tabs.is_empty() {
+            let feed = NoteFeed::new();
+            frame.render_stateful_widget(feed.clone(), area, &mut self.feed_state);
+            area
+        } else {
+            self.sync_pinned();
+            let [tabs_area, feed_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+            frame.render_widget(self.tab_bar(tabs_area.width), tabs_area);
+
+            let tab = &mut self.tabs[self.selected_tab];
+            tab.start();
+            tab.update();
+            tab.render(frame, feed_area);
+            feed_area
+        };
+
+        let [_, _, status_area] = NoteFeed::areas(feed_area);
+        self.render_status(frame, status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        self.status = None;
+        if self.command_line.is_open() {
+            let mut command_line = mem::take(&mut self.command_line);
+            let line = command_line.handle_key(key, |line| self.complete(line));
+            self.command_line = command_line;
+            if let Some(line) = line {
+                self.message(Message::RunCommand(line));
+            }
+            return;
+        }
+
+        if let (KeyCode::Char(digit @ '0'..='9'), KeyModifiers::NONE) = (key.code, key.modifiers) {
+            // a count can't start with 0, which is left to bindings.
+            if (digit != '0' || self.count.is_some()) && !self.keys.is_pending() {
+                let count = self.count.unwrap_or(0) * 10 + digit.to_digit(10).unwrap() as usize;
+                self.count = Some(count.min(MAX_COUNT));
+                return;
+            }
+        }
+
+        let Some(action) = self.keys.action(key) else {
+            if !self.keys.is_pending() {
+                self.count = None;
+            }
+            return;
+        };
+        if let (HomeAction::GoToTab, Some(number)) = (action, self.count) {
+            // `3gt` goes to the third tab, while `gt` goes to the next one.
+            self.count = None;
+            self.message(Message::GoToTab(number));
+            return;
+        }
+        let count = match self.count.take() {
+            Some(count) if Self::repeats(action) => count,
+            _ => 1,
+        };
+        for _ in 0..count {
+            self.action(action);
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.command_line.insert(text);
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::ScrollDown => self.message(Message::SetNote(Direction::Next)),
+            MouseEventKind::ScrollUp => self.message(Message::SetNote(Direction::Previous)),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.message(Message::Click(Position::new(event.column, event.row)));
+            }
+            _ => (),
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
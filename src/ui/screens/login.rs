@@ -1,19 +1,34 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Margin, Rect},
+    layout::{Margin, Position, Rect},
     text::Text,
     widgets::{Block, BorderType, Borders},
     Frame,
 };
 
-use super::Screen;
+use crate::{
+    config::{
+        keymap::{Keymap, LoginAction},
+        Config,
+    },
+    ui::{layout, widgets::text_input::TextInput},
+};
+
+use super::{Navigation, Screen};
+
+/// The widest the form gets on large terminals.
+const FORM_WIDTH: u16 = 60;
 
-#[derive(Default)]
 pub struct Login {
-    instance: String,
-    username: String,
-    password: String,
+    instance: TextInput,
+    username: TextInput,
+    password: TextInput,
     selected: Field,
+    /// The keys bound to the screen's actions. Other keys edit the selected field.
+    keys: Keymap<LoginAction>,
+    /// Where each field and its label were last drawn, to focus the one clicked.
+    field_areas: Vec<(Field, Rect)>,
+    navigation: Option<Navigation>,
 }
 
 pub enum Message {
@@ -21,124 +36,159 @@ pub enum Message {
     NextField,
     /// Selects the previous field.
     PrevField,
-    /// Deletes one character in the selected field.
-    Delete,
+    /// Selects the field clicked at a position, if any.
+    Click(Position),
+    /// Edits the selected field.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the selected field.
+    Paste(String),
+    Quit,
+}
+
+impl Default for Login {
+    fn default() -> Self {
+        Self {
+            instance: TextInput::default(),
+            username: TextInput::default(),
+            password: TextInput::masked(),
+            selected: Field::default(),
+            keys: Keymap::default(),
+            field_areas: Vec::new(),
+            navigation: None,
+        }
+    }
 }
 
 impl Login {
+    /// Creates the login screen, with the keys bound in `config`.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            keys: Keymap::new(&config.keys.login),
+            ..Self::default()
+        }
+    }
+
     fn message(&mut self, message: Message) {
         match message {
             Message::NextField => self.selected = self.selected.next(),
             Message::PrevField => self.selected = self.selected.prev(),
-            Message::Delete => self.delete_char(),
+            Message::Click(position) => {
+                if let Some((field, _)) = self
+                    .field_areas
+                    .iter()
+                    .find(|(_, area)| area.contains(position))
+                {
+                    self.selected = *field;
+                }
+            }
+            Message::Edit(key) => {
+                self.selected_input().handle_key(key);
+            }
+            Message::Paste(text) => self.selected_input().insert(&text),
+            Message::Quit => self.navigation = Some(Navigation::Quit),
         }
     }
-    /// Deletes the last character of the currently selected field.
-    /// Does nothing if the field is empty.
-    pub fn delete_char(&mut self) {
-        let text = match self.selected {
+
+    fn selected_input(&mut self) -> &mut TextInput {
+        match self.selected {
             Field::Instance => &mut self.instance,
             Field::Username => &mut self.username,
             Field::Password => &mut self.password,
-        };
-
-        if !text.is_empty() {
-            let mut chars = text.chars();
-            chars.next_back();
-            let text: String = chars.collect();
-
-            match self.selected {
-                Field::Instance => self.instance = text,
-                Field::Username => self.username = text,
-                Field::Password => self.password = text,
-            }
         }
     }
 
-    fn draw_form(&self, frame: &mut Frame) {
+    fn draw_form(&mut self, frame: &mut Frame) {
         let area = frame.area();
 
-        let form_rect = area.inner(Margin {
-            horizontal: area.width / 4,
-            vertical: area.height / 3,
-        });
+        // inside the screen's borders: a title row, then a label and an input per field,
+        // with a blank row between fields.
+        let form_rect = layout::centered(
+            area.inner(Margin {
+                horizontal: 2,
+                vertical: 1,
+            }),
+            FORM_WIDTH,
+            9,
+        );
         let form_frame = Block::new()
             .borders(Borders::TOP)
             .border_type(BorderType::Plain)
             .title_top("Login");
+        frame.render_widget(form_frame, form_rect);
 
+        self.field_areas.clear();
         let mut line = form_rect.y + 1;
-
-        let instance_label_rect = Rect::new(form_rect.x, line, form_rect.width, 1);
-        let instance_label_text = Text::from(if self.selected == Field::Instance {
-            "> Instance"
-        } else {
-            "Instance"
-        });
-        let instance_text_rect = Rect::new(form_rect.x, line + 1, form_rect.width, 1);
-        let instance_text = Text::from(self.instance.clone());
-        line += 3;
-        let username_label_rect = Rect::new(form_rect.x, line, form_rect.width, 1);
-        let username_label_text = Text::from(if self.selected == Field::Username {
-            "> Username"
-        } else {
-            "Username"
-        });
-        let username_text_rect = Rect::new(form_rect.x, line + 1, form_rect.width, 1);
-        let username_text = Text::from(self.username.clone());
-        line += 3;
-        let password_label_rect = Rect::new(form_rect.x, line, form_rect.width, 1);
-        let password_label_text = Text::from(if self.selected == Field::Password {
-            "> Password"
-        } else {
-            "Password"
-        });
-        let password_text_rect = Rect::new(form_rect.x, line + 1, form_rect.width, 1);
-        let password_text = Text::from(
-            self.password
-                .clone()
-                .chars()
-                .map(|_| '*')
-                .collect::<String>(),
-        );
-
-        frame.render_widget(form_frame, form_rect);
-        frame.render_widget(instance_label_text, instance_label_rect);
-        frame.render_widget(instance_text, instance_text_rect);
-        frame.render_widget(username_label_text, username_label_rect);
-        frame.render_widget(username_text, username_text_rect);
-        frame.render_widget(password_label_text, password_label_rect);
-        frame.render_widget(password_text, password_text_rect);
+        for (field, label) in [
+            (Field::Instance, "Instance"),
+            (Field::Username, "Username"),
+            (Field::Password, "Password"),
+        ] {
+            let selected = self.selected == field;
+            let label_rect =
+                Rect::new(form_rect.x, line, form_rect.width, 1).intersection(form_rect);
+            let text_rect =
+                Rect::new(form_rect.x, line + 1, form_rect.width, 1).intersection(form_rect);
+            let label = if selected {
+                format!("> {label}")
+            } else {
+                label.to_owned()
+            };
+
+            frame.render_widget(Text::from(label), label_rect);
+            let input = match field {
+                Field::Instance => &mut self.instance,
+                Field::Username => &mut self.username,
+                Field::Password => &mut self.password,
+            };
+            input.render(frame, text_rect, selected);
+            self.field_areas.push((field, label_rect.union(text_rect)));
+            line += 3;
+        }
     }
 }
 
 impl Screen for Login {
     fn view(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        let help = self.keys.help(&[
+            ("Next", &[LoginAction::NextField]),
+            ("Back", &[LoginAction::PrevField]),
+            ("Exit", &[LoginAction::Quit]),
+        ]);
         let screen_frame = Block::new()
             .borders(Borders::TOP | Borders::BOTTOM)
             .border_type(BorderType::Plain)
-            .title_bottom("Confirm (Enter) / Next (Tab) / Back (Shift-Tab) / Exit (Esc)");
+            .title_bottom(format!("Confirm (Enter) / {help}"));
 
         frame.render_widget(screen_frame, area);
         self.draw_form(frame);
     }
     fn handle_key(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char(c) => match self.selected {
-                Field::Instance => self.instance.push(c),
-                Field::Username => self.username.push(c),
-                Field::Password => self.password.push(c),
-            },
-            KeyCode::Backspace => self.message(Message::Delete),
-            KeyCode::Tab => self.message(Message::NextField),
-            KeyCode::BackTab => self.message(Message::PrevField),
-            _ => (),
+        self.handle_key_event(KeyEvent::from(key));
+    }
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        match self.keys.action(key) {
+            Some(LoginAction::NextField) => self.message(Message::NextField),
+            Some(LoginAction::PrevField) => self.message(Message::PrevField),
+            Some(LoginAction::Quit) => self.message(Message::Quit),
+            None if self.keys.is_pending() => (),
+            None => self.message(Message::Edit(key)),
         }
     }
+    fn handle_paste(&mut self, text: &str) {
+        self.message(Message::Paste(text.to_owned()));
+    }
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        if let MouseEventKind::Down(MouseButton::Left) = event.kind {
+            self.message(Message::Click(Position::new(event.column, event.row)));
+        }
+    }
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 enum Field {
     Instance,
     Username,
@@ -0,0 +1,525 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        models::{self, User, Visibility},
+        Error,
+    },
+    config::keymap::{Keymap, MentionsAction},
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        theme::Theme,
+        widgets::feed::page::{NotePage, NotePageState},
+    },
+};
+
+use super::{compose::Compose, Direction, Navigation, Screen};
+
+/// Notes that mention us, and direct notes grouped into conversations.
+pub struct Mentions {
+    session: Session,
+    tasks: Tasks<Message>,
+    tab: Tab,
+    /// The signed in user, once fetched.
+    me: Option<User>,
+    /// Every user we know of, by id, to name conversation participants.
+    users: HashMap<String, User>,
+    mentions: Vec<Arc<models::Note>>,
+    mentions_page: NotePageState,
+    /// Direct notes, newest first.
+    direct: Vec<Arc<models::Note>>,
+    conversations: Vec<Conversation>,
+    conversations_state: ListState,
+    /// The index of the conversation being read, if any.
+    open_conversation: Option<usize>,
+    conversation_page: NotePageState,
+    keys: Keymap<MentionsAction>,
+    /// Whether a request is running.
+    loading: bool,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tab {
+    Mentions,
+    Direct,
+}
+
+/// Direct notes between us and the same set of users.
+pub struct Conversation {
+    /// The ids of everyone involved except us, sorted.
+    pub participants: Vec<String>,
+    /// Newest first.
+    pub notes: Vec<Arc<models::Note>>,
+}
+
+pub enum Message {
+    /// Switches between mentions and direct notes.
+    SwitchTab,
+    /// Selects the next or previous note or conversation.
+    Select(Direction),
+    /// Replies to the selected note, or opens the selected conversation.
+    Confirm,
+    /// Fetches the newest notes of the current tab again.
+    Refresh,
+    /// Fetches notes older than the last one in the current tab.
+    LoadMore,
+    LoadedMe(Result<User, Error>),
+    /// Receives fetched notes.
+    /// `append` is whether they come after the current ones.
+    Loaded {
+        tab: Tab,
+        result: Result<Vec<Arc<models::Note>>, Error>,
+        append: bool,
+    },
+    LoadedUsers(Result<Vec<User>, Error>),
+    /// Closes the open conversation, or the screen.
+    Close,
+}
+
+impl Mentions {
+    /// Creates the screen and starts fetching both tabs.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.mentions);
+        let mut screen = Self {
+            session,
+            tasks: Tasks::default(),
+            tab: Tab::Mentions,
+            me: None,
+            users: HashMap::new(),
+            mentions: Vec::new(),
+            mentions_page: NotePageState::default(),
+            direct: Vec::new(),
+            conversations: Vec::new(),
+            conversations_state: ListState::default(),
+            open_conversation: None,
+            conversation_page: NotePageState::default(),
+            keys,
+            loading: false,
+            status: None,
+            navigation: None,
+        };
+
+        let client = screen.session.client.clone();
+        screen.tasks.spawn(move || Message::LoadedMe(client.me()));
+        screen.fetch(Tab::Mentions, None);
+        screen.fetch(Tab::Direct, None);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SwitchTab => {
+                self.tab = match self.tab {
+                    Tab::Mentions => Tab::Direct,
+                    Tab::Direct => Tab::Mentions,
+                }
+            }
+            Message::Select(direction) => self.select(direction),
+            Message::Confirm => self.confirm(),
+            Message::Refresh => self.fetch(self.tab, None),
+            Message::LoadMore => {
+                if !self.loading {
+                    let until_id = match self.tab {
+                        Tab::Mentions => self.mentions.last(),
+                        Tab::Direct => self.direct.last(),
+                    }
+                    .map(|note| note.id.clone());
+                    self.fetch(self.tab, until_id);
+                }
+            }
+            Message::LoadedMe(result) => match result {
+                Ok(me) => {
+                    self.users.insert(me.id.clone(), me.clone());
+                    self.me = Some(me);
+                    self.rebuild_conversations();
+                }
+                Err(err) => self.status = Some(err.to_string()),
+            },
+            Message::Loaded {
+                tab,
+                result,
+                append,
+            } => {
+                self.loading = false;
+                let notes = match result {
+                    Ok(notes) => notes,
+                    Err(err) => {
+                        self.status = Some(err.to_string());
+                        return;
+                    }
+                };
+                self.status = None;
+                for note in &notes {
+                    self.users.insert(note.user.id.clone(), note.user.clone());
+                }
+
+                match tab {
+                    Tab::Mentions => {
+                        if !append {
+                            self.mentions.clear();
+                        }
+                        self.mentions.extend(notes);
+                        let selected = self.mentions_page.selected;
+                        self.mentions_page = NotePageState::new(
+                            self.session.client.store.clone(),
+                            self.mentions.clone(),
+                        );
+                        if append {
+                            self.mentions_page.selected = selected;
+                        }
+                    }
+                    Tab::Direct => {
+                        if !append {
+                            self.direct.clear();
+                        }
+                        self.direct.extend(notes);
+                        self.rebuild_conversations();
+                        self.fetch_unknown_users();
+                    }
+                }
+            }
+            Message::LoadedUsers(result) => match result {
+                Ok(users) => {
+                    for user in users {
+                        self.users.insert(user.id.clone(), user);
+                    }
+                }
+                Err(err) => self.status = Some(err.to_string()),
+            },
+            Message::Close => {
+                if self.tab == Tab::Direct && self.open_conversation.is_some() {
+                    self.open_conversation = None;
+                } else {
+                    self.navigation = Some(Navigation::Back);
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, direction: Direction) {
+        let conversation = match self.tab {
+            Tab::Mentions => None,
+            Tab::Direct => self.open_conversation,
+        };
+        let (len, selected) = match (self.tab, conversation) {
+            (Tab::Mentions, _) => (self.mentions_page.notes.len(), self.mentions_page.selected),
+            (Tab::Direct, Some(_)) => (
+                self.conversation_page.notes.len(),
+                self.conversation_page.selected,
+            ),
+            (Tab::Direct, None) => (self.conversations.len(), self.conversations_state.selected),
+        };
+        let at_end = selected.map_or(len == 0, |idx| idx + 1 >= len);
+
+        match (direction, self.tab, conversation) {
+            // open conversations only hold what's already loaded.
+            (Direction::Next, _, None) if at_end => self.message(Message::LoadMore),
+            (Direction::Next, Tab::Mentions, _) => self.mentions_page.next(),
+            (Direction::Previous, Tab::Mentions, _) => self.mentions_page.prev(),
+            (Direction::Next, Tab::Direct, Some(_)) => self.conversation_page.next(),
+            (Direction::Previous, Tab::Direct, Some(_)) => self.conversation_page.prev(),
+            (Direction::Next, Tab::Direct, None) => self.conversations_state.next(),
+            (Direction::Previous, Tab::Direct, None) => self.conversations_state.previous(),
+        }
+    }
+
+    fn confirm(&mut self) {
+        let note = match (self.tab, self.open_conversation) {
+            (Tab::Mentions, _) => self
+                .mentions_page
+                .selected
+                .and_then(|idx| self.mentions.get(idx)),
+            (Tab::Direct, Some(conversation)) => self
+                .conversation_page
+                .selected
+                .and_then(|idx| self.conversations.get(conversation)?.notes.get(idx)),
+            (Tab::Direct, None) => {
+                if let Some(idx) = self.conversations_state.selected {
+                    if let Some(conversation) = self.conversations.get(idx) {
+                        self.conversation_page = NotePageState::new(
+                            self.session.client.store.clone(),
+                            conversation.notes.clone(),
+                        );
+                        self.open_conversation = Some(idx);
+                    }
+                }
+                return;
+            }
+        };
+
+        if let Some(note) = note {
+            let users = self.known_users(note);
+            let compose = Compose::reply(self.session.clone(), note, &users, self.me.as_ref());
+            self.navigation = Some(Navigation::Push(Box::new(compose)));
+        }
+    }
+
+    /// Returns the users who can see `note` that were already looked up, to show
+    /// their handles.
+    fn known_users(&self, note: &models::Note) -> Vec<User> {
+        let mut participants = vec![note.user.clone()];
+        for id in note.visible_user_ids.iter().chain(&note.mentions) {
+            if let Some(user) = self.users.get(id) {
+                if !participants.iter().any(|known| known.id == user.id) {
+                    participants.push(user.clone());
+                }
+            }
+        }
+        participants
+    }
+
+    /// Groups direct notes by the users involved, newest conversation first.
+    fn rebuild_conversations(&mut self) {
+        let me = self.me.as_ref().map(|me| me.id.as_str());
+        let mut conversations: Vec<Conversation> = Vec::new();
+
+        for note in &self.direct {
+            let mut participants: Vec<String> = std::iter::once(&note.user.id)
+                .chain(&note.visible_user_ids)
+                .filter(|id| Some(id.as_str()) != me)
+                .cloned()
+                .collect();
+            participants.sort();
+            participants.dedup();
+
+            match conversations
+                .iter_mut()
+                .find(|conversation| conversation.participants == participants)
+            {
+                Some(conversation) => conversation.notes.push(note.clone()),
+                None => conversations.push(Conversation {
+                    participants,
+                    notes: vec![note.clone()],
+                }),
+            }
+        }
+
+        // keep the open conversation open, wherever it moved.
+        let open = self
+            .open_conversation
+            .and_then(|idx| self.conversations.get(idx))
+            .map(|conversation| conversation.participants.clone());
+        self.open_conversation = open.and_then(|participants| {
+            conversations
+                .iter()
+                .position(|conversation| conversation.participants == participants)
+        });
+        if let Some(idx) = self.open_conversation {
+            let selected = self.conversation_page.selected;
+            self.conversation_page = NotePageState::new(
+                self.session.client.store.clone(),
+                conversations[idx].notes.clone(),
+            );
+            self.conversation_page.selected = selected;
+        }
+        self.conversations = conversations;
+        if self.conversations.is_empty() {
+            self.conversations_state.select(None);
+        } else if self.conversations_state.selected.is_none() {
+            self.conversations_state.select(Some(0));
+        }
+    }
+
+    /// Fetches participants we only know by id.
+    fn fetch_unknown_users(&mut self) {
+        let mut unknown: Vec<String> = self
+            .conversations
+            .iter()
+            .flat_map(|conversation| &conversation.participants)
+            .filter(|id| !self.users.contains_key(*id))
+            .cloned()
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+
+        if !unknown.is_empty() {
+            let client = self.session.client.clone();
+            self.tasks
+                .spawn(move || Message::LoadedUsers(client.users_by_ids(&unknown)));
+        }
+    }
+
+    /// Fetches the notes of `tab`, older than `until_id` if given.
+    fn fetch(&mut self, tab: Tab, until_id: Option<String>) {
+        let client = self.session.client.clone();
+        let visibility = (tab == Tab::Direct).then_some(Visibility::Specified);
+        let append = until_id.is_some();
+
+        self.loading = true;
+        self.tasks.spawn(move || Message::Loaded {
+            tab,
+            result: client.mentions(visibility, until_id.as_deref()),
+            append,
+        });
+    }
+
+    /// Names the participants of a conversation, e.g. `@alice, @bob@misskey.io`.
+    fn participant_names(&self, conversation: &Conversation) -> String {
+        if conversation.participants.is_empty() {
+            return "Only you".into();
+        }
+
+        conversation
+            .participants
+            .iter()
+            .map(|id| match self.users.get(id) {
+                Some(user) => format!("@{}", user.acct()),
+                None => format!("[{id}]"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn tab_bar(&self) -> Line<'static> {
+        let tab = |tab: Tab, label: &'static str| {
+            let style = if self.tab == tab {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            Span::styled(format!(" {label} "), style)
+        };
+
+        Line::from(vec![
+            tab(Tab::Mentions, "Mentions"),
+            Span::raw(" "),
+            tab(Tab::Direct, "Direct"),
+        ])
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: MentionsAction) {
+        self.message(match action {
+            MentionsAction::SwitchTab => Message::SwitchTab,
+            MentionsAction::NextNote => Message::Select(Direction::Next),
+            MentionsAction::PrevNote => Message::Select(Direction::Previous),
+            MentionsAction::Confirm => Message::Confirm,
+            MentionsAction::Refresh => Message::Refresh,
+            MentionsAction::Close => Message::Close,
+        });
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading {
+            return "Loading...".into();
+        }
+
+        let confirm = match (self.tab, self.open_conversation) {
+            (Tab::Direct, None) => "Open",
+            _ => "Reply",
+        };
+        self.keys.help(&[
+            ("Switch tab", &[MentionsAction::SwitchTab]),
+            (
+                "Move",
+                &[MentionsAction::NextNote, MentionsAction::PrevNote],
+            ),
+            (confirm, &[MentionsAction::Confirm]),
+            ("Refresh", &[MentionsAction::Refresh]),
+            ("Back", &[MentionsAction::Close]),
+        ])
+    }
+
+    fn view_conversations(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let items: Vec<Paragraph> = self
+            .conversations
+            .iter()
+            .map(|conversation| {
+                let excerpt = conversation
+                    .notes
+                    .first()
+                    .and_then(|note| note.text.as_deref())
+                    .and_then(|text| text.lines().next())
+                    .unwrap_or("")
+                    .to_owned();
+                Paragraph::new(Text::from(vec![
+                    Line::from(self.participant_names(conversation)),
+                    Line::from(excerpt),
+                ]))
+            })
+            .collect();
+
+        let builder = ListBuilder::new(move |ctx| {
+            let borders = if ctx.is_selected {
+                Borders::ALL
+            } else {
+                Borders::TOP | Borders::BOTTOM
+            };
+            let item = items[ctx.index]
+                .clone()
+                .block(Block::new().borders(borders).border_type(BorderType::Plain));
+            (item, 4)
+        });
+        let list_view = ListView::new(builder, self.conversations.len());
+        frame.render_stateful_widget(list_view, area, &mut self.conversations_state);
+    }
+}
+
+impl Screen for Mentions {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let [tabs_area, list_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(self.tab_bar(), tabs_area);
+        match (self.tab, self.open_conversation) {
+            (Tab::Mentions, _) => {
+                frame.render_stateful_widget(NotePage::new(), list_area, &mut self.mentions_page)
+            }
+            (Tab::Direct, Some(idx)) => {
+                let [title_area, notes_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(list_area);
+                let title = self
+                    .conversations
+                    .get(idx)
+                    .map(|conversation| format!("With {}", self.participant_names(conversation)));
+                frame.render_widget(Line::from(title.unwrap_or_default()), title_area);
+                frame.render_stateful_widget(
+                    NotePage::new(),
+                    notes_area,
+                    &mut self.conversation_page,
+                );
+            }
+            (Tab::Direct, None) => self.view_conversations(frame, list_area),
+        }
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
@@ -1,8 +1,21 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::Frame;
 
+pub mod channels;
+pub mod clips;
+pub mod compose;
+pub mod deck;
+pub mod drafts;
+pub mod drive;
+pub mod favorites;
+pub mod feeds;
+pub mod hashtag;
 pub mod home;
 pub mod login;
+pub mod mentions;
+pub mod notifications;
+pub mod search;
+pub mod timeline;
 
 /// A full screen view.
 pub trait Screen {
@@ -10,4 +23,41 @@ pub trait Screen {
     fn view(&mut self, frame: &mut Frame);
     /// Handles keydown.
     fn handle_key(&mut self, key: KeyCode);
+    /// Handles keydown with its modifiers, for screens with text inputs.
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        self.handle_key(key.code);
+    }
+    /// Handles text pasted into the terminal.
+    fn handle_paste(&mut self, _text: &str) {}
+    /// Handles clicks and the scroll wheel.
+    fn handle_mouse(&mut self, _event: MouseEvent) {}
+    /// Takes the screen change requested while handling the last event, if any.
+    fn navigation(&mut self) -> Option<Navigation> {
+        None
+    }
+    /// Takes work to run outside of the TUI, requested while handling the last event, if any.
+    fn suspend(&mut self) -> Option<Suspend> {
+        None
+    }
+}
+
+/// Work that takes over the terminal, e.g. an external editor.
+/// The TUI is torn down while it runs and set up again after.
+pub type Suspend = Box<dyn FnOnce() + Send + Sync>;
+
+/// A change of the current screen.
+pub enum Navigation {
+    /// Opens a screen on top of the current one.
+    Push(Box<dyn Screen + Send + Sync>),
+    /// Opens a screen in place of the current one.
+    Replace(Box<dyn Screen + Send + Sync>),
+    /// Closes the current screen and goes back to the previous one.
+    Back,
+    /// Quits keytan, asking first if drafts weren't posted.
+    Quit,
+}
+
+pub enum Direction {
+    Next,
+    Previous,
 }
@@ -0,0 +1,418 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        notifications::{Notification, NotificationQuery, NotificationType},
+        Client, Error,
+    },
+    config::keymap::{Keymap, NotificationsAction},
+    state::session::Session,
+    ui::{tasks::Tasks, theme::Theme, widgets::notification::NotificationItem},
+};
+
+use super::{Direction, Navigation, Screen};
+
+/// A list of notifications, fetching older ones when scrolled past the end.
+pub struct NotificationFeed {
+    client: Client,
+    tasks: Tasks<FeedMessage>,
+    /// Which notifications to fetch, without `until_id`.
+    query: NotificationQuery,
+    notifications: Vec<Notification>,
+    list_state: ListState,
+    /// Whether a request is running.
+    loading: bool,
+    /// Bumped whenever the list is fetched anew, so responses for an older query are dropped.
+    generation: u64,
+    /// Whether older notifications are left to fetch.
+    has_more: bool,
+    /// Why the last fetch failed, if it did.
+    error: Option<String>,
+}
+
+pub enum FeedMessage {
+    /// Receives fetched notifications.
+    /// `append` is whether they come after the current ones,
+    /// and `generation` the feed's generation when they were requested.
+    Loaded {
+        result: Result<Vec<Notification>, Error>,
+        append: bool,
+        generation: u64,
+    },
+}
+
+/// Our notifications, optionally grouped and filtered by type.
+pub struct Notifications {
+    session: Session,
+    tasks: Tasks<Message>,
+    feed: NotificationFeed,
+    /// Whether reactions and renotes on the same note are merged into one entry.
+    grouped: bool,
+    /// How each type is filtered, in the order of `NotificationType::FILTERABLE`.
+    filters: Vec<TypeFilter>,
+    /// The index of the type under the cursor in the filter bar.
+    filter_cursor: usize,
+    keys: Keymap<NotificationsAction>,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous notification.
+    SetNotification(Direction),
+    /// Moves the cursor in the filter bar.
+    MoveFilterCursor(Direction),
+    /// Cycles the filter of the type under the cursor.
+    CycleFilter,
+    /// Switches between the grouped and the flat list.
+    ToggleGrouped,
+    /// Fetches the newest notifications again.
+    Refresh,
+    MarkAllRead,
+    MarkedAllRead(Result<(), Error>),
+    Close,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TypeFilter {
+    /// Show the type along with others.
+    Any,
+    /// Only show this type (and other `Only` types).
+    Only,
+    /// Never show this type.
+    Hidden,
+}
+
+impl TypeFilter {
+    fn next(&self) -> Self {
+        match self {
+            Self::Any => Self::Only,
+            Self::Only => Self::Hidden,
+            Self::Hidden => Self::Any,
+        }
+    }
+}
+
+impl NotificationFeed {
+    /// Creates an empty feed. Nothing is fetched until `refresh` is called.
+    pub fn new(client: Client, query: NotificationQuery) -> Self {
+        Self {
+            client,
+            tasks: Tasks::default(),
+            query,
+            notifications: Vec::new(),
+            list_state: ListState::default(),
+            loading: false,
+            generation: 0,
+            has_more: true,
+            error: None,
+        }
+    }
+
+    /// Fetches the newest notifications matching `query` instead.
+    pub fn set_query(&mut self, query: NotificationQuery) {
+        self.query = query;
+        self.refresh();
+    }
+
+    /// Fetches the newest notifications again.
+    pub fn refresh(&mut self) {
+        self.generation += 1;
+        self.fetch(None);
+    }
+
+    /// Handles finished requests.
+    pub fn update(&mut self) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+    }
+
+    fn message(&mut self, message: FeedMessage) {
+        match message {
+            FeedMessage::Loaded {
+                result,
+                append,
+                generation,
+            } => {
+                if generation != self.generation {
+                    return;
+                }
+                self.loading = false;
+                match result {
+                    Ok(notifications) => {
+                        self.has_more = !notifications.is_empty();
+                        if append {
+                            self.notifications.extend(notifications);
+                        } else {
+                            self.notifications = notifications;
+                            self.list_state = ListState::default();
+                        }
+                        if self.list_state.selected.is_none() && !self.notifications.is_empty() {
+                            self.list_state.select(Some(0));
+                        }
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+        }
+    }
+
+    /// Selects the next notification, fetching older ones at the end.
+    pub fn select_next(&mut self) {
+        let next = self.list_state.selected.map_or(0, |idx| idx + 1);
+        if next < self.notifications.len() {
+            self.list_state.select(Some(next));
+        } else if self.has_more && !self.loading {
+            let until_id = self.notifications.last().map(|n| n.id.clone());
+            self.fetch(until_id);
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if let Some(idx) = self.list_state.selected {
+            self.list_state.select(Some(idx.saturating_sub(1)));
+        }
+    }
+
+    /// Fetches notifications older than `until_id`, or the newest ones.
+    fn fetch(&mut self, until_id: Option<String>) {
+        let append = until_id.is_some();
+        let query = NotificationQuery {
+            until_id,
+            ..self.query.clone()
+        };
+        let client = self.client.clone();
+        let generation = self.generation;
+
+        self.loading = true;
+        self.tasks.spawn(move || FeedMessage::Loaded {
+            result: client.notifications(&query),
+            append,
+            generation,
+        });
+    }
+
+    /// Returns the selected notification, if any.
+    pub fn selected(&self) -> Option<&Notification> {
+        self.notifications.get(self.list_state.selected?)
+    }
+
+    /// Returns the last error, or whether notifications are loading.
+    pub fn status_text(&self) -> Option<String> {
+        match &self.error {
+            Some(error) => Some(error.clone()),
+            None => self.loading.then(|| "Loading...".into()),
+        }
+    }
+
+    /// Draws the list.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let notifications = &self.notifications;
+        let builder = ListBuilder::new(move |ctx| {
+            let mut item = NotificationItem::new(&notifications[ctx.index]);
+            item.selected = ctx.is_selected;
+            (item, NotificationItem::HEIGHT)
+        });
+        let list_view = ListView::new(builder, notifications.len());
+        frame.render_stateful_widget(list_view, area, &mut self.list_state);
+    }
+}
+
+impl Notifications {
+    /// Creates the screen and starts fetching notifications.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.notifications);
+        let mut screen = Self {
+            feed: NotificationFeed::new(session.client.clone(), NotificationQuery::default()),
+            session,
+            tasks: Tasks::default(),
+            grouped: true,
+            filters: vec![TypeFilter::Any; NotificationType::FILTERABLE.len()],
+            filter_cursor: 0,
+            keys,
+            status: None,
+            navigation: None,
+        };
+        screen.message(Message::Refresh);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SetNotification(Direction::Next) => self.feed.select_next(),
+            Message::SetNotification(Direction::Previous) => self.feed.select_prev(),
+            Message::MoveFilterCursor(Direction::Next) => {
+                self.filter_cursor = (self.filter_cursor + 1) % self.filters.len();
+            }
+            Message::MoveFilterCursor(Direction::Previous) => {
+                self.filter_cursor =
+                    (self.filter_cursor + self.filters.len() - 1) % self.filters.len();
+            }
+            Message::CycleFilter => {
+                let filter = &mut self.filters[self.filter_cursor];
+                *filter = filter.next();
+                self.message(Message::Refresh);
+            }
+            Message::ToggleGrouped => {
+                self.grouped = !self.grouped;
+                self.message(Message::Refresh);
+            }
+            Message::Refresh => {
+                self.status = None;
+                self.feed.set_query(self.query());
+            }
+            Message::MarkAllRead => {
+                let client = self.session.client.clone();
+                self.tasks
+                    .spawn(move || Message::MarkedAllRead(client.mark_all_notifications_read()));
+            }
+            Message::MarkedAllRead(result) => {
+                self.status = Some(match result {
+                    Ok(()) => "Marked all notifications as read.".into(),
+                    Err(err) => err.to_string(),
+                });
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: NotificationsAction) {
+        self.message(match action {
+            NotificationsAction::NextNotification => Message::SetNotification(Direction::Next),
+            NotificationsAction::PrevNotification => Message::SetNotification(Direction::Previous),
+            NotificationsAction::NextFilter => Message::MoveFilterCursor(Direction::Next),
+            NotificationsAction::PrevFilter => Message::MoveFilterCursor(Direction::Previous),
+            NotificationsAction::CycleFilter => Message::CycleFilter,
+            NotificationsAction::ToggleGrouped => Message::ToggleGrouped,
+            NotificationsAction::Refresh => Message::Refresh,
+            NotificationsAction::MarkAllRead => Message::MarkAllRead,
+            NotificationsAction::Close => Message::Close,
+        });
+    }
+
+    fn query(&self) -> NotificationQuery {
+        let with_filter = |wanted: TypeFilter| {
+            NotificationType::FILTERABLE
+                .iter()
+                .zip(&self.filters)
+                .filter(|(_, filter)| **filter == wanted)
+                .map(|(kind, _)| *kind)
+                .collect()
+        };
+
+        NotificationQuery {
+            grouped: self.grouped,
+            include_types: with_filter(TypeFilter::Only),
+            exclude_types: with_filter(TypeFilter::Hidden),
+            until_id: None,
+        }
+    }
+
+    /// Returns a line with every type and its filter, e.g. `+mention -reaction follow`.
+    fn filter_bar(&self) -> Line<'static> {
+        let spans = NotificationType::FILTERABLE
+            .iter()
+            .zip(&self.filters)
+            .enumerate()
+            .map(|(idx, (kind, filter))| {
+                let (prefix, mut style) = match filter {
+                    TypeFilter::Any => ("", Style::new()),
+                    TypeFilter::Only => ("+", Style::new().add_modifier(Modifier::BOLD)),
+                    TypeFilter::Hidden => ("-", Style::new().add_modifier(Modifier::CROSSED_OUT)),
+                };
+                if idx == self.filter_cursor {
+                    style = style.patch(Theme::current().selection);
+                }
+                [
+                    Span::styled(format!("{prefix}{}", kind.as_str()), style),
+                    Span::raw(" "),
+                ]
+            });
+
+        Line::from(spans.flatten().collect::<Vec<_>>())
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = self.status.clone().or_else(|| self.feed.status_text()) {
+            return status;
+        }
+
+        let help = self.keys.help(&[
+            (
+                "Move",
+                &[
+                    NotificationsAction::NextNotification,
+                    NotificationsAction::PrevNotification,
+                ],
+            ),
+            (
+                "Filter",
+                &[
+                    NotificationsAction::PrevFilter,
+                    NotificationsAction::NextFilter,
+                    NotificationsAction::CycleFilter,
+                ],
+            ),
+            ("Group", &[NotificationsAction::ToggleGrouped]),
+            ("Refresh", &[NotificationsAction::Refresh]),
+            ("Mark all as read", &[NotificationsAction::MarkAllRead]),
+            ("Back", &[NotificationsAction::Close]),
+        ]);
+        let mode = if self.grouped { "Grouped" } else { "Flat" };
+        format!("{mode} / {help}")
+    }
+}
+
+impl Screen for Notifications {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+        self.feed.update();
+
+        let [filter_area, list_area, status_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let filter_bar = Paragraph::new(self.filter_bar()).block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Plain)
+                .title_top("Notifications"),
+        );
+        frame.render_widget(filter_bar, filter_area);
+
+        self.feed.render(frame, list_area);
+
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
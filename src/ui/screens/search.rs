@@ -0,0 +1,569 @@
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::{
+        meta::Meta,
+        models::{self, User},
+        notes::NoteSearch,
+        store::LocalQuery,
+        timelines::{TagQuery, Timeline},
+        Error,
+    },
+    config::keymap::{Keymap, SearchAction},
+    state::session::Session,
+    ui::{
+        tasks::Tasks,
+        theme::Theme,
+        widgets::{
+            feed::page::{NotePage, NotePageState},
+            text_input::TextInput,
+            user::UserItem,
+        },
+    },
+};
+
+use super::{compose::Compose, timeline::TimelineScreen, Direction, Navigation, Screen};
+
+/// Searches notes, users and hashtags.
+pub struct Search {
+    session: Session,
+    tasks: Tasks<Message>,
+    tab: Tab,
+    focus: Focus,
+    /// The text typed in the query box, filters included.
+    query: TextInput,
+    /// Whether the instance lets us search notes, once known.
+    can_search_notes: Option<bool>,
+    notes: Vec<Arc<models::Note>>,
+    notes_page: NotePageState,
+    /// The last server note search, to load more results with.
+    note_search: Option<NoteSearch>,
+    users: Vec<User>,
+    users_state: ListState,
+    hashtags: Vec<String>,
+    hashtags_state: ListState,
+    /// The keys bound to the screen's actions. While typing a query, only switching
+    /// tabs is taken and other keys edit it.
+    keys: Keymap<SearchAction>,
+    /// Whether a request is running.
+    loading: bool,
+    /// The outcome of the last action, shown at the bottom.
+    status: Option<String>,
+    navigation: Option<Navigation>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tab {
+    Notes,
+    Users,
+    Hashtags,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Focus {
+    Query,
+    Results,
+}
+
+pub enum Message {
+    /// Edits the query.
+    Edit(KeyEvent),
+    /// Inserts pasted text in the query.
+    Paste(String),
+    /// Searches the current tab for the query.
+    Submit,
+    /// Goes to the next tab, keeping the query.
+    SwitchTab,
+    /// Goes back to editing the query.
+    FocusQuery,
+    /// Selects the next or previous result.
+    Select(Direction),
+    /// Opens the selected result.
+    Open,
+    /// Fetches more note results.
+    LoadMore,
+    /// Adds the note search to the home screen's tabs, or removes it.
+    TogglePin,
+    LoadedMeta(Result<Meta, Error>),
+    /// Receives note results.
+    /// `append` is whether they come after the current ones.
+    LoadedNotes {
+        result: Result<(NoteSearch, Vec<Arc<models::Note>>), Error>,
+        append: bool,
+    },
+    LoadedUsers(Result<Vec<User>, Error>),
+    LoadedHashtags(Result<Vec<String>, Error>),
+    Close,
+}
+
+/// A query with its `from:`, `host:` and `channel:` filters pulled out.
+#[derive(Default)]
+struct ParsedQuery {
+    words: Vec<String>,
+    /// `from:@alice@misskey.io`
+    user: Option<String>,
+    /// `host:misskey.io`, or `host:.` for local notes.
+    host: Option<String>,
+    /// `channel:<id>`
+    channel_id: Option<String>,
+}
+
+impl ParsedQuery {
+    fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for word in query.split_whitespace() {
+            if let Some(user) = word.strip_prefix("from:") {
+                parsed.user = Some(user.trim_start_matches('@').to_owned());
+            } else if let Some(host) = word.strip_prefix("host:") {
+                parsed.host = Some(host.to_owned());
+            } else if let Some(channel_id) = word.strip_prefix("channel:") {
+                parsed.channel_id = Some(channel_id.to_owned());
+            } else {
+                parsed.words.push(word.to_owned());
+            }
+        }
+        parsed
+    }
+
+    fn text(&self) -> String {
+        self.words.join(" ")
+    }
+}
+
+impl Search {
+    /// Creates the screen and checks whether the instance allows note search.
+    pub fn new(session: Session) -> Self {
+        let keys = Keymap::new(&session.config.read().keys.search);
+        let screen = Self {
+            session,
+            tasks: Tasks::default(),
+            tab: Tab::Notes,
+            focus: Focus::Query,
+            query: TextInput::default(),
+            can_search_notes: None,
+            notes: Vec::new(),
+            notes_page: NotePageState::default(),
+            note_search: None,
+            users: Vec::new(),
+            users_state: ListState::default(),
+            hashtags: Vec::new(),
+            hashtags_state: ListState::default(),
+            keys,
+            loading: false,
+            status: None,
+            navigation: None,
+        };
+
+        let client = screen.session.client.clone();
+        screen
+            .tasks
+            .spawn(move || Message::LoadedMeta(client.meta()));
+        screen
+    }
+
+    /// Creates the screen and searches notes for `query` right away, e.g. `from:@foo@misskey.io`.
+    pub fn with_query(session: Session, query: &str) -> Self {
+        let mut screen = Self::new(session);
+        screen.query.set_text(query);
+        screen.message(Message::Submit);
+        screen
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::Edit(key) => {
+                self.query.handle_key(key);
+            }
+            Message::Paste(text) => self.query.insert(&text),
+            Message::Submit => {
+                self.focus = Focus::Results;
+                self.search();
+            }
+            Message::SwitchTab => {
+                self.tab = match self.tab {
+                    Tab::Notes => Tab::Users,
+                    Tab::Users => Tab::Hashtags,
+                    Tab::Hashtags => Tab::Notes,
+                };
+                self.status = None;
+            }
+            Message::FocusQuery => self.focus = Focus::Query,
+            Message::Select(direction) => self.select(direction),
+            Message::Open => self.open(),
+            Message::LoadMore => {
+                if let (false, Some(search)) = (self.loading, self.note_search.clone()) {
+                    let until_id = self.notes.last().map(|note| note.id.clone());
+                    let client = self.session.client.clone();
+                    self.loading = true;
+                    self.tasks.spawn(move || Message::LoadedNotes {
+                        result: client
+                            .search_notes(&search, until_id.as_deref())
+                            .map(|notes| (search, notes)),
+                        append: true,
+                    });
+                }
+            }
+            Message::TogglePin => {
+                let parsed = ParsedQuery::parse(self.query.text());
+                if parsed.user.is_some() || parsed.host.is_some() || parsed.channel_id.is_some() {
+                    self.status = Some("Searches with filters can't be pinned.".into());
+                } else if !parsed.words.is_empty() {
+                    let timeline = Timeline::Search {
+                        query: parsed.text(),
+                    };
+                    self.session
+                        .update_config(|config| config.toggle_pinned(timeline));
+                }
+            }
+            Message::LoadedMeta(result) => match result {
+                Ok(meta) => self.can_search_notes = Some(meta.policies.can_search_notes),
+                Err(err) => self.status = Some(err.to_string()),
+            },
+            Message::LoadedNotes { result, append } => {
+                self.loading = false;
+                match result {
+                    Ok((search, notes)) => {
+                        self.status = None;
+                        self.note_search = Some(search);
+                        self.show_notes(notes, append);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::LoadedUsers(result) => {
+                self.loading = false;
+                match result {
+                    Ok(users) => {
+                        self.status = None;
+                        self.users = users;
+                        self.users_state = ListState::default();
+                        if !self.users.is_empty() {
+                            self.users_state.select(Some(0));
+                        }
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::LoadedHashtags(result) => {
+                self.loading = false;
+                match result {
+                    Ok(hashtags) => {
+                        self.status = None;
+                        self.hashtags = hashtags;
+                        self.hashtags_state = ListState::default();
+                        if !self.hashtags.is_empty() {
+                            self.hashtags_state.select(Some(0));
+                        }
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: SearchAction) {
+        self.message(match action {
+            SearchAction::SwitchTab => Message::SwitchTab,
+            SearchAction::NextResult => Message::Select(Direction::Next),
+            SearchAction::PrevResult => Message::Select(Direction::Previous),
+            SearchAction::Open => Message::Open,
+            SearchAction::FocusQuery => Message::FocusQuery,
+            SearchAction::TogglePin if self.tab == Tab::Notes => Message::TogglePin,
+            SearchAction::TogglePin => return,
+            SearchAction::Close => Message::Close,
+        });
+    }
+
+    /// Runs the query for the current tab.
+    fn search(&mut self) {
+        let client = self.session.client.clone();
+        let parsed = ParsedQuery::parse(self.query.text());
+
+        match self.tab {
+            Tab::Notes if self.can_search_notes == Some(false) => {
+                let query = LocalQuery {
+                    words: parsed.words,
+                    user: parsed.user,
+                    host: parsed.host,
+                    channel_id: parsed.channel_id,
+                };
+                let notes = self.session.client.store.read().search(&query);
+                self.note_search = None;
+                self.show_notes(notes, false);
+                self.status = Some(
+                    "Note search is disabled on this instance, showing cached notes only.".into(),
+                );
+            }
+            Tab::Notes => {
+                self.loading = true;
+                self.tasks.spawn(move || {
+                    let search = |user_id| {
+                        let search = NoteSearch {
+                            query: parsed.text(),
+                            user_id,
+                            host: parsed.host.clone(),
+                            channel_id: parsed.channel_id.clone(),
+                        };
+                        client
+                            .search_notes(&search, None)
+                            .map(|notes| (search, notes))
+                    };
+                    let result = match &parsed.user {
+                        Some(acct) => client
+                            .user_by_acct(acct)
+                            .and_then(|user| search(Some(user.id))),
+                        None => search(None),
+                    };
+                    Message::LoadedNotes {
+                        result,
+                        append: false,
+                    }
+                });
+            }
+            Tab::Users => {
+                let query = self.query.text().trim().to_owned();
+                self.loading = true;
+                self.tasks
+                    .spawn(move || Message::LoadedUsers(client.search_users(&query)));
+            }
+            Tab::Hashtags => {
+                let query = self.query.text().trim().to_owned();
+                self.loading = true;
+                self.tasks
+                    .spawn(move || Message::LoadedHashtags(client.search_hashtags(&query)));
+            }
+        }
+    }
+
+    fn show_notes(&mut self, notes: Vec<Arc<models::Note>>, append: bool) {
+        if !append {
+            self.notes.clear();
+        }
+        self.notes.extend(notes);
+
+        let selected = self.notes_page.selected;
+        self.notes_page = NotePageState::new(self.session.client.store.clone(), self.notes.clone());
+        if append {
+            self.notes_page.selected = selected;
+        }
+    }
+
+    fn select(&mut self, direction: Direction) {
+        match (self.tab, direction) {
+            (Tab::Notes, Direction::Next) => {
+                let at_end = self
+                    .notes_page
+                    .selected
+                    .map_or(true, |idx| idx + 1 >= self.notes.len());
+                if at_end {
+                    self.message(Message::LoadMore);
+                } else {
+                    self.notes_page.next();
+                }
+            }
+            (Tab::Notes, Direction::Previous) => self.notes_page.prev(),
+            (Tab::Users, Direction::Next) => self.users_state.next(),
+            (Tab::Users, Direction::Previous) => self.users_state.previous(),
+            (Tab::Hashtags, Direction::Next) => self.hashtags_state.next(),
+            (Tab::Hashtags, Direction::Previous) => self.hashtags_state.previous(),
+        }
+    }
+
+    /// Replies to a note, searches notes by the selected user, or opens a hashtag's timeline.
+    fn open(&mut self) {
+        match self.tab {
+            Tab::Notes => {
+                if let Some(note) = self.notes_page.selected.and_then(|idx| self.notes.get(idx)) {
+                    let compose =
+                        Compose::reply(self.session.clone(), note, &[note.user.clone()], None);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Tab::Users => {
+                if let Some(user) = self
+                    .users_state
+                    .selected
+                    .and_then(|idx| self.users.get(idx))
+                {
+                    self.query.set_text(format!("from:@{}", user.acct()));
+                    self.tab = Tab::Notes;
+                    self.message(Message::Submit);
+                }
+            }
+            Tab::Hashtags => {
+                if let Some(tag) = self
+                    .hashtags_state
+                    .selected
+                    .and_then(|idx| self.hashtags.get(idx))
+                {
+                    let timeline = Timeline::Hashtag {
+                        query: TagQuery::tag(tag),
+                    };
+                    let screen = TimelineScreen::new(self.session.clone(), timeline);
+                    self.navigation = Some(Navigation::Push(Box::new(screen)));
+                }
+            }
+        }
+    }
+
+    fn tab_bar(&self) -> Line<'static> {
+        let tab = |tab: Tab, label: &'static str| {
+            let style = if self.tab == tab {
+                Theme::current().selection
+            } else {
+                Style::new()
+            };
+            [Span::styled(format!(" {label} "), style), Span::raw(" ")]
+        };
+
+        Line::from(
+            [
+                tab(Tab::Notes, "Notes"),
+                tab(Tab::Users, "Users"),
+                tab(Tab::Hashtags, "Hashtags"),
+            ]
+            .concat(),
+        )
+    }
+
+    /// Whether the query is pinned to the home screen's tabs.
+    fn is_pinned(&self) -> bool {
+        let timeline = Timeline::Search {
+            query: ParsedQuery::parse(self.query.text()).text(),
+        };
+        self.session
+            .config
+            .read()
+            .pinned_timelines
+            .contains(&timeline)
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading {
+            return "Searching...".into();
+        }
+
+        if self.focus == Focus::Query {
+            let switch = self
+                .keys
+                .help(&[("Switch tab", &[SearchAction::SwitchTab])]);
+            return format!(
+                "Search (Enter) / {switch} / Filters: from:@user host:example.com channel:<id>"
+            );
+        }
+        let (pin_label, pin): (&str, &[SearchAction]) = match self.tab {
+            Tab::Notes if self.is_pinned() => ("Unpin as tab", &[SearchAction::TogglePin]),
+            Tab::Notes => ("Pin as tab", &[SearchAction::TogglePin]),
+            // only note searches can be pinned.
+            _ => ("", &[]),
+        };
+        self.keys.help(&[
+            (
+                "Move",
+                &[SearchAction::NextResult, SearchAction::PrevResult],
+            ),
+            ("Open", &[SearchAction::Open]),
+            (pin_label, pin),
+            ("Switch tab", &[SearchAction::SwitchTab]),
+            ("Edit query", &[SearchAction::FocusQuery]),
+            ("Back", &[SearchAction::Close]),
+        ])
+    }
+
+    fn view_results(&mut self, frame: &mut Frame, area: Rect) {
+        match self.tab {
+            Tab::Notes => frame.render_stateful_widget(NotePage::new(), area, &mut self.notes_page),
+            Tab::Users => {
+                let users = &self.users;
+                let builder = ListBuilder::new(move |ctx| {
+                    let mut item = UserItem::new(&users[ctx.index]);
+                    item.selected = ctx.is_selected;
+                    (item, UserItem::HEIGHT)
+                });
+                let list_view = ListView::new(builder, users.len());
+                frame.render_stateful_widget(list_view, area, &mut self.users_state);
+            }
+            Tab::Hashtags => {
+                let hashtags = &self.hashtags;
+                let builder = ListBuilder::new(move |ctx| {
+                    let style = if ctx.is_selected {
+                        Theme::current().selection
+                    } else {
+                        Style::new()
+                    };
+                    (Line::styled(format!("#{}", hashtags[ctx.index]), style), 1)
+                });
+                let list_view = ListView::new(builder, hashtags.len());
+                frame.render_stateful_widget(list_view, area, &mut self.hashtags_state);
+            }
+        }
+    }
+}
+
+impl Screen for Search {
+    fn view(&mut self, frame: &mut Frame) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+
+        let [query_area, tabs_area, results_area, status_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let query_box = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .title_top("Search");
+        let input_area = query_box.inner(query_area);
+        frame.render_widget(query_box, query_area);
+        self.query
+            .render(frame, input_area, self.focus == Focus::Query);
+        frame.render_widget(self.tab_bar(), tabs_area);
+        self.view_results(frame, results_area);
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        let action = self.keys.action(key);
+        match (self.focus, action, key.code) {
+            (Focus::Query, Some(SearchAction::SwitchTab), _) => self.message(Message::SwitchTab),
+            (Focus::Query, _, KeyCode::Enter) => self.message(Message::Submit),
+            (Focus::Query, _, _) => self.message(Message::Edit(key)),
+            (Focus::Results, Some(action), _) => self.action(action),
+            _ => (),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.focus == Focus::Query {
+            self.message(Message::Paste(text.to_owned()));
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
@@ -0,0 +1,849 @@
+use std::{
+    collections::HashSet,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    text::Line,
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    api::{
+        models,
+        timelines::{TagQuery, Timeline, TIMELINE_LIMIT},
+        Client, Error,
+    },
+    config::keymap::{Keymap, TimelineAction},
+    state::session::Session,
+    ui::{
+        browser,
+        tasks::Tasks,
+        widgets::feed::{
+            note::Link,
+            page::{NotePage, NotePageState},
+            NoteFeed, NoteFeedState,
+        },
+    },
+};
+
+use super::{
+    clips::Clips, compose::Compose, hashtag::HashtagPrompt, search::Search, Direction, Navigation,
+    Screen,
+};
+
+/// A timeline's notes in a paginated feed, kept up to date through the streaming API.
+///
+/// Notes are fetched in batches by id, with `untilId` for older ones and `sinceId` for newer
+/// ones, each batch on its own page. Older notes are fetched ahead as the end is neared,
+/// and a gap row stands for the notes missing between two batches that don't meet.
+pub struct TimelineFeed {
+    pub timeline: Timeline,
+    client: Client,
+    tasks: Tasks<FeedMessage>,
+    /// The loaded notes, a page per batch, newest first.
+    pub feed_state: NoteFeedState,
+    /// Streamed notes held back while reading further down, newest first.
+    pending: Vec<Arc<models::Note>>,
+    /// Whether the first fetch and the stream were started.
+    started: bool,
+    /// Whether a request is running. Only one runs at a time, so each lands where the
+    /// notes were when it started.
+    loading: bool,
+    /// Whether to fetch newer notes once the running request is done.
+    refresh_queued: bool,
+    /// Whether the oldest notes were reached.
+    at_end: bool,
+    /// Whether new notes are being streamed in.
+    live: bool,
+    /// Tells the stream to stop, once the feed is gone.
+    stop: Arc<AtomicBool>,
+    /// The outcome of the last action, shown in the status row.
+    status: Option<String>,
+}
+
+/// Which notes a fetch is for, and where they go.
+pub enum Fetch {
+    /// The newest notes, replacing the loaded ones.
+    Newest,
+    /// The newest notes, on a new first page above the loaded ones, with a gap below
+    /// it unless they reach them.
+    Newer,
+    /// Notes older than the loaded ones, on a new last page.
+    Older,
+    /// Notes missing below the page ending with the note `until_id`, down to the page
+    /// starting with the note `since_id`, on a new page between them.
+    Gap {
+        until_id: String,
+        since_id: Option<String>,
+    },
+}
+
+pub enum FeedMessage {
+    /// Receives fetched notes, newest first.
+    Loaded {
+        result: Result<Vec<Arc<models::Note>>, Error>,
+        fetch: Fetch,
+    },
+    /// Receives a new note from the stream.
+    Streamed(Arc<models::Note>),
+    StreamClosed(Result<(), Error>),
+    /// Receives whether the note is now a favorite.
+    FavoriteToggled(Result<bool, Error>),
+}
+
+/// How close to the end of the loaded notes older ones start being fetched.
+const PRELOAD_NOTES: usize = 5;
+
+impl TimelineFeed {
+    /// Creates an empty feed. Nothing is fetched until `start` is called.
+    pub fn new(client: Client, timeline: Timeline) -> Self {
+        Self {
+            timeline,
+            client,
+            tasks: Tasks::default(),
+            feed_state: NoteFeedState::default(),
+            pending: Vec::new(),
+            started: false,
+            loading: false,
+            refresh_queued: false,
+            at_end: false,
+            live: false,
+            stop: Arc::default(),
+            status: None,
+        }
+    }
+
+    /// Fetches the newest notes and starts streaming, unless already done.
+    pub fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        self.fetch(Fetch::Newest);
+        self.stream();
+    }
+
+    /// Starts streaming new notes, if the timeline can be followed live.
+    fn stream(&mut self) {
+        let Some((channel, params)) = self.timeline.channel() else {
+            return;
+        };
+        let client = self.client.clone();
+        let stop = self.stop.clone();
+        self.live = true;
+        self.tasks.spawn_with(move |sender| {
+            FeedMessage::StreamClosed(client.stream_notes(channel, params, &stop, |note| {
+                sender.send(FeedMessage::Streamed(note)).is_ok()
+            }))
+        });
+    }
+
+    /// Shows the notes held back, then fetches the ones newer than the loaded notes,
+    /// and goes to the newest. Reconnects the stream if it stopped.
+    pub fn refresh(&mut self) {
+        if self.started && !self.live {
+            self.stream();
+        }
+        self.show_pending();
+        if self.loading {
+            self.refresh_queued = true;
+        } else {
+            self.fetch_newer();
+        }
+        if !self.feed_state.pages.is_empty() {
+            self.feed_state.select(Some(0));
+            self.feed_state.select_first_note();
+        }
+    }
+
+    /// Handles finished requests and streamed notes.
+    pub fn update(&mut self) {
+        for message in self.tasks.drain() {
+            self.message(message);
+        }
+    }
+
+    fn message(&mut self, message: FeedMessage) {
+        match message {
+            FeedMessage::Loaded { result, fetch } => {
+                self.loading = false;
+                match result {
+                    Ok(notes) => {
+                        self.status = None;
+                        self.insert(notes, fetch);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                if mem::take(&mut self.refresh_queued) {
+                    self.fetch_newer();
+                } else {
+                    self.load_around_selection();
+                }
+            }
+            FeedMessage::Streamed(note) => {
+                if self.feed_state.pages.is_empty() || self.is_loaded(&note.id) {
+                    return;
+                }
+                if self.is_at_top() {
+                    self.feed_state.pages[0].1.notes.insert(0, note);
+                } else {
+                    self.pending.insert(0, note);
+                }
+            }
+            FeedMessage::StreamClosed(result) => {
+                self.live = false;
+                if let Err(err) = result {
+                    self.status = Some(format!(
+                        "Live updates stopped: {err}. Refresh to reconnect."
+                    ));
+                }
+            }
+            FeedMessage::FavoriteToggled(result) => {
+                self.status = Some(match result {
+                    Ok(true) => "Added to favorites.".into(),
+                    Ok(false) => "Removed from favorites.".into(),
+                    Err(err) => err.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Puts fetched notes where `fetch` says, leaving out those already loaded.
+    fn insert(&mut self, mut notes: Vec<Arc<models::Note>>, fetch: Fetch) {
+        // a full batch may not reach the notes it was fetched up to.
+        let is_full = notes.len() >= TIMELINE_LIMIT;
+        let fetched = notes.len();
+        if !matches!(fetch, Fetch::Newest) {
+            let loaded: HashSet<&str> = self
+                .notes()
+                .chain(self.pending.iter().map(|note| &**note))
+                .map(|note| note.id.as_str())
+                .collect();
+            notes.retain(|note| !loaded.contains(note.id.as_str()));
+        }
+        let reaches_loaded = notes.len() < fetched;
+        match fetch {
+            Fetch::Newest => {
+                self.feed_state = NoteFeedState {
+                    pages: vec![(
+                        NotePage::new(),
+                        NotePageState::new(self.client.store.clone(), notes),
+                    )],
+                    selected_page_idx: Some(0),
+                };
+                self.pending.clear();
+                self.at_end = !is_full;
+            }
+            Fetch::Older if notes.is_empty() => {
+                self.at_end = true;
+                self.status = Some("No older notes.".into());
+            }
+            Fetch::Older => {
+                self.feed_state.pages.push((
+                    NotePage::new(),
+                    NotePageState::new(self.client.store.clone(), notes),
+                ));
+            }
+            Fetch::Newer if notes.is_empty() => (),
+            Fetch::Newer => {
+                // notes streamed in meanwhile may be newer than some fetched ones, which
+                // go among them instead.
+                let newest = self.notes().next().map(|note| note.created_at);
+                let (notes, older): (Vec<_>, Vec<_>) = notes
+                    .into_iter()
+                    .partition(|note| newest.map_or(true, |newest| note.created_at > newest));
+                let reaches_loaded = reaches_loaded || !older.is_empty();
+                if let Some((_, state)) = self.feed_state.pages.first_mut() {
+                    for note in older {
+                        let idx = state
+                            .notes
+                            .partition_point(|loaded| loaded.created_at > note.created_at);
+                        if state.selected.is_some_and(|selected| selected >= idx) {
+                            state.selected = state.selected.map(|selected| selected + 1);
+                        }
+                        state.notes.insert(idx, note);
+                    }
+                }
+                if notes.is_empty() {
+                    return;
+                }
+                let at_top = self.is_at_top();
+                let mut state = NotePageState::new(self.client.store.clone(), notes);
+                // unless the batch reaches the loaded notes, some between them are missing.
+                state.gap_below = is_full && !reaches_loaded;
+                self.feed_state.pages.insert(0, (NotePage::new(), state));
+                match &mut self.feed_state.selected_page_idx {
+                    _ if at_top => self.feed_state.select(Some(0)),
+                    // keep the same page selected as the others move down.
+                    Some(idx) => *idx += 1,
+                    None => (),
+                }
+            }
+            Fetch::Gap { until_id, .. } => {
+                // the pages may have moved since the gap was asked for.
+                let Some(idx) = self.feed_state.pages.iter().position(|(_, state)| {
+                    state.gap_below && state.notes.last().is_some_and(|note| note.id == until_id)
+                }) else {
+                    return;
+                };
+                if notes.is_empty() {
+                    self.set_gap(idx, false);
+                    return;
+                }
+                let mut state = NotePageState::new(self.client.store.clone(), notes);
+                state.gap_below = is_full;
+                state.selected = None;
+                self.set_gap(idx, false);
+                self.feed_state
+                    .pages
+                    .insert(idx + 1, (NotePage::new(), state));
+                match &mut self.feed_state.selected_page_idx {
+                    Some(selected) if *selected > idx => *selected += 1,
+                    // the gap row was selected, so the first note filling it is.
+                    Some(selected) if *selected == idx => {
+                        *selected += 1;
+                        self.feed_state.select_first_note();
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Fetches the newest notes above the loaded ones, or in their place if none are.
+    fn fetch_newer(&mut self) {
+        self.fetch(if self.notes().next().is_none() {
+            Fetch::Newest
+        } else {
+            Fetch::Newer
+        });
+    }
+
+    /// Fetches the notes `fetch` asks for.
+    fn fetch(&mut self, fetch: Fetch) {
+        if self.loading {
+            return;
+        }
+        let pages = &self.feed_state.pages;
+        let (until_id, since_id) = match &fetch {
+            Fetch::Newest => (None, None),
+            // with only `sinceId`, the oldest notes after it would come instead.
+            Fetch::Newer => (None, None),
+            Fetch::Older => (oldest_id(pages.last()), None),
+            Fetch::Gap { until_id, since_id } => (Some(until_id.clone()), since_id.clone()),
+        };
+        let client = self.client.clone();
+        let timeline = self.timeline.clone();
+
+        self.loading = true;
+        self.tasks.spawn(move || FeedMessage::Loaded {
+            result: client.timeline(&timeline, until_id.as_deref(), since_id.as_deref()),
+            fetch,
+        });
+    }
+
+    fn set_gap(&mut self, page: usize, gap: bool) {
+        if let Some((_, state)) = self.feed_state.pages.get_mut(page) {
+            state.gap_below = gap;
+            if !gap && state.selected == Some(state.notes.len()) {
+                state.selected = state.notes.len().checked_sub(1);
+            }
+        }
+    }
+
+    /// Whether a note is in the feed, or held back.
+    fn is_loaded(&self, id: &str) -> bool {
+        self.notes().any(|note| note.id == id) || self.pending.iter().any(|note| note.id == id)
+    }
+
+    /// Whether the newest note is selected, so new ones can go above it.
+    fn is_at_top(&self) -> bool {
+        self.feed_state.selected_page_idx == Some(0)
+            && self
+                .feed_state
+                .pages
+                .first()
+                .is_some_and(|(_, state)| state.selected.map_or(true, |idx| idx == 0))
+    }
+
+    /// Shows the notes held back above the newest one.
+    fn show_pending(&mut self) {
+        let Some((_, state)) = self.feed_state.pages.first_mut() else {
+            return;
+        };
+        let count = self.pending.len();
+        let mut notes = mem::take(&mut self.pending);
+        notes.append(&mut state.notes);
+        state.notes = notes;
+        state.selected = state.selected.map(|idx| idx + count);
+    }
+
+    /// Selects the next note, going on to the next page at the end of one.
+    ///
+    /// Older notes are fetched when nearing the end of the loaded ones, and missing
+    /// notes when reaching a gap row.
+    pub fn select_next(&mut self) {
+        let Some(page) = self.feed_state.selected_page_idx else {
+            return;
+        };
+        let Some((_, state)) = self.feed_state.pages.get(page) else {
+            return;
+        };
+        let at_page_end = state.selected.map_or(true, |idx| idx + 1 >= state.len());
+        if at_page_end && page + 1 < self.feed_state.pages.len() {
+            self.feed_state.select_next_page();
+            self.feed_state.select_first_note();
+        } else {
+            self.feed_state.select_next_note();
+        }
+        self.load_around_selection();
+    }
+
+    /// Selects the previous note, going back to the previous page at the start of one.
+    /// Above the newest note, shows the notes held back.
+    pub fn select_prev(&mut self) {
+        let Some(page) = self.feed_state.selected_page_idx else {
+            return;
+        };
+        let Some((_, state)) = self.feed_state.pages.get(page) else {
+            return;
+        };
+        if state.selected.map_or(true, |idx| idx > 0) {
+            self.feed_state.select_prev_note();
+        } else if page > 0 {
+            self.feed_state.select_prev_page();
+            self.feed_state.select_last_note();
+        } else if !self.pending.is_empty() {
+            let count = self.pending.len();
+            self.show_pending();
+            if let Some((_, state)) = self.feed_state.pages.first_mut() {
+                state.select(count - 1);
+            }
+        }
+        self.load_around_selection();
+    }
+
+    /// Fetches older notes near the end of the loaded ones, or missing ones on a gap row.
+    fn load_around_selection(&mut self) {
+        if self.loading {
+            return;
+        }
+        let Some(page) = self.feed_state.selected_page_idx else {
+            return;
+        };
+        let pages = &self.feed_state.pages;
+        let Some(current) = pages.get(page) else {
+            return;
+        };
+        let state = &current.1;
+        if state.is_gap_selected() {
+            if let Some(until_id) = oldest_id(Some(current)) {
+                let since_id = newest_id(pages.get(page + 1));
+                self.fetch(Fetch::Gap { until_id, since_id });
+            }
+        } else if page + 1 == self.feed_state.pages.len()
+            && !self.at_end
+            && state.selected.unwrap_or(0) + PRELOAD_NOTES >= state.notes.len()
+        {
+            self.fetch(Fetch::Older);
+        }
+    }
+
+    /// Goes to the next page, fetching older notes if on the last one.
+    pub fn next_page(&mut self) {
+        let last = self.feed_state.pages.len().saturating_sub(1);
+        if self
+            .feed_state
+            .selected_page_idx
+            .map_or(true, |idx| idx < last)
+        {
+            self.feed_state.select_next_page();
+        } else if !self.loading && !self.feed_state.pages.is_empty() {
+            self.fetch(Fetch::Older);
+        }
+    }
+
+    /// Favorites the selected note, or unfavorites it.
+    pub fn toggle_favorite(&mut self) {
+        if let Some(note) = self.selected_note() {
+            let client = self.client.clone();
+            let note_id = note.id.clone();
+            self.tasks
+                .spawn(move || FeedMessage::FavoriteToggled(client.toggle_favorite(&note_id)));
+        }
+    }
+
+    /// Selects the note clicked at `position`, and opens the mention, hashtag or link
+    /// clicked in it: the user's notes, the hashtag's timeline or the browser.
+    pub fn click(&mut self, session: &Session, position: Position) -> Option<Navigation> {
+        let link = self.feed_state.click(position);
+        self.load_around_selection();
+        let screen: Box<dyn Screen + Send + Sync> = match link? {
+            Link::Mention(acct) => Box::new(Search::with_query(
+                session.clone(),
+                &format!("from:@{acct}"),
+            )),
+            Link::Hashtag(tag) => Box::new(TimelineScreen::new(
+                session.clone(),
+                Timeline::Hashtag {
+                    query: TagQuery::tag(&tag),
+                },
+            )),
+            Link::Url(url) => {
+                if let Err(err) = browser::open(&url) {
+                    self.status = Some(err);
+                }
+                return None;
+            }
+        };
+        Some(Navigation::Push(screen))
+    }
+
+    /// Returns the loaded notes, newest first.
+    pub fn notes(&self) -> impl Iterator<Item = &models::Note> {
+        self.feed_state
+            .pages
+            .iter()
+            .flat_map(|(_, state)| state.notes.iter().map(|note| &**note))
+    }
+
+    /// Returns the selected note, if any.
+    pub fn selected_note(&self) -> Option<&models::Note> {
+        let page = self.feed_state.selected_page_idx?;
+        let (_, state) = self.feed_state.pages.get(page)?;
+        state.notes.get(state.selected?).map(|note| &**note)
+    }
+
+    fn status_text(&self) -> String {
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        if self.loading {
+            return "Loading...".into();
+        }
+
+        let page = self.feed_state.selected_page_idx.map_or(0, |idx| idx + 1);
+        let live = if self.live { " / live" } else { "" };
+        let pending = match self.pending.len() {
+            0 => String::new(),
+            1 => "↑ 1 new note / ".into(),
+            count => format!("↑ {count} new notes / "),
+        };
+        format!(
+            "{pending}{} / page {page} of {}{live}",
+            self.timeline.title(),
+            self.feed_state.pages.len()
+        )
+    }
+
+    /// Draws the feed, with its status in the bottom row.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_stateful_widget(NoteFeed::new(), area, &mut self.feed_state);
+
+        let [_, _, status_area] = NoteFeed::areas(area);
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+
+    /// Draws only the selected page, with the status in the bottom row, for a deck column.
+    pub fn render_column(&mut self, frame: &mut Frame, area: Rect) {
+        let [notes_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        if let Some((page, page_state)) = self.feed_state.get_selected_page_mut() {
+            frame.render_stateful_widget(page.clone(), notes_area, page_state);
+        }
+        frame.render_widget(Line::from(self.status_text()), status_area);
+    }
+}
+
+impl Drop for TimelineFeed {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Returns the id of the newest note on a page.
+fn newest_id(page: Option<&(NotePage, NotePageState)>) -> Option<String> {
+    page?.1.notes.first().map(|note| note.id.clone())
+}
+
+/// Returns the id of the oldest note on a page.
+fn oldest_id(page: Option<&(NotePage, NotePageState)>) -> Option<String> {
+    page?.1.notes.last().map(|note| note.id.clone())
+}
+
+/// Columns left in the feed's header for its own text, beside the help.
+const HEADER_TEXT_WIDTH: usize = 20;
+
+/// A single timeline on its own screen, e.g. a hashtag's.
+pub struct TimelineScreen {
+    session: Session,
+    feed: TimelineFeed,
+    keys: Keymap<TimelineAction>,
+    navigation: Option<Navigation>,
+}
+
+pub enum Message {
+    /// Selects the next or previous note in the current page.
+    SetNote(Direction),
+    /// Goes to the next of previous page.
+    SetPage(Direction),
+    /// Selects the first note.
+    JumpToStart,
+    /// Selects the last note.
+    JumpToEnd,
+    /// Selects the note clicked, and opens what was clicked in it.
+    Click(Position),
+    Refresh,
+    /// Adds this timeline to the home screen's tabs, or removes it.
+    TogglePin,
+    /// Replies to the selected note.
+    Reply,
+    /// Quotes the selected note in a new one.
+    Quote,
+    /// Opens the composer, posting in this timeline's channel if it is one.
+    Compose,
+    /// Favorites the selected note, or unfavorites it.
+    ToggleFavorite,
+    /// Adds the selected note to clips or removes it from them.
+    ClipNote,
+    /// Picks one of the selected note's hashtags to open.
+    OpenHashtags,
+    Close,
+}
+
+impl TimelineScreen {
+    /// Creates the screen and starts loading the timeline.
+    pub fn new(session: Session, timeline: Timeline) -> Self {
+        let mut feed = TimelineFeed::new(session.client.clone(), timeline);
+        feed.start();
+        let keys = Keymap::new(&session.config.read().keys.timeline);
+
+        Self {
+            keys,
+            session,
+            feed,
+            navigation: None,
+        }
+    }
+
+    fn message(&mut self, message: Message) {
+        match message {
+            Message::SetNote(Direction::Next) => self.feed.select_next(),
+            Message::SetNote(Direction::Previous) => self.feed.select_prev(),
+            Message::SetPage(Direction::Next) => self.feed.next_page(),
+            Message::SetPage(Direction::Previous) => self.feed.feed_state.select_prev_page(),
+            Message::JumpToStart => self.feed.feed_state.select_first_note(),
+            Message::JumpToEnd => self.feed.feed_state.select_last_note(),
+            Message::Click(position) => {
+                self.navigation = self.feed.click(&self.session, position);
+            }
+            Message::Refresh => self.feed.refresh(),
+            Message::TogglePin => {
+                let timeline = self.feed.timeline.clone();
+                if Timeline::MAIN.contains(&timeline) {
+                    return;
+                }
+                self.session
+                    .update_config(|config| config.toggle_pinned(timeline));
+            }
+            Message::Reply => {
+                if let Some(note) = self.feed.selected_note() {
+                    let compose =
+                        Compose::reply(self.session.clone(), note, &[note.user.clone()], None);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::Quote => {
+                if let Some(note) = self.feed.selected_note() {
+                    let compose = Compose::quote(self.session.clone(), note);
+                    self.navigation = Some(Navigation::Push(Box::new(compose)));
+                }
+            }
+            Message::Compose => {
+                let compose = Compose::for_timeline(self.session.clone(), &self.feed.timeline);
+                self.navigation = Some(Navigation::Push(Box::new(compose)));
+            }
+            Message::ToggleFavorite => self.feed.toggle_favorite(),
+            Message::ClipNote => {
+                if let Some(note) = self.feed.selected_note() {
+                    let clips = Clips::for_note(self.session.clone(), note.id.clone());
+                    self.navigation = Some(Navigation::Push(Box::new(clips)));
+                }
+            }
+            Message::OpenHashtags => {
+                let tags = self
+                    .feed
+                    .selected_note()
+                    .map(|note| note.tags.clone())
+                    .unwrap_or_default();
+                let prompt = HashtagPrompt::new(self.session.clone(), tags);
+                self.navigation = Some(Navigation::Push(Box::new(prompt)));
+            }
+            Message::Close => self.navigation = Some(Navigation::Back),
+        }
+    }
+
+    /// Runs the message an action stands for.
+    fn action(&mut self, action: TimelineAction) {
+        self.message(match action {
+            TimelineAction::NextNote => Message::SetNote(Direction::Next),
+            TimelineAction::PrevNote => Message::SetNote(Direction::Previous),
+            TimelineAction::NextPage => Message::SetPage(Direction::Next),
+            TimelineAction::PrevPage => Message::SetPage(Direction::Previous),
+            TimelineAction::JumpToStart => Message::JumpToStart,
+            TimelineAction::JumpToEnd => Message::JumpToEnd,
+            TimelineAction::Refresh => Message::Refresh,
+            TimelineAction::TogglePin => Message::TogglePin,
+            TimelineAction::Reply => Message::Reply,
+            TimelineAction::Quote => Message::Quote,
+            TimelineAction::OpenHashtags => Message::OpenHashtags,
+            TimelineAction::Compose => Message::Compose,
+            TimelineAction::ToggleFavorite => Message::ToggleFavorite,
+            TimelineAction::ClipNote => Message::ClipNote,
+            TimelineAction::Close => Message::Close,
+        });
+    }
+
+    fn is_pinned(&self) -> bool {
+        self.session
+            .config
+            .read()
+            .pinned_timelines
+            .contains(&self.feed.timeline)
+    }
+}
+
+impl Screen for TimelineScreen {
+    fn view(&mut self, frame: &mut Frame) {
+        self.feed.update();
+
+        let area = frame.area();
+        self.feed.render(frame, area);
+
+        let pin = if self.is_pinned() { "Unpin" } else { "Pin" };
+        let help = self.keys.help(&[
+            (pin, &[TimelineAction::TogglePin]),
+            ("Reply", &[TimelineAction::Reply]),
+            ("Quote", &[TimelineAction::Quote]),
+            ("Compose", &[TimelineAction::Compose]),
+            ("Favorite", &[TimelineAction::ToggleFavorite]),
+            ("Clip", &[TimelineAction::ClipNote]),
+            ("Hashtags", &[TimelineAction::OpenHashtags]),
+            ("Back", &[TimelineAction::Close]),
+        ]);
+        let help = format!(" {help} ");
+        let [header_area, _, _] = NoteFeed::areas(area);
+        let help_area = if header_area.height < 3 {
+            header_area
+        } else {
+            header_area.inner(Margin {
+                horizontal: 1,
+                vertical: 1,
+            })
+        };
+        // narrow terminals drop the help, leaving room for the header's own text.
+        if help.width() + HEADER_TEXT_WIDTH <= help_area.width as usize {
+            frame.render_widget(Line::from(help).right_aligned(), help_area);
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        self.handle_key_event(KeyEvent::from(key));
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(action) = self.keys.action(key) {
+            self.action(action);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::ScrollDown => self.message(Message::SetNote(Direction::Next)),
+            MouseEventKind::ScrollUp => self.message(Message::SetNote(Direction::Previous)),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.message(Message::Click(Position::new(event.column, event.row)));
+            }
+            _ => (),
+        }
+    }
+
+    fn navigation(&mut self) -> Option<Navigation> {
+        self.navigation.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    /// Notes numbered `from` down to `to`, newest first, the number being their id and
+    /// second.
+    fn notes(from: i64, to: i64) -> Vec<Arc<models::Note>> {
+        (to..=from)
+            .rev()
+            .map(|second| {
+                Arc::new(models::Note {
+                    id: second.to_string(),
+                    created_at: DateTime::<Utc>::from_timestamp(second, 0).unwrap(),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn loaded() -> TimelineFeed {
+        let mut feed = TimelineFeed::new(Client::new("example.com", None), Timeline::Home);
+        feed.insert(notes(100, 81), Fetch::Newest);
+        feed
+    }
+
+    fn gaps(feed: &TimelineFeed) -> Vec<(usize, bool)> {
+        feed.feed_state
+            .pages
+            .iter()
+            .map(|(_, state)| (state.notes.len(), state.gap_below))
+            .collect()
+    }
+
+    #[test]
+    fn leaves_a_gap_below_a_full_newer_batch() {
+        let mut feed = loaded();
+        feed.insert(notes(200, 181), Fetch::Newer);
+        assert_eq!(gaps(&feed), [(20, true), (20, false)]);
+        assert_eq!(feed.notes().next().unwrap().id, "200");
+    }
+
+    #[test]
+    fn joins_a_newer_batch_reaching_the_loaded_notes() {
+        let mut feed = loaded();
+        feed.insert(notes(110, 91), Fetch::Newer);
+        assert_eq!(gaps(&feed), [(10, false), (20, false)]);
+
+        let mut feed = loaded();
+        feed.insert(notes(105, 101), Fetch::Newer);
+        assert_eq!(gaps(&feed), [(5, false), (20, false)]);
+    }
+
+    #[test]
+    fn moves_nowhere_in_an_empty_timeline() {
+        let mut feed = TimelineFeed::new(Client::new("example.com", None), Timeline::Home);
+        // as if the first fetch were still going.
+        feed.loading = true;
+        feed.refresh();
+        feed.select_next();
+        feed.select_prev();
+        assert!(!feed.is_at_top());
+        assert_eq!(feed.feed_state.selected_page_idx, None);
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use parking_lot::Mutex;
+
+/// Runs blocking work (mostly API calls) in background threads,
+/// and hands the results back to a screen as messages.
+pub struct Tasks<M> {
+    sender: Sender<M>,
+    // behind a mutex so screens holding this stay `Sync`.
+    receiver: Mutex<Receiver<M>>,
+}
+
+impl<M: Send + 'static> Tasks<M> {
+    /// Runs `task` in a new thread. Its result is returned by a later `drain`.
+    pub fn spawn(&self, task: impl FnOnce() -> M + Send + 'static) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            sender.send(task()).ok();
+        });
+    }
+    /// Like `spawn`, but `task` can also send messages while it runs.
+    /// Sending fails once the receiving screen is gone, which is a sign to stop.
+    pub fn spawn_with(&self, task: impl FnOnce(&Sender<M>) -> M + Send + 'static) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = task(&sender);
+            sender.send(result).ok();
+        });
+    }
+    /// Returns a handle to send messages from elsewhere, e.g. from another screen.
+    pub fn sender(&self) -> Sender<M> {
+        self.sender.clone()
+    }
+    /// Returns the results of every task that finished since the last call.
+    pub fn drain(&self) -> Vec<M> {
+        self.receiver.lock().try_iter().collect()
+    }
+}
+
+impl<M> Default for Tasks<M> {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use parking_lot::RwLock;
+use ratatui::{
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders},
+};
+
+use crate::config::theme::{Palette, ThemeColor};
+
+/// The theme in use, set once the config is loaded.
+static CURRENT: RwLock<Theme> = RwLock::new(Theme::PLAIN);
+
+/// The styles widgets draw with, made from a theme's colors.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    /// The text and background of the whole screen.
+    pub base: Style,
+    pub accent: Style,
+    /// The selected tab or item.
+    pub selection: Style,
+    pub mention: Style,
+    pub hashtag: Style,
+    pub link: Style,
+    pub cw: Style,
+    pub renote: Style,
+    pub border: Style,
+    /// The borders of the selected item.
+    pub selected_border: Style,
+}
+
+impl Theme {
+    /// The terminal's own colors, with the selection reversed.
+    const PLAIN: Self = Self {
+        base: Style::new(),
+        accent: Style::new(),
+        selection: Style::new().add_modifier(Modifier::REVERSED),
+        mention: Style::new(),
+        hashtag: Style::new(),
+        link: Style::new().add_modifier(Modifier::UNDERLINED),
+        cw: Style::new().add_modifier(Modifier::ITALIC),
+        renote: Style::new(),
+        border: Style::new(),
+        selected_border: Style::new(),
+    };
+
+    /// Returns the theme in use.
+    pub fn current() -> Self {
+        *CURRENT.read()
+    }
+
+    /// Switches the theme every widget draws with.
+    pub fn set(theme: Self) {
+        *CURRENT.write() = theme;
+    }
+
+    /// Makes the styles for a theme's colors, keeping the plain ones for colors it leaves out.
+    pub fn new(palette: &Palette) -> Self {
+        let plain = Self::PLAIN;
+        let fg = |style: Style, color: Option<ThemeColor>| match color {
+            Some(ThemeColor(color)) => style.fg(color),
+            None => style,
+        };
+        let bg = |style: Style, color: Option<ThemeColor>| match color {
+            Some(ThemeColor(color)) => style.bg(color),
+            None => style,
+        };
+
+        let base = bg(fg(Style::new(), palette.fg), palette.bg);
+        let selection = match (palette.selection_fg, palette.selection_bg) {
+            (None, None) => plain.selection,
+            (selection_fg, selection_bg) => bg(fg(Style::new(), selection_fg), selection_bg),
+        };
+        let accent = fg(plain.accent, palette.accent);
+
+        Self {
+            base,
+            accent,
+            selection,
+            mention: fg(plain.mention, palette.mention),
+            hashtag: fg(plain.hashtag, palette.hashtag),
+            link: fg(plain.link, palette.link),
+            cw: fg(plain.cw, palette.cw),
+            renote: fg(plain.renote, palette.renote),
+            border: fg(plain.border, palette.border),
+            selected_border: fg(plain.selected_border, palette.accent),
+        }
+    }
+
+    /// Makes an empty block in the base colors, to draw over an area after a `Clear`.
+    pub fn background(&self) -> Block<'static> {
+        Block::new().style(self.base)
+    }
+
+    /// Makes the box around a list item, closed on its sides when it's selected.
+    pub fn item_block(&self, selected: bool) -> Block<'static> {
+        let (borders, style) = if selected {
+            (Borders::ALL, self.selected_border)
+        } else {
+            (Borders::TOP | Borders::BOTTOM, self.border)
+        };
+        Block::new()
+            .borders(borders)
+            .border_type(BorderType::Plain)
+            .border_style(style)
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Datelike, FixedOffset, Local, Utc};
+use parking_lot::RwLock;
+
+use crate::config::time::{TimeConfig, TimeFormat, TimeZone};
+
+/// The time settings in use, set once the config is loaded.
+static CURRENT: RwLock<TimeConfig> = RwLock::new(TimeConfig {
+    format: TimeFormat::Relative,
+    timezone: TimeZone::Local,
+});
+
+/// Switches how times are written.
+pub fn set(config: TimeConfig) {
+    *CURRENT.write() = config;
+}
+
+fn current() -> TimeConfig {
+    *CURRENT.read()
+}
+
+/// Writes a time in the configured format, e.g. `3m` or `2024-10-04 13:37`.
+///
+/// Relative times are worked out from the current time, so they keep up when redrawn.
+pub fn short(time: DateTime<Utc>) -> String {
+    let config = current();
+    match config.format {
+        TimeFormat::Relative => relative(time, Utc::now(), config.timezone),
+        TimeFormat::Absolute => in_zone(time, config.timezone)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+    }
+}
+
+/// Writes the full date and time, e.g. `Fri, Oct 4 2024 13:37:05 +09:00`.
+pub fn full(time: DateTime<Utc>) -> String {
+    in_zone(time, current().timezone)
+        .format("%a, %b %-d %Y %H:%M:%S %:z")
+        .to_string()
+}
+
+/// Writes how long ago `time` was, like `now`, `3m`, `2h` and `5d`,
+/// or its date after a week, like `Oct 4`, with the year if it isn't this one.
+fn relative(time: DateTime<Utc>, now: DateTime<Utc>, zone: TimeZone) -> String {
+    let elapsed = now - time;
+    match elapsed {
+        // times a bit ahead of the clock are also `now`.
+        _ if elapsed.num_minutes() < 1 => "now".into(),
+        _ if elapsed.num_hours() < 1 => format!("{}m", elapsed.num_minutes()),
+        _ if elapsed.num_days() < 1 => format!("{}h", elapsed.num_hours()),
+        _ if elapsed.num_days() < 7 => format!("{}d", elapsed.num_days()),
+        _ => {
+            let time = in_zone(time, zone);
+            if time.year() == in_zone(now, zone).year() {
+                time.format("%b %-d").to_string()
+            } else {
+                time.format("%b %-d, %Y").to_string()
+            }
+        }
+    }
+}
+
+fn in_zone(time: DateTime<Utc>, zone: TimeZone) -> DateTime<FixedOffset> {
+    match zone {
+        TimeZone::Local => time.with_timezone(&Local).fixed_offset(),
+        TimeZone::Utc => time.fixed_offset(),
+        TimeZone::Offset(offset) => time.with_timezone(&offset),
+    }
+}
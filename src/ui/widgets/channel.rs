@@ -0,0 +1,57 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Margin, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+
+use crate::api::channels::Channel;
+use crate::ui::theme::Theme;
+
+/// A channel as an item in a list.
+#[derive(Clone)]
+pub struct ChannelItem<'a> {
+    pub channel: &'a Channel,
+    /// Whether to highlight this item when rendering it.
+    pub selected: bool,
+}
+
+impl<'a> ChannelItem<'a> {
+    /// 1 line of name and 1 of description, plus a box line on each side.
+    pub const HEIGHT: u16 = 4;
+
+    pub fn new(channel: &'a Channel) -> Self {
+        Self {
+            channel,
+            selected: false,
+        }
+    }
+}
+
+impl Widget for ChannelItem<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        let following = if self.channel.is_following {
+            " [following]"
+        } else {
+            ""
+        };
+        let header = format!(
+            "{} ({} users, {} notes){following}",
+            self.channel.name, self.channel.users_count, self.channel.notes_count
+        );
+        buf.set_line(inner.x, inner.y, &Line::from(header), inner.width);
+        if let Some(description) = self
+            .channel
+            .description
+            .as_deref()
+            .and_then(|description| description.lines().next())
+        {
+            buf.set_line(inner.x, inner.y + 1, &Line::from(description), inner.width);
+        }
+
+        Theme::current().item_block(self.selected).render(area, buf);
+    }
+}
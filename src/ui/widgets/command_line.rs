@@ -0,0 +1,167 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    Frame,
+};
+
+use super::text_input::TextInput;
+
+/// How many commands are remembered.
+const MAX_HISTORY: usize = 100;
+
+/// A vim-like command line, typed after `:`, with history and tab completion.
+///
+/// Screens open it, pass it their keys while it's open and run the commands it returns.
+#[derive(Default)]
+pub struct CommandLine {
+    /// The command being typed, if the line is open.
+    input: Option<TextInput>,
+    /// The commands run so far, oldest first.
+    history: Vec<String>,
+    /// The history entry shown, if browsing it.
+    browsing: Option<usize>,
+    /// What was typed before browsing the history, to come back to.
+    typed: String,
+    /// The completions offered for what was typed, and the one shown.
+    completions: Vec<String>,
+    completion: Option<usize>,
+}
+
+impl CommandLine {
+    /// Opens an empty command line.
+    pub fn open(&mut self) {
+        self.input = Some(TextInput::default());
+        self.browsing = None;
+        self.completions.clear();
+        self.completion = None;
+    }
+
+    pub fn close(&mut self) {
+        self.input = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Edits the command, browses the history with Up/Down and completes with Tab.
+    ///
+    /// `complete` lists the full commands the typed one could become.
+    /// Returns the command when Enter is pressed, which also closes the line.
+    /// Esc, Backspace on an empty line or `Ctrl-c` close it without running anything.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        complete: impl FnOnce(&str) -> Vec<String>,
+    ) -> Option<String> {
+        let input = self.input.as_mut()?;
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Enter => {
+                let command = input.text().trim().to_owned();
+                self.input = None;
+                if command.is_empty() {
+                    return None;
+                }
+                self.history.retain(|old| *old != command);
+                self.history.push(command.clone());
+                if self.history.len() > MAX_HISTORY {
+                    self.history.remove(0);
+                }
+                return Some(command);
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Char('c') if ctrl => self.input = None,
+            KeyCode::Backspace if input.is_empty() => self.input = None,
+            KeyCode::Up => self.browse(Direction::Previous),
+            KeyCode::Char('p') if ctrl => self.browse(Direction::Previous),
+            KeyCode::Down => self.browse(Direction::Next),
+            KeyCode::Char('n') if ctrl => self.browse(Direction::Next),
+            KeyCode::Tab => self.complete(Direction::Next, complete),
+            KeyCode::BackTab => self.complete(Direction::Previous, complete),
+            _ => {
+                if input.handle_key(key) {
+                    self.browsing = None;
+                    self.completion = None;
+                }
+            }
+        }
+        None
+    }
+
+    /// Inserts pasted text in the command.
+    pub fn insert(&mut self, text: &str) {
+        if let Some(input) = &mut self.input {
+            input.insert(text);
+            self.browsing = None;
+            self.completion = None;
+        }
+    }
+
+    fn browse(&mut self, direction: Direction) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        let next = match (direction, self.browsing) {
+            (Direction::Previous, None) => self.history.len().checked_sub(1),
+            (Direction::Previous, Some(idx)) => Some(idx.saturating_sub(1)),
+            (Direction::Next, None) => return,
+            (Direction::Next, Some(idx)) => (idx + 1 < self.history.len()).then_some(idx + 1),
+        };
+        if self.browsing.is_none() {
+            self.typed = input.text().to_owned();
+        }
+        match next {
+            Some(idx) => input.set_text(self.history[idx].clone()),
+            None if self.browsing.is_some() => input.set_text(self.typed.clone()),
+            None => (),
+        }
+        self.browsing = next;
+        self.completion = None;
+    }
+
+    /// Shows the next or previous completion, listing them on the first Tab.
+    fn complete(&mut self, direction: Direction, complete: impl FnOnce(&str) -> Vec<String>) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        let len = match self.completion {
+            Some(_) => self.completions.len(),
+            None => {
+                self.completions = complete(input.text());
+                self.completions.len()
+            }
+        };
+        if len == 0 {
+            return;
+        }
+        let next = match (direction, self.completion) {
+            (Direction::Next, None) => 0,
+            (Direction::Previous, None) => len - 1,
+            (Direction::Next, Some(idx)) => (idx + 1) % len,
+            (Direction::Previous, Some(idx)) => (idx + len - 1) % len,
+        };
+        input.set_text(self.completions[next].clone());
+        self.completion = Some(next);
+        self.browsing = None;
+    }
+
+    /// Draws `:` and the command, with the terminal's cursor in it.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        let [prompt_area, input_area] =
+            Layout::horizontal([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(Line::from(":"), prompt_area);
+        input.render(frame, input_area, true);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Previous,
+    Next,
+}
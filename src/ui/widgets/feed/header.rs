@@ -5,6 +5,8 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, StatefulWidget, Widget},
 };
 
+use crate::ui::theme::Theme;
+
 /// A horizontal bar with some information.
 pub struct FeedHeader;
 
@@ -23,16 +25,19 @@ impl StatefulWidget for FeedHeader {
     type State = FeedHeaderState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        if area.height < 4 {
-            Line::from("Bar must be at least 4 lines high.").render(area, buf);
+        let theme = Theme::current();
+        // short terminals only get the text.
+        if area.height < 3 {
+            Line::styled(state.get_status_text(), theme.accent).render(area, buf);
             return;
         }
 
         Block::new()
             .borders(Borders::all())
             .border_type(BorderType::Plain)
+            .border_style(theme.border)
             .render(area, buf);
-        Line::from(state.get_status_text()).render(
+        Line::styled(state.get_status_text(), theme.accent).render(
             area.inner(Margin {
                 horizontal: 1,
                 vertical: 1,
@@ -1,67 +1,86 @@
+pub mod header;
 pub mod note;
 pub mod page;
-pub mod header;
-
-use std::marker::PhantomData;
+pub mod preview;
 
 use header::FeedHeader;
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Direction, Layout, Rect},
-    widgets::StatefulWidget,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    widgets::{StatefulWidget, Widget},
 };
 
+use note::Link;
 use page::{NotePage, NotePageState};
+use preview::NotePreview;
+
+use crate::ui::layout::{self, Breakpoint};
 
 /// A generic, paginated feed of notes.
 #[derive(Default, Clone)]
-pub struct NoteFeed<'a> {
-    _pd: PhantomData<&'a ()>,
-}
+pub struct NoteFeed;
 
-impl NoteFeed<'_> {
+impl NoteFeed {
     pub fn new() -> Self {
-        Self::default()
+        Self
     }
-    pub fn layout() -> Layout {
+    /// Splits a feed's area into its header, notes and status row.
+    /// The header is a single row on short terminals.
+    pub fn areas(area: Rect) -> [Rect; 3] {
+        let header_height = if layout::is_short(area) { 1 } else { 4 };
         Layout::new(
             Direction::Vertical,
             [
-                Constraint::Length(4), // header.
-                Constraint::Fill(1),   // notes.
-                Constraint::Length(1), // status.
+                Constraint::Length(header_height), // header.
+                Constraint::Fill(1),               // notes.
+                Constraint::Length(1),             // status.
             ],
         )
+        .areas(area)
     }
 }
 
-impl<'a> StatefulWidget for NoteFeed<'a> {
-    type State = NoteFeedState<'a>;
+impl StatefulWidget for NoteFeed {
+    type State = NoteFeedState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let layout = Self::layout().split(area);
+        let [header_area, notes_area, _] = Self::areas(area);
 
         let (bar, mut bar_state) = FeedHeader::new_with_state();
-        bar.render(layout[0], buf, &mut bar_state);
-        if let Some((page, page_state)) = state.get_selected_page() {
-            page.clone().render(layout[1], buf, &mut page_state.clone());
+        bar.render(header_area, buf, &mut bar_state);
+        let Some((page, page_state)) = state.get_selected_page_mut() else {
+            return;
+        };
+
+        if Breakpoint::of(area) < Breakpoint::Wide {
+            page.clone().render(notes_area, buf, page_state);
+            return;
+        }
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]).areas(notes_area);
+        page.clone().render(list_area, buf, page_state);
+        let selected = page_state
+            .selected
+            .and_then(|idx| page_state.notes.get(idx));
+        if let Some(note) = selected {
+            NotePreview::new(note).render(preview_area, buf);
         }
     }
 }
 
 #[derive(Default)]
-pub struct NoteFeedState<'a> {
-    pub pages: Vec<(NotePage<'a>, NotePageState<'a>)>,
+pub struct NoteFeedState {
+    pub pages: Vec<(NotePage, NotePageState)>,
     pub selected_page_idx: Option<usize>,
 }
 
-impl<'a> NoteFeedState<'a> {
+impl NoteFeedState {
     /// Gets a mutable reference to the currently selected page, if any.
-    pub fn get_selected_page_mut(&mut self) -> Option<&mut (NotePage<'a>, NotePageState<'a>)> {
+    pub fn get_selected_page_mut(&mut self) -> Option<&mut (NotePage, NotePageState)> {
         self.pages.get_mut(self.selected_page_idx?)
     }
     /// Gets a reference to the currently selected page, if any.
-    pub fn get_selected_page(&self) -> Option<&(NotePage<'a>, NotePageState<'a>)> {
+    pub fn get_selected_page(&self) -> Option<&(NotePage, NotePageState)> {
         self.pages.get(self.selected_page_idx?)
     }
     /// Selects page with given `idx`.
@@ -95,4 +114,26 @@ impl<'a> NoteFeedState<'a> {
             page_state.prev();
         }
     }
+    /// Selects the first note of the current page.
+    pub fn select_first_note(&mut self) {
+        if let Some((_, page_state)) = self.get_selected_page_mut() {
+            page_state.select(0);
+        }
+    }
+    /// Selects the last note of the current page, or its gap row.
+    pub fn select_last_note(&mut self) {
+        if let Some((_, page_state)) = self.get_selected_page_mut() {
+            if !page_state.is_empty() {
+                page_state.select(page_state.len() - 1)
+            }
+        }
+    }
+    /// Selects the note clicked at `position`, and returns the mention, hashtag or link
+    /// clicked in it, if any.
+    pub fn click(&mut self, position: Position) -> Option<Link> {
+        let (_, page_state) = self.get_selected_page_mut()?;
+        let idx = page_state.note_at(position)?;
+        page_state.select(idx);
+        page_state.link_at(position)
+    }
 }
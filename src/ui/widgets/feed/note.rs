@@ -1,65 +1,245 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Margin, Rect};
-use ratatui::text::Line;
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Widget, Wrap};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
+use unicode_width::UnicodeWidthStr;
+
+use crate::api::models;
+use crate::ui::theme::Theme;
+use crate::ui::time;
 
-#[derive(Clone)]
+/// Draws a note of the shared model, in a feed.
+#[derive(Clone, Copy)]
 pub struct Note<'a> {
-    pub author: User,
-    /// A text element to render as content.
-    pub text: Paragraph<'a>,
+    pub note: &'a models::Note,
     /// Whether to highlight this note when rendering it.
     pub selected: bool,
+    /// Whether to draw it without its box and padding, for narrow terminals.
+    pub compact: bool,
 }
 
 impl<'a> Note<'a> {
-    pub fn new<'n>(author: User, text: Option<&'n str>) -> Self
-    where
-        'n: 'a,
-    {
+    pub fn new(note: &'a models::Note) -> Self {
         Self {
-            author,
-            text: text.map_or(
-                Paragraph::new("[no text]").wrap(Wrap { trim: false }),
-                |text| Paragraph::new(text).wrap(Wrap { trim: false }),
-            ),
+            note,
             selected: false,
+            compact: false,
+        }
+    }
+
+    /// Styles and wraps the note's text for a given width, to draw it with `render_layout`.
+    pub fn layout(&self, width: u16) -> NoteLayout {
+        let text = self.paragraph();
+        let lines = text.line_count(self.text_width(width)) as u16;
+        let height = if self.compact {
+            // the header, and a line under the note.
+            lines + 2
+        } else {
+            // 1 for padding and 1 for a box line, on each side (so, 4 extra lines).
+            lines + 4
+        };
+        NoteLayout {
+            width,
+            compact: self.compact,
+            content: self.content_hash(),
+            text,
+            lines,
+            height,
+        }
+    }
+
+    /// Hashes what `paragraph` shows, to tell when a layout is out of date.
+    fn content_hash(&self) -> u64 {
+        let note = self.note;
+        let mut hasher = DefaultHasher::new();
+        note.cw.hash(&mut hasher);
+        note.text.hash(&mut hasher);
+        note.renote
+            .as_ref()
+            .map(|renote| &renote.text)
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn text_width(&self, width: u16) -> u16 {
+        if self.compact {
+            width
+        } else {
+            width.saturating_sub(2)
+        }
+    }
+
+    /// Returns the note's text, or its CW or renoted text, styled and wrapped.
+    pub fn paragraph(&self) -> Paragraph<'static> {
+        let note = self.note;
+        let theme = Theme::current();
+        let text = match (&note.cw, &note.text, &note.renote) {
+            (Some(cw), _, _) => Text::styled(format!("CW: {cw}"), theme.cw),
+            (None, Some(text), _) => styled_text(text, &theme),
+            (None, None, Some(renote)) => match &renote.text {
+                Some(text) => styled_text(&format!("RN: {text}"), &theme).patch_style(theme.renote),
+                None => Text::raw("[no text]"),
+            },
+            (None, None, None) => Text::raw("[no text]"),
+        };
+        Paragraph::new(text).wrap(Wrap { trim: false })
+    }
+
+    /// Returns the line naming the author, and the channel if any.
+    pub fn header(&self) -> Line<'static> {
+        let theme = Theme::current();
+        let user = &self.note.user;
+        let mut header = vec![
+            Span::styled(
+                user.display_name().to_owned(),
+                theme.accent.add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" @{}", user.acct())),
+        ];
+        if let Some(channel) = &self.note.channel {
+            header.push(Span::raw(format!(" · in {}", channel.name)));
+        }
+        Line::from(header)
+    }
+
+    /// Returns when the note was posted and edited, e.g. `3m · edited 1m`.
+    pub fn time_text(&self) -> String {
+        let created = time::short(self.note.created_at);
+        match self.note.updated_at {
+            Some(updated) => format!("{created} · edited {}", time::short(updated)),
+            None => created,
         }
     }
 }
 
-impl<'a> Widget for Note<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let inner = area.inner(Margin {
-            vertical: 1,
-            horizontal: 1,
-        });
+/// A note's text, styled and wrapped at some width, so it's laid out once rather than
+/// on every draw.
+pub struct NoteLayout {
+    width: u16,
+    compact: bool,
+    /// The hash of the note's text it was laid out from.
+    content: u64,
+    text: Paragraph<'static>,
+    /// How many rows the text wraps to.
+    lines: u16,
+    /// How many rows the whole note takes.
+    pub height: u16,
+}
 
-        let header = format!("{} @{}", self.author.name, self.author.username);
+impl NoteLayout {
+    /// Whether it was laid out for the note as it is, drawn this way.
+    pub fn fits(&self, note: &Note, width: u16) -> bool {
+        self.width == width && self.compact == note.compact && self.content == note.content_hash()
+    }
+}
 
-        buf.set_line(inner.x, inner.y, &Line::from(header), inner.width);
-        let text_area = Rect::new(
-            inner.x,
-            inner.y + 2,
-            inner.width,
-            self.text.line_count(inner.width) as u16,
-        );
-        self.text.render(text_area, buf);
+impl Note<'_> {
+    /// Draws the note from its `layout`, made at the width of `area`.
+    pub fn render_layout(self, layout: &NoteLayout, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::current();
+        let inner = if self.compact {
+            area
+        } else {
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 1,
+            })
+        };
 
-        let borders = if self.selected {
-            Borders::ALL
+        // the time goes on the right, cutting the header short if needed.
+        let time = self.time_text();
+        let time_width = match time.width() as u16 {
+            0 => 0,
+            width => (width + 1).min(inner.width),
+        };
+        buf.set_line(inner.x, inner.y, &self.header(), inner.width - time_width);
+        if time_width > 0 {
+            buf.set_line(
+                inner.right() - time_width + 1,
+                inner.y,
+                &Line::from(time),
+                time_width - 1,
+            );
+        }
+        let text_y = if self.compact {
+            inner.y + 1
         } else {
-            Borders::TOP | Borders::BOTTOM
+            inner.y + 2
         };
-        Block::new()
-            .borders(borders)
-            .border_type(BorderType::Plain)
-            .render(area, buf);
+        let text_area = Rect::new(inner.x, text_y, inner.width, layout.lines).intersection(area);
+        (&layout.text).render(text_area, buf);
+
+        if self.compact {
+            let border = if self.selected {
+                buf.set_style(Rect { height: 1, ..inner }, theme.selection);
+                theme.selected_border
+            } else {
+                theme.border
+            };
+            Block::new()
+                .borders(Borders::BOTTOM)
+                .border_style(border)
+                .render(area, buf);
+        } else {
+            theme.item_block(self.selected).render(area, buf);
+        }
     }
 }
 
-#[derive(Clone)]
-pub struct User {
-    pub name: String,
-    pub username: String,
+impl Widget for Note<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let layout = self.layout(area.width);
+        self.render_layout(&layout, area, buf);
+    }
+}
+
+/// Styles the mentions, hashtags and links in a note's text.
+fn styled_text(text: &str, theme: &Theme) -> Text<'static> {
+    let lines = text.lines().map(|line| {
+        let spans = line.split_inclusive(' ').map(|word| {
+            let style = match Link::parse(word.trim_end()) {
+                Some(Link::Mention(_)) => theme.mention,
+                Some(Link::Hashtag(_)) => theme.hashtag,
+                Some(Link::Url(_)) => theme.link,
+                None => Style::new(),
+            };
+            Span::styled(word.to_owned(), style)
+        });
+        Line::from(spans.collect::<Vec<_>>())
+    });
+    Text::from(lines.collect::<Vec<_>>())
+}
+
+/// A mention, hashtag or link in a note, which can be clicked.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Link {
+    /// A user, as `name` or `name@host`.
+    Mention(String),
+    /// A hashtag, without its `#`.
+    Hashtag(String),
+    Url(String),
+}
+
+impl Link {
+    /// Reads a word of a note, leaving out punctuation after it, like the comma in `@foo,`.
+    pub fn parse(word: &str) -> Option<Self> {
+        if word.starts_with("https://") || word.starts_with("http://") {
+            let url = word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '"', '\'']);
+            return Some(Self::Url(url.to_owned()));
+        }
+        let name = |word: &str| {
+            let name = word.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_'));
+            (!name.is_empty()).then(|| name.to_owned())
+        };
+        if let Some(user) = word.strip_prefix('@') {
+            return name(user).map(Self::Mention);
+        }
+        if let Some(tag) = word.strip_prefix('#') {
+            return name(tag).map(Self::Hashtag);
+        }
+        None
+    }
 }
@@ -1,74 +1,308 @@
-use std::marker::PhantomData;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
-use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
-use tui_widget_list::{ListBuilder, ListState, ListView};
+use parking_lot::RwLock;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{StatefulWidget, Widget},
+};
+use unicode_width::UnicodeWidthStr;
 
-use super::note::Note;
+use crate::{
+    api::{models, store::Store},
+    ui::{layout::Breakpoint, theme::Theme},
+};
+
+use super::note::{Link, Note, NoteLayout};
 
 /// A scrollable list of notes.
 #[derive(Default, Clone)]
-pub struct NotePage<'a> {
-    // needed for the lifetime in the StatefulWidget impl.
-    _pd: PhantomData<&'a ()>,
-}
+pub struct NotePage;
 
-impl NotePage<'_> {
+impl NotePage {
     pub fn new() -> Self {
-        Self { _pd: PhantomData }
-    }
-    /// Returns a `ListView` component with this page's notes.
-    fn list_view<'a>(
-        &self,
-        notes: &'a [Note<'_>],
-        width: u16,
-        selected_note: Option<usize>,
-    ) -> ListView<'a, Note<'a>> {
-        let builder = ListBuilder::new(move |ctx| {
-            let mut note = notes
-                .get(ctx.index)
-                .expect("beep boop, indexing failed because of bad list size. this should never happen.")
-                .clone();
-            if let Some(selected_note) = selected_note {
-                if selected_note == ctx.index {
-                    note.selected = true;
-                }
+        Self
+    }
+}
+
+enum Row<'a> {
+    Note(Note<'a>, Arc<NoteLayout>),
+    Gap(GapRow),
+}
+
+impl Row<'_> {
+    fn height(&self) -> u16 {
+        match self {
+            Self::Note(_, layout) => layout.height,
+            Self::Gap(gap) => gap.height(),
+        }
+    }
+
+    /// Draws the rows of the row from `hidden` on in `area`. A row cut off by the page's
+    /// edges is drawn whole off screen first, and its visible rows copied over.
+    fn render_from(self, hidden: u16, area: Rect, buf: &mut Buffer) {
+        let height = self.height();
+        if hidden == 0 && area.height == height {
+            return self.render(area, buf);
+        }
+        let whole = Rect { height, ..area };
+        let mut hidden_buf = Buffer::empty(whole);
+        self.render(whole, &mut hidden_buf);
+        for y in 0..area.height {
+            for x in area.left()..area.right() {
+                buf[(x, area.y + y)] = hidden_buf[(x, whole.y + hidden + y)].clone();
+            }
+        }
+    }
+
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Self::Note(note, layout) => note.render_layout(&layout, area, buf),
+            Self::Gap(gap) => gap.render(area, buf),
+        }
+    }
+}
+
+/// The layouts of a page's notes, by note id.
+#[derive(Default, Clone)]
+struct Layouts(HashMap<String, Arc<NoteLayout>>);
+
+impl Layouts {
+    /// Gets a note's layout at a given width, laying it out again only if it was drawn
+    /// another way or its text changed since.
+    fn get(&mut self, note: Note, width: u16) -> Arc<NoteLayout> {
+        if let Some(layout) = self.0.get(&note.note.id) {
+            if layout.fits(&note, width) {
+                return layout.clone();
             }
-            // 1 for padding and 1 for a box line, on each side (so, 4 extra lines).
-            let note_height = note.text.line_count(width) + 4;
+        }
+        let layout = Arc::new(note.layout(width));
+        self.0.insert(note.note.id.clone(), layout.clone());
+        layout
+    }
+
+    /// Forgets the layouts of notes no longer in `notes`, once there are more than them.
+    fn prune(&mut self, notes: &[Arc<models::Note>]) {
+        if self.0.len() <= notes.len() {
+            return;
+        }
+        let ids: HashSet<&str> = notes.iter().map(|note| note.id.as_str()).collect();
+        self.0.retain(|id, _| ids.contains(id.as_str()));
+    }
+}
+
+/// The row after a page's notes standing for the notes missing below them.
+struct GapRow {
+    selected: bool,
+    compact: bool,
+}
+
+impl GapRow {
+    const TEXT: &'static str = "Load missing notes";
 
-            (note, note_height as u16)
-        });
+    fn height(&self) -> u16 {
+        if self.compact {
+            1
+        } else {
+            3
+        }
+    }
+}
 
-        ListView::new(builder, notes.len())
+impl Widget for GapRow {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::current();
+        let style = if self.selected {
+            theme.selection
+        } else {
+            theme.accent
+        };
+        if self.compact {
+            Line::styled(Self::TEXT, style).centered().render(area, buf);
+            return;
+        }
+        let block = theme.item_block(self.selected);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Line::styled(Self::TEXT, style)
+            .centered()
+            .render(inner, buf);
     }
 }
 
-impl<'a> StatefulWidget for NotePage<'a> {
-    type State = NotePageState<'a>;
+impl StatefulWidget for NotePage {
+    type State = NotePageState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let list_view = self.list_view(&state.notes, area.width, state.selected);
-        let mut list_state = ListState::default();
-        list_state.select(state.selected);
+        state.sync();
+        state.compact = Breakpoint::of(area) == Breakpoint::Narrow;
+        state.layouts.prune(&state.notes);
+        state.scroll(area);
 
-        list_view.render(area, buf, &mut list_state);
+        let mut drawn = Vec::new();
+        let mut y = area.y;
+        let mut index = state.offset;
+        while y < area.bottom() && index < state.len() {
+            let hidden = if index == state.offset {
+                state.top_cut
+            } else {
+                0
+            };
+            let row = state.row(index, area.width);
+            let row_area = Rect {
+                y,
+                height: (row.height() - hidden).min(area.bottom() - y),
+                ..area
+            };
+            row.render_from(hidden, row_area, buf);
+            drawn.push(DrawnNote {
+                index,
+                area: row_area,
+                hidden,
+            });
+            y = row_area.bottom();
+            index += 1;
+        }
+        state.drawn = drawn;
     }
 }
 
+/// Where a note was last drawn, to find what's under the mouse.
+#[derive(Clone, Copy)]
+struct DrawnNote {
+    index: usize,
+    /// The visible part of the note.
+    area: Rect,
+    /// How many of its rows are scrolled out above `area`.
+    hidden: u16,
+}
+
 #[derive(Default, Clone)]
-pub struct NotePageState<'a> {
-    /// The notes on this page.
-    pub notes: Vec<Note<'a>>,
+pub struct NotePageState {
+    /// The notes on this page, shared with the store.
+    pub notes: Vec<Arc<models::Note>>,
+    /// Where the notes come from, to draw their latest copies.
+    store: Arc<RwLock<Store>>,
+    /// The store's revision when the notes were last synced with it.
+    revision: u64,
     /// The index of the currently selected note, if any.
+    /// It's `notes.len()` when the gap row is selected.
     pub selected: Option<usize>,
+    /// Whether notes are missing between this page and the next, shown as a row after
+    /// the notes.
+    pub gap_below: bool,
+    /// Where the notes were last drawn.
+    drawn: Vec<DrawnNote>,
+    /// Whether the notes were last drawn compact.
+    compact: bool,
+    /// The notes' layouts, kept until they're drawn at another width or change.
+    layouts: Layouts,
+    /// The index of the first row drawn.
+    offset: usize,
+    /// How many rows of the first row drawn are scrolled out above the page.
+    top_cut: u16,
 }
 
-impl<'a> NotePageState<'a> {
-    /// Initialises a state with some `notes`, and the first one selected.
-    pub fn new(notes: Vec<Note<'a>>) -> Self {
+impl NotePageState {
+    /// Initialises a state with some `notes` from `store`, and the first one selected.
+    pub fn new(store: Arc<RwLock<Store>>, notes: Vec<Arc<models::Note>>) -> Self {
         Self {
             notes,
+            store,
+            revision: 0,
             selected: Some(0),
+            gap_below: false,
+            drawn: Vec::new(),
+            compact: false,
+            layouts: Layouts::default(),
+            offset: 0,
+            top_cut: 0,
+        }
+    }
+    /// Swaps in the store's copies of the notes updated since the last sync.
+    fn sync(&mut self) {
+        let store = self.store.read();
+        if store.revision() == self.revision {
+            return;
+        }
+        self.revision = store.revision();
+        for note in &mut self.notes {
+            if let Some(stored) = store.note(&note.id) {
+                *note = stored;
+            }
+        }
+    }
+    /// Returns the row at `index`, laid out at `width`.
+    fn row(&mut self, index: usize, width: u16) -> Row<'_> {
+        let selected = self.selected == Some(index);
+        match self.notes.get(index) {
+            Some(note) => {
+                let note = Note {
+                    note,
+                    selected,
+                    compact: self.compact,
+                };
+                let layout = self.layouts.get(note, width);
+                Row::Note(note, layout)
+            }
+            None => Row::Gap(GapRow {
+                selected,
+                compact: self.compact,
+            }),
+        }
+    }
+    /// Scrolls as little as needed for the selected row to be drawn whole in `area`,
+    /// or from its top if it's taller. Only the rows passed by are laid out.
+    fn scroll(&mut self, area: Rect) {
+        let len = self.len();
+        if self.offset >= len {
+            self.offset = len.saturating_sub(1);
+            self.top_cut = 0;
+        }
+        if len == 0 || area.is_empty() {
+            return;
+        }
+        let height = u32::from(area.height);
+        let row_height = |state: &mut Self, index| u32::from(state.row(index, area.width).height());
+        self.top_cut = self
+            .top_cut
+            .min(row_height(self, self.offset).saturating_sub(1) as u16);
+        let Some(selected) = self.selected.filter(|&idx| idx < len) else {
+            return;
+        };
+
+        if selected < self.offset || (selected == self.offset && self.top_cut > 0) {
+            self.offset = selected;
+            self.top_cut = 0;
+            return;
+        }
+        // whether it already ends above the bottom.
+        let mut end = 0;
+        for index in self.offset..=selected {
+            end += row_height(self, index);
+            if end > height + u32::from(self.top_cut) {
+                break;
+            }
+        }
+        if end <= height + u32::from(self.top_cut) {
+            return;
+        }
+
+        // otherwise it goes at the bottom, under the rows above that fit.
+        let mut top = selected;
+        let mut used = row_height(self, selected);
+        while top > 0 && used < height {
+            top -= 1;
+            used += row_height(self, top);
+        }
+        self.offset = top;
+        self.top_cut = used.saturating_sub(height) as u16;
+        if top == selected {
+            self.top_cut = 0;
         }
     }
     /// Sets the selected item to a given `idx`.
@@ -76,11 +310,22 @@ impl<'a> NotePageState<'a> {
     pub fn select(&mut self, idx: usize) {
         self.selected = Some(idx);
     }
-    /// Selects the next note.
+    /// Returns how many rows the page has: its notes, and the gap row if any.
+    pub fn len(&self) -> usize {
+        self.notes.len() + usize::from(self.gap_below)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Whether the gap row is selected.
+    pub fn is_gap_selected(&self) -> bool {
+        self.gap_below && self.selected == Some(self.notes.len())
+    }
+    /// Selects the next note, or the gap row after the last one.
     /// Does nothing if at the end.
     pub fn next(&mut self) {
         if let Some(idx) = self.selected {
-            if self.notes.get(idx + 1).is_some() {
+            if idx + 1 < self.len() {
                 self.select(idx + 1);
             }
         }
@@ -98,13 +343,64 @@ impl<'a> NotePageState<'a> {
             }
         }
     }
-}
+    /// Finds the index of the note drawn at a position on screen.
+    pub fn note_at(&self, position: Position) -> Option<usize> {
+        self.drawn_at(position).map(|drawn| drawn.index)
+    }
+    /// Finds the mention, hashtag or link drawn at a position on screen.
+    pub fn link_at(&self, position: Position) -> Option<Link> {
+        let drawn = self.drawn_at(position)?;
+        let note = Note {
+            note: self.notes.get(drawn.index)?,
+            selected: self.selected == Some(drawn.index),
+            compact: self.compact,
+        };
+        let layout = self.layouts.0.get(&note.note.id)?;
 
-impl<'a> From<&Vec<Note<'a>>> for NotePageState<'a> {
-    fn from(notes: &Vec<Note<'a>>) -> Self {
-        Self {
-            notes: notes.clone(),
-            selected: None,
+        // draws the note again on its own, to read the word under the position.
+        let area = Rect::new(0, 0, drawn.area.width, layout.height);
+        let mut buf = Buffer::empty(area);
+        note.render_layout(layout, area, &mut buf);
+
+        let row = position.y - drawn.area.y + drawn.hidden;
+        let column = position.x - drawn.area.x;
+        // leaves out the borders, which would join the words next to them.
+        let (left, right) = if self.compact {
+            (0, area.width)
+        } else {
+            (1, area.width.saturating_sub(1))
+        };
+        let mut cells = Vec::new();
+        let mut x = left;
+        while x < right {
+            let symbol = buf[(x, row)].symbol();
+            cells.push((x, symbol));
+            // wide characters are followed by blank cells they cover.
+            x += (symbol.width() as u16).max(1);
         }
+
+        let at = cells
+            .iter()
+            .rposition(|&(x, _)| x <= column)
+            .filter(|&idx| !cells[idx].1.trim().is_empty())?;
+        let is_blank = |&(_, symbol): &(u16, &str)| symbol.trim().is_empty();
+        let start = cells[..at]
+            .iter()
+            .rposition(is_blank)
+            .map_or(0, |idx| idx + 1);
+        let end = cells[at..]
+            .iter()
+            .position(is_blank)
+            .map_or(cells.len(), |idx| at + idx);
+        let word: String = cells[start..end]
+            .iter()
+            .map(|(_, symbol)| *symbol)
+            .collect();
+        Link::parse(&word)
+    }
+    fn drawn_at(&self, position: Position) -> Option<&DrawnNote> {
+        self.drawn
+            .iter()
+            .find(|drawn| drawn.area.contains(position))
     }
 }
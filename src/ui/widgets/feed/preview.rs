@@ -0,0 +1,116 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Widget},
+};
+
+use crate::{
+    api::models,
+    ui::{theme::Theme, time},
+};
+
+use super::note::Note;
+
+/// A note in full, shown beside the list on wide terminals.
+pub struct NotePreview<'a> {
+    note: &'a models::Note,
+}
+
+impl<'a> NotePreview<'a> {
+    pub fn new(note: &'a models::Note) -> Self {
+        Self { note }
+    }
+
+    /// Lists the files, poll, reactions and counts shown under the text.
+    fn details(&self) -> Vec<Line<'static>> {
+        let note = self.note;
+        let mut lines = Vec::new();
+        for file in &note.files {
+            let sensitive = if file.is_sensitive {
+                " (sensitive)"
+            } else {
+                ""
+            };
+            lines.push(Line::from(format!(
+                "File: {} [{}]{sensitive}",
+                file.name, file.file_type
+            )));
+        }
+        if let Some(poll) = &note.poll {
+            for choice in &poll.choices {
+                let voted = if choice.is_voted { " ✓" } else { "" };
+                lines.push(Line::from(format!(
+                    "Poll: {} ({}){voted}",
+                    choice.text, choice.votes
+                )));
+            }
+            if let Some(expires_at) = poll.expires_at {
+                lines.push(Line::from(format!("Poll ends {}", time::full(expires_at))));
+            }
+        }
+        if !note.reactions.is_empty() {
+            let reactions: Vec<String> = note
+                .reactions
+                .iter()
+                .map(|reaction| {
+                    let mine = if note.my_reaction.as_ref() == Some(&reaction.emoji) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    format!("{}{mine} {}", reaction.emoji, reaction.count)
+                })
+                .collect();
+            lines.push(Line::from(reactions.join("  ")));
+        }
+        if note.replies_count > 0 || note.renote_count > 0 {
+            lines.push(Line::from(format!(
+                "{} replies · {} renotes",
+                note.replies_count, note.renote_count
+            )));
+        }
+        lines
+    }
+}
+
+impl Widget for NotePreview<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::current();
+        Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .border_style(theme.border)
+            .title_top("Note")
+            .render(area, buf);
+
+        let inner = area.inner(Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+        if inner.is_empty() {
+            return;
+        }
+        let note = Note::new(self.note);
+        let mut lines = vec![
+            note.header(),
+            Line::from(format!("Posted {}", time::full(self.note.created_at))),
+        ];
+        if let Some(updated_at) = self.note.updated_at {
+            lines.push(Line::from(format!("Edited {}", time::full(updated_at))));
+        }
+        let text = note.paragraph();
+        let text_height = text.line_count(inner.width) as u16;
+        let [lines_area, _, text_area, _, details_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(text_height),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        Text::from(lines).render(lines_area, buf);
+        text.render(text_area, buf);
+        Text::from(self.details()).render(details_area, buf);
+    }
+}
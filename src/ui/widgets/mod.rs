@@ -1 +1,7 @@
+pub mod channel;
+pub mod command_line;
 pub mod feed;
+pub mod notification;
+pub mod text_area;
+pub mod text_input;
+pub mod user;
@@ -0,0 +1,132 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Margin, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+
+use crate::api::{
+    models::User,
+    notifications::{Notification, NotificationType},
+};
+use crate::ui::theme::Theme;
+
+/// A notification, or a group of them, as an item in a list.
+#[derive(Clone)]
+pub struct NotificationItem<'a> {
+    pub notification: &'a Notification,
+    /// Whether to highlight this item when rendering it.
+    pub selected: bool,
+}
+
+impl<'a> NotificationItem<'a> {
+    /// 1 line of summary and 1 of note excerpt, plus a box line on each side.
+    pub const HEIGHT: u16 = 4;
+
+    pub fn new(notification: &'a Notification) -> Self {
+        Self {
+            notification,
+            selected: false,
+        }
+    }
+
+    /// Describes what happened, e.g. "alice, bob and 12 others reacted ⭐ to your note".
+    pub fn summary(&self) -> String {
+        let n = self.notification;
+        let name = n.user.as_ref().map_or("Someone", User::display_name);
+
+        match n.kind {
+            NotificationType::Note => format!("{name} posted a note"),
+            NotificationType::Follow => format!("{name} followed you"),
+            NotificationType::Mention => format!("{name} mentioned you"),
+            NotificationType::Reply => format!("{name} replied to you"),
+            NotificationType::Renote => format!("{name} renoted your note"),
+            NotificationType::Quote => format!("{name} quoted your note"),
+            NotificationType::Reaction => format!(
+                "{name} reacted {} to your note",
+                n.reaction.as_deref().map_or("".into(), reaction_label)
+            ),
+            NotificationType::PollEnded => "A poll has ended".into(),
+            NotificationType::ReceiveFollowRequest => format!("{name} wants to follow you"),
+            NotificationType::FollowRequestAccepted => {
+                format!("{name} accepted your follow request")
+            }
+            NotificationType::RoleAssigned => "You were assigned a role".into(),
+            NotificationType::AchievementEarned => format!(
+                "Achievement earned: {}",
+                n.achievement.as_deref().unwrap_or("?")
+            ),
+            NotificationType::App => n.body.clone().unwrap_or_else(|| "App notification".into()),
+            NotificationType::Test => "Test notification".into(),
+            NotificationType::ReactionGrouped => {
+                let users: Vec<&User> = n.reactions.iter().map(|r| &r.user).collect();
+                let mut reactions: Vec<String> = Vec::new();
+                for reaction in &n.reactions {
+                    let label = reaction_label(&reaction.reaction);
+                    if !reactions.contains(&label) {
+                        reactions.push(label);
+                    }
+                }
+                format!(
+                    "{} reacted {} to your note",
+                    list_names(&users),
+                    reactions.join(" ")
+                )
+            }
+            NotificationType::RenoteGrouped => {
+                let users: Vec<&User> = n.users.iter().collect();
+                format!("{} renoted your note", list_names(&users))
+            }
+            NotificationType::Unknown => "Unsupported notification".into(),
+        }
+    }
+
+    /// The first line of the related note, if any.
+    pub fn excerpt(&self) -> Option<String> {
+        let note = self.notification.note.as_ref()?;
+        let text = note.cw.as_deref().or(note.text.as_deref()).or(note
+            .renote
+            .as_ref()
+            .and_then(|renote| renote.text.as_deref()))?;
+
+        text.lines().next().map(str::to_owned)
+    }
+}
+
+impl Widget for NotificationItem<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        buf.set_line(inner.x, inner.y, &Line::from(self.summary()), inner.width);
+        if let Some(excerpt) = self.excerpt() {
+            buf.set_line(inner.x, inner.y + 1, &Line::from(excerpt), inner.width);
+        }
+
+        Theme::current().item_block(self.selected).render(area, buf);
+    }
+}
+
+/// Joins display names like "alice", "alice and bob" or "alice, bob and 12 others".
+fn list_names(users: &[&User]) -> String {
+    let names: Vec<&str> = users.iter().map(|user| user.display_name()).collect();
+
+    match names.as_slice() {
+        [] => "Someone".into(),
+        [one] => (*one).into(),
+        [first, second] => format!("{first} and {second}"),
+        [first, second, third] => format!("{first}, {second} and {third}"),
+        [first, second, rest @ ..] => format!("{first}, {second} and {} others", rest.len()),
+    }
+}
+
+/// Shortens custom emoji reactions, e.g. `:blobcat@.:` to `:blobcat:`.
+fn reaction_label(reaction: &str) -> String {
+    match reaction
+        .strip_prefix(':')
+        .and_then(|name| name.strip_suffix(':'))
+    {
+        Some(name) => format!(":{}:", name.split('@').next().unwrap_or(name)),
+        None => reaction.into(),
+    }
+}
@@ -0,0 +1,256 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    text::{Line, Text},
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::text_input::TextInput;
+
+/// Several lines of editable text, wrapped to the width it's drawn at.
+///
+/// Edits like a [`TextInput`], with Enter starting a new line and Up/Down moving between rows.
+#[derive(Default, Clone)]
+pub struct TextArea {
+    input: TextInput,
+    /// The width the text was last wrapped at, to move between rows.
+    width: usize,
+    /// How many rows are scrolled out at the top.
+    scroll: usize,
+}
+
+/// A wrapped row of text, as byte offsets.
+struct Row {
+    start: usize,
+    end: usize,
+}
+
+impl TextArea {
+    /// Creates an area holding `text`, with the cursor at its end.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            input: TextInput::new(text),
+            ..Self::default()
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Where the cursor is, in bytes.
+    pub fn cursor(&self) -> usize {
+        self.input.cursor()
+    }
+
+    /// Replaces the text, moving the cursor to its end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.input.set_text(text);
+    }
+
+    /// Replaces the text between `start` and the cursor, e.g. to complete a word.
+    pub fn replace_before_cursor(&mut self, start: usize, replacement: &str) {
+        self.input.replace_before_cursor(start, replacement);
+    }
+
+    /// Inserts text at the cursor, keeping newlines.
+    pub fn insert(&mut self, text: &str) {
+        self.input
+            .insert_raw(&text.replace("\r\n", "\n").replace('\r', "\n"));
+    }
+
+    /// Applies an editing or moving key.
+    /// Returns whether the key was one, as others are left to the screen.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter => self.input.insert_raw("\n"),
+            KeyCode::Up => self.move_rows(-1),
+            KeyCode::Down => self.move_rows(1),
+            _ => return self.input.handle_key(key),
+        }
+        true
+    }
+
+    /// Moves the cursor up or down by rows, staying in about the same column.
+    fn move_rows(&mut self, by: isize) {
+        let rows = self.rows(self.width);
+        let text = self.text();
+        let current = cursor_row(&rows, self.cursor());
+        let column = text[rows[current].start..self.cursor()].width();
+        let Some(target) = current
+            .checked_add_signed(by)
+            .and_then(|target| rows.get(target))
+        else {
+            return;
+        };
+
+        let mut cursor = target.start;
+        let mut width = 0;
+        for (idx, grapheme) in text[target.start..target.end].grapheme_indices(true) {
+            width += grapheme.width();
+            if width > column {
+                break;
+            }
+            cursor = target.start + idx + grapheme.len();
+        }
+        self.input.set_cursor(cursor);
+    }
+
+    /// Splits the text into rows at newlines and wherever it's wider than `width`.
+    fn rows(&self, width: usize) -> Vec<Row> {
+        let text = self.text();
+        let width = width.max(1);
+        let mut rows = Vec::new();
+        let mut line_start = 0;
+
+        for line in text.split('\n') {
+            let mut row_start = line_start;
+            let mut row_width = 0;
+            for (idx, grapheme) in line.grapheme_indices(true) {
+                let grapheme_width = grapheme.width();
+                if row_width + grapheme_width > width && row_width > 0 {
+                    rows.push(Row {
+                        start: row_start,
+                        end: line_start + idx,
+                    });
+                    row_start = line_start + idx;
+                    row_width = 0;
+                }
+                row_width += grapheme_width;
+            }
+            rows.push(Row {
+                start: row_start,
+                end: line_start + line.len(),
+            });
+            line_start += line.len() + 1;
+        }
+        rows
+    }
+
+    /// Draws the wrapped text, scrolled to keep the cursor in view.
+    /// Shows the terminal's cursor if `focused`.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        // a column is kept for the cursor at the end of a full row.
+        self.width = area.width.saturating_sub(1).max(1) as usize;
+        let height = area.height as usize;
+        let rows = self.rows(self.width);
+        let current = cursor_row(&rows, self.cursor());
+
+        if current < self.scroll {
+            self.scroll = current;
+        } else if height > 0 && current >= self.scroll + height {
+            self.scroll = current + 1 - height;
+        }
+
+        let text = self.text();
+        let lines: Vec<Line> = rows
+            .iter()
+            .skip(self.scroll)
+            .take(height)
+            .map(|row| Line::from(text[row.start..row.end].to_owned()))
+            .collect();
+        frame.render_widget(Text::from(lines), area);
+
+        if focused && height > 0 {
+            let column = text[rows[current].start..self.cursor()].width();
+            frame.set_cursor_position((
+                area.x + column.min(self.width) as u16,
+                area.y + (current - self.scroll) as u16,
+            ));
+        }
+    }
+}
+
+/// Finds the row the cursor is on, the later one when it's at the edge between two.
+fn cursor_row(rows: &[Row], cursor: usize) -> usize {
+    rows.iter()
+        .rposition(|row| row.start <= cursor)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, layout::Position, Terminal};
+
+    use super::*;
+
+    fn press(area: &mut TextArea, code: KeyCode) {
+        area.handle_key(KeyEvent::from(code));
+    }
+
+    fn ctrl(area: &mut TextArea, c: char) {
+        area.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    /// Draws the text in a `width` by `height` area, returning where the cursor is.
+    fn render(area: &mut TextArea, width: u16, height: u16) -> Position {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| area.render(frame, frame.area(), true))
+            .unwrap();
+        terminal.get_cursor_position().unwrap()
+    }
+
+    #[test]
+    fn deletes_whole_graphemes() {
+        let mut area = TextArea::new("a\nno\u{303}");
+        press(&mut area, KeyCode::Backspace);
+        assert_eq!(area.text(), "a\nn");
+        area.insert("\u{1f3f3}\u{fe0f}\u{200d}\u{1f308}");
+        press(&mut area, KeyCode::Backspace);
+        assert_eq!(area.text(), "a\nn");
+    }
+
+    #[test]
+    fn kills_within_the_line() {
+        let mut area = TextArea::new("first line\nsecond line");
+        ctrl(&mut area, 'w');
+        assert_eq!(area.text(), "first line\nsecond ");
+        ctrl(&mut area, 'u');
+        assert_eq!(area.text(), "first line\n");
+        press(&mut area, KeyCode::Up);
+        ctrl(&mut area, 'a');
+        ctrl(&mut area, 'k');
+        assert_eq!(area.text(), "\n");
+    }
+
+    #[test]
+    fn moves_between_rows_by_display_width() {
+        let mut area = TextArea::new("日本語\nabcdef");
+        assert_eq!(render(&mut area, 10, 3), Position::new(6, 1));
+        press(&mut area, KeyCode::Up);
+        assert_eq!(area.cursor(), "日本語".len());
+
+        // the column falls inside `本`, so the cursor stops before it.
+        press(&mut area, KeyCode::Down);
+        press(&mut area, KeyCode::Home);
+        for _ in 0..3 {
+            press(&mut area, KeyCode::Right);
+        }
+        press(&mut area, KeyCode::Up);
+        assert_eq!(area.cursor(), "日".len());
+        assert_eq!(render(&mut area, 10, 3), Position::new(2, 0));
+    }
+
+    #[test]
+    fn wraps_wide_characters() {
+        // four columns fit, so `語` goes on the next row.
+        let mut area = TextArea::new("日本語");
+        assert_eq!(render(&mut area, 5, 3), Position::new(2, 1));
+        press(&mut area, KeyCode::Up);
+        assert_eq!(area.cursor(), "日".len());
+    }
+
+    #[test]
+    fn starts_new_lines_on_enter() {
+        let mut area = TextArea::new("ab");
+        press(&mut area, KeyCode::Left);
+        press(&mut area, KeyCode::Enter);
+        assert_eq!(area.text(), "a\nb");
+        area.insert("c\r\nd");
+        assert_eq!(area.text(), "a\nc\ndb");
+    }
+}
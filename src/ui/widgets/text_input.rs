@@ -0,0 +1,319 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{layout::Rect, text::Line, Frame};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A line of editable text with a cursor, edited by grapheme and with readline keys.
+///
+/// Only keeps the text: screens draw it with [`TextInput::render`] and read it back with
+/// [`TextInput::text`].
+#[derive(Default, Clone)]
+pub struct TextInput {
+    text: String,
+    /// Where the cursor is, in bytes. Always on a grapheme boundary.
+    cursor: usize,
+    /// The last text deleted by a kill (`Ctrl-k`, `Ctrl-u`, `Ctrl-w`...), for `Ctrl-y`.
+    killed: String,
+    /// How many columns are scrolled out on the left.
+    scroll: usize,
+    /// Whether to show every grapheme as `*`, e.g. for passwords.
+    masked: bool,
+}
+
+impl TextInput {
+    /// Creates an input holding `text`, with the cursor at its end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            cursor: text.len(),
+            text,
+            ..Self::default()
+        }
+    }
+
+    /// Creates an empty input that hides what's typed.
+    pub fn masked() -> Self {
+        Self {
+            masked: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Where the cursor is, in bytes.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replaces the text, moving the cursor to its end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+    }
+
+    /// Replaces the text between `start` and the cursor, e.g. to complete a word.
+    pub fn replace_before_cursor(&mut self, start: usize, replacement: &str) {
+        self.text.replace_range(start..self.cursor, replacement);
+        self.cursor = start + replacement.len();
+    }
+
+    /// Inserts text at the cursor. Newlines become spaces.
+    pub fn insert(&mut self, text: &str) {
+        let text = text.replace(['\r', '\n'], " ");
+        self.insert_raw(&text);
+    }
+
+    /// Inserts text at the cursor as is.
+    pub(super) fn insert_raw(&mut self, text: &str) {
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Moves the cursor to a byte offset, which must be on a grapheme boundary.
+    pub(super) fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.text.len());
+    }
+
+    /// Applies an editing or moving key.
+    /// Returns whether the key was one, as others are left to the screen.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = self.line_start(),
+            KeyCode::Char('e') if ctrl => self.cursor = self.line_end(),
+            KeyCode::Char('b') if ctrl => self.cursor = self.prev_grapheme(),
+            KeyCode::Char('f') if ctrl => self.cursor = self.next_grapheme(),
+            KeyCode::Char('b') if alt => self.cursor = self.prev_word(),
+            KeyCode::Char('f') if alt => self.cursor = self.next_word(),
+            KeyCode::Char('h') if ctrl => self.delete(self.prev_grapheme(), self.cursor),
+            KeyCode::Char('d') if ctrl => self.delete(self.cursor, self.next_grapheme()),
+            KeyCode::Char('w') if ctrl => self.kill(self.prev_word(), self.cursor),
+            KeyCode::Char('d') if alt => self.kill(self.cursor, self.next_word()),
+            KeyCode::Char('u') if ctrl => self.kill(self.line_start(), self.cursor),
+            KeyCode::Char('k') if ctrl => self.kill(self.cursor, self.line_end()),
+            KeyCode::Char('y') if ctrl => self.insert_raw(&self.killed.clone()),
+            // other shortcuts are the screen's.
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => self.insert_raw(c.encode_utf8(&mut [0; 4])),
+            KeyCode::Backspace if alt || ctrl => self.kill(self.prev_word(), self.cursor),
+            KeyCode::Backspace => self.delete(self.prev_grapheme(), self.cursor),
+            KeyCode::Delete => self.delete(self.cursor, self.next_grapheme()),
+            KeyCode::Left if alt || ctrl => self.cursor = self.prev_word(),
+            KeyCode::Right if alt || ctrl => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = self.prev_grapheme(),
+            KeyCode::Right => self.cursor = self.next_grapheme(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn delete(&mut self, start: usize, end: usize) {
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+    }
+
+    fn kill(&mut self, start: usize, end: usize) {
+        if start < end {
+            self.killed = self.text[start..end].to_owned();
+        }
+        self.delete(start, end);
+    }
+
+    fn prev_grapheme(&self) -> usize {
+        self.text[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    fn next_grapheme(&self) -> usize {
+        self.text[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    /// Finds the start of the word before the cursor, skipping spaces.
+    fn prev_word(&self) -> usize {
+        self.text[..self.cursor]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    /// Finds the end of the word after the cursor, skipping spaces.
+    fn next_word(&self) -> usize {
+        self.text[self.cursor..]
+            .split_word_bound_indices()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(self.text.len(), |(idx, word)| {
+                self.cursor + idx + word.len()
+            })
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor]
+            .rfind('\n')
+            .map_or(0, |idx| idx + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..]
+            .find('\n')
+            .map_or(self.text.len(), |idx| self.cursor + idx)
+    }
+
+    /// Draws the text on one line, scrolled to keep the cursor in view.
+    /// Shows the terminal's cursor if `focused`.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let width = area.width.saturating_sub(1) as usize;
+        let shown = if self.masked {
+            "*".repeat(self.text.graphemes(true).count())
+        } else {
+            self.text.replace('\n', " ")
+        };
+        let cursor_column = if self.masked {
+            self.text[..self.cursor].graphemes(true).count()
+        } else {
+            self.text[..self.cursor].width()
+        };
+
+        if cursor_column < self.scroll {
+            self.scroll = cursor_column;
+        } else if cursor_column > self.scroll + width {
+            self.scroll = cursor_column - width;
+        }
+
+        let mut column = 0;
+        let visible: String = shown
+            .graphemes(true)
+            .filter(|grapheme| {
+                let start = column;
+                column += grapheme.width();
+                // wide graphemes cut by the left edge are hidden whole.
+                start >= self.scroll && column <= self.scroll + width + 1
+            })
+            .collect();
+        frame.render_widget(Line::from(visible), area);
+
+        if focused {
+            let x = (cursor_column - self.scroll).min(area.width.saturating_sub(1) as usize);
+            frame.set_cursor_position((area.x + x as u16, area.y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{backend::TestBackend, layout::Position, Terminal};
+
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) {
+        input.handle_key(KeyEvent::from(code));
+    }
+
+    fn ctrl(input: &mut TextInput, c: char) {
+        input.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    /// Draws the input `width` columns wide, returning the row and the cursor's column.
+    fn render(input: &mut TextInput, width: u16) -> (String, u16) {
+        let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+        terminal
+            .draw(|frame| input.render(frame, frame.area(), true))
+            .unwrap();
+        let mut row = String::new();
+        let mut covered = 0;
+        for cell in terminal.backend().buffer().content() {
+            // the cells a wide character covers hold a placeholder.
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            row.push_str(cell.symbol());
+            covered = cell.symbol().width().saturating_sub(1);
+        }
+        let Position { x, .. } = terminal.get_cursor_position().unwrap();
+        (row, x)
+    }
+
+    #[test]
+    fn deletes_whole_graphemes() {
+        // `e` with a combining acute accent.
+        let mut input = TextInput::new("cafe\u{301}");
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.text(), "caf");
+
+        // a family, joined with zero width joiners.
+        let mut input = TextInput::new("hi \u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}");
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.text(), "hi ");
+
+        let mut input = TextInput::new("e\u{301}x");
+        press(&mut input, KeyCode::Home);
+        ctrl(&mut input, 'd');
+        assert_eq!(input.text(), "x");
+    }
+
+    #[test]
+    fn kills_words_and_yanks_them_back() {
+        let mut input = TextInput::new("hello big  world");
+        ctrl(&mut input, 'w');
+        assert_eq!(input.text(), "hello big  ");
+        ctrl(&mut input, 'w');
+        assert_eq!(input.text(), "hello ");
+        ctrl(&mut input, 'y');
+        assert_eq!(input.text(), "hello big  ");
+    }
+
+    #[test]
+    fn kills_to_either_end() {
+        let mut input = TextInput::new("one two three");
+        for _ in 0.."three".len() {
+            press(&mut input, KeyCode::Left);
+        }
+        ctrl(&mut input, 'k');
+        assert_eq!(input.text(), "one two ");
+        ctrl(&mut input, 'b');
+        ctrl(&mut input, 'u');
+        assert_eq!(input.text(), " ");
+        assert_eq!(input.cursor(), 0);
+        ctrl(&mut input, 'y');
+        assert_eq!(input.text(), "one two ");
+    }
+
+    #[test]
+    fn places_the_cursor_by_display_width() {
+        let mut input = TextInput::new("日本語");
+        assert_eq!(render(&mut input, 10), ("日本語    ".into(), 6));
+        press(&mut input, KeyCode::Left);
+        assert_eq!(input.cursor(), "日本".len());
+        assert_eq!(render(&mut input, 10).1, 4);
+
+        // scrolled, the wide character cut by the left edge is hidden whole.
+        let mut input = TextInput::new("ab日本語");
+        assert_eq!(render(&mut input, 6), ("本語  ".into(), 5));
+    }
+
+    #[test]
+    fn masks_every_grapheme() {
+        let mut input = TextInput::masked();
+        input.insert("pa\u{1f468}\u{200d}\u{1f469}e\u{301}");
+        assert_eq!(render(&mut input, 8), ("****    ".into(), 4));
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(render(&mut input, 8), ("***     ".into(), 3));
+    }
+}
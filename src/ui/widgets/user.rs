@@ -0,0 +1,49 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Margin, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+
+use crate::api::models::User;
+use crate::ui::theme::Theme;
+
+/// A user as an item in a list.
+#[derive(Clone)]
+pub struct UserItem<'a> {
+    pub user: &'a User,
+    /// Whether to highlight this item when rendering it.
+    pub selected: bool,
+}
+
+impl<'a> UserItem<'a> {
+    /// 1 line of name and 1 of bio, plus a box line on each side.
+    pub const HEIGHT: u16 = 4;
+
+    pub fn new(user: &'a User) -> Self {
+        Self {
+            user,
+            selected: false,
+        }
+    }
+}
+
+impl Widget for UserItem<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        let header = format!("{} @{}", self.user.display_name(), self.user.acct());
+        buf.set_line(inner.x, inner.y, &Line::from(header), inner.width);
+        if let Some(bio) = self
+            .user
+            .description
+            .as_deref()
+            .and_then(|bio| bio.lines().next())
+        {
+            buf.set_line(inner.x, inner.y + 1, &Line::from(bio), inner.width);
+        }
+
+        Theme::current().item_block(self.selected).render(area, buf);
+    }
+}